@@ -11,7 +11,8 @@ pub use bevy_derive::AppLabel;
 use bevy_ecs::{
     component::RequiredComponentsError,
     error::{DefaultErrorHandler, ErrorHandler},
-    event::{event_update_system, EventCursor},
+    event::{event_update_system, EventCursor, EventRetention},
+    index::IndexableComponent,
     intern::Interned,
     prelude::*,
     schedule::{InternedSystemSet, ScheduleBuildSettings, ScheduleLabel},
@@ -366,6 +367,35 @@ impl App {
         self
     }
 
+    /// Sets how long the events of type `T` are kept, initializing their event handling if needed.
+    ///
+    /// With another retention than the default, the cursors of the [`EventReader`]s of `T`
+    /// are registered, and the events they miss are reported by the
+    /// [`EventRegistry`](bevy_ecs::event::EventRegistry).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{event::EventRetention, prelude::*};
+    /// #
+    /// # #[derive(Event)]
+    /// # struct MyEvent;
+    /// # let mut app = App::new();
+    /// #
+    /// // Read in `FixedUpdate`, which may not run every frame.
+    /// app.set_event_retention::<MyEvent>(EventRetention::UntilRead);
+    /// ```
+    ///
+    /// [`EventReader`]: bevy_ecs::event::EventReader
+    pub fn set_event_retention<T>(&mut self, retention: EventRetention) -> &mut Self
+    where
+        T: Event,
+    {
+        self.main_mut().set_event_retention::<T>(retention);
+        self
+    }
+
     /// Inserts the [`Resource`] into the app, overwriting any existing resource of the same type.
     ///
     /// There is also an [`init_resource`](Self::init_resource) for resources that have
@@ -1047,6 +1077,17 @@ impl App {
         self.world_mut().register_disabling_component::<C>();
     }
 
+    /// Registers an [index](bevy_ecs::index) of the values of the immutable component `C`,
+    /// to find the entities holding a value with a [`QueryByIndex`](bevy_ecs::index::QueryByIndex).
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `C` exists in any archetypes.
+    pub fn register_index<C: IndexableComponent>(&mut self) -> &mut Self {
+        self.world_mut().register_index::<C>();
+        self
+    }
+
     /// Returns a reference to the main [`SubApp`]'s [`World`]. This is the same as calling
     /// [`app.main().world()`].
     ///
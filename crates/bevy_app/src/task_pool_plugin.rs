@@ -22,6 +22,9 @@ cfg_if::cfg_if! {
 }
 
 /// Setup of default task pools: [`AsyncComputeTaskPool`], [`ComputeTaskPool`], [`IoTaskPool`].
+///
+/// This also applies the world accesses requested by tasks spawned with
+/// [`World::spawn_task`](bevy_ecs::world::World::spawn_task) in [`PreUpdate`](crate::PreUpdate).
 #[derive(Default)]
 pub struct TaskPoolPlugin {
     /// Options for the [`TaskPool`](bevy_tasks::TaskPool) created at application start.
@@ -35,6 +38,9 @@ impl Plugin for TaskPoolPlugin {
 
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
         _app.add_systems(Last, tick_global_task_pools);
+
+        #[cfg(feature = "std")]
+        _app.add_systems(crate::PreUpdate, bevy_ecs::world::apply_async_tasks);
     }
 }
 
@@ -293,4 +299,34 @@ mod tests {
         compute_rx.try_recv().unwrap();
         io_rx.try_recv().unwrap();
     }
+
+    #[test]
+    fn applies_async_world_accesses() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource)]
+        struct Loaded(u32);
+
+        // Signals that the task requested a world access, which the next update applies.
+        let (queued_tx, queued_rx) = crossbeam_channel::unbounded();
+        let mut app = App::new();
+        app.add_plugins(TaskPoolPlugin::default());
+        app.world_mut().spawn_task(async move |world| {
+            let access = world.run(|_: &mut World| 1);
+            queued_tx.send(()).unwrap();
+            let value = access.await;
+            let access =
+                world.commands(move |mut commands| commands.insert_resource(Loaded(value + 1)));
+            queued_tx.send(()).unwrap();
+            access.await;
+        });
+
+        // Single-threaded task pools only run the task during updates.
+        app.update();
+        while !app.world().contains_resource::<Loaded>() {
+            queued_rx.recv().unwrap();
+            app.update();
+        }
+        assert_eq!(app.world().resource::<Loaded>().0, 2);
+    }
 }
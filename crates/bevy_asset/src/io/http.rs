@@ -0,0 +1,493 @@
+//! An [`AssetReader`] fetching assets over HTTP, with an optional on-disk cache.
+
+use crate::io::{get_meta_path, AssetReader, AssetReaderError, PathStream, Reader, VecReader};
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use core::time::Duration;
+use std::{
+    io::{ErrorKind, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+use tracing::{error, warn};
+
+/// An [`AssetReader`] fetching assets over HTTP(S).
+///
+/// Asset paths are appended to a base URL to build the URL of each asset, and meta files are
+/// fetched from the URL of their asset with `.meta` appended. Registering readers with the
+/// `http://` and `https://` base URLs as the `http` and `https` asset sources makes it possible to
+/// load assets from any URL:
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::{http::{HttpAssetReader, HttpCache}, AssetSourceBuilder}, AssetApp};
+/// # let mut app = App::new();
+/// let cache = HttpCache::new("web_asset_cache");
+/// app.register_asset_source(
+///     "https",
+///     AssetSourceBuilder::http(HttpAssetReader::new("https://").with_cache(cache)),
+/// );
+/// // Later: asset_server.load("https://example.com/textures/season.png")
+/// ```
+///
+/// Directories cannot be read from HTTP sources, so [`AssetServer::load_folder`](crate::AssetServer::load_folder)
+/// is not supported.
+///
+/// Requests block a thread of the `blocking` thread pool rather than the
+/// [`IoTaskPool`](bevy_tasks::IoTaskPool).
+#[derive(Clone)]
+pub struct HttpAssetReader {
+    base_url: String,
+    agent: ureq::Agent,
+    cache: Option<HttpCache>,
+    max_body_size: u64,
+}
+
+/// The default value of [`HttpAssetReader::max_body_size`], in bytes.
+pub const DEFAULT_MAX_BODY_SIZE: u64 = 256 * 1024 * 1024;
+
+impl HttpAssetReader {
+    /// Creates a new [`HttpAssetReader`] fetching assets from URLs starting with `base_url`, such as
+    /// `https://` or `https://cdn.example.com/game/`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            agent: Self::agent(None),
+            cache: None,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+
+    fn agent(timeout: Option<Duration>) -> ureq::Agent {
+        ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(timeout)
+            .build()
+            .into()
+    }
+
+    /// Caches fetched assets in `cache`, which is used to avoid downloading unchanged assets again
+    /// and to serve assets when the server cannot be reached.
+    pub fn with_cache(mut self, cache: HttpCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Fails requests taking longer than `timeout`.
+    ///
+    /// Cached assets are served when a request times out.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            agent: Self::agent(Some(timeout)),
+            ..self
+        }
+    }
+
+    /// Fails requests whose response body is larger than `max_body_size` bytes, instead of
+    /// [`DEFAULT_MAX_BODY_SIZE`].
+    pub fn with_max_body_size(mut self, max_body_size: u64) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Returns the size in bytes above which response bodies are rejected.
+    pub fn max_body_size(&self) -> u64 {
+        self.max_body_size
+    }
+
+    /// Returns the URL assets paths are appended to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the cache used by this reader, if any.
+    pub fn cache(&self) -> Option<&HttpCache> {
+        self.cache.as_ref()
+    }
+
+    /// Returns the URL of the asset at `path`.
+    pub fn url(&self, path: &Path) -> String {
+        let mut url = self.base_url.clone();
+        for component in path.components() {
+            if let Component::Normal(component) = component {
+                if !url.is_empty() && !url.ends_with('/') {
+                    url.push('/');
+                }
+                url.push_str(&component.to_string_lossy());
+            }
+        }
+        url
+    }
+
+    async fn fetch(&self, path: &Path, url: String) -> Result<VecReader, AssetReaderError> {
+        let agent = self.agent.clone();
+        let cache = self.cache.clone();
+        let max_body_size = self.max_body_size;
+        let bytes =
+            blocking::unblock(move || fetch_blocking(&agent, cache.as_ref(), &url, max_body_size))
+                .await;
+        match bytes {
+            Ok(bytes) => Ok(VecReader::new(bytes)),
+            Err(FetchError::NotFound) => Err(AssetReaderError::NotFound(path.to_owned())),
+            Err(FetchError::Status(status)) => Err(AssetReaderError::HttpError(status)),
+            Err(FetchError::Io(error)) => Err(error.into()),
+        }
+    }
+}
+
+enum FetchError {
+    NotFound,
+    Status(u16),
+    Io(std::io::Error),
+}
+
+fn fetch_blocking(
+    agent: &ureq::Agent,
+    cache: Option<&HttpCache>,
+    url: &str,
+    max_body_size: u64,
+) -> Result<Vec<u8>, FetchError> {
+    let cached = cache.and_then(|cache| cache.get(url));
+    let mut request = agent.get(url);
+    if let Some(cached) = &cached {
+        request = request.header("If-None-Match", &cached.etag);
+    }
+
+    let mut response = match request.call() {
+        Ok(response) => response,
+        Err(err) => {
+            return match cached {
+                Some(cached) => {
+                    warn!("Failed to fetch {url}, using the cached version instead: {err}");
+                    cached.into_bytes().map_err(FetchError::Io)
+                }
+                None => Err(FetchError::Io(err.into_io())),
+            };
+        }
+    };
+
+    match (response.status().as_u16(), cached) {
+        (200, _) => {
+            let bytes = response
+                .body_mut()
+                .with_config()
+                .limit(max_body_size)
+                .read_to_vec()
+                .map_err(|err| FetchError::Io(err.into_io()))?;
+            if let Some(cache) = cache {
+                let etag = response
+                    .headers()
+                    .get("ETag")
+                    .and_then(|etag| etag.to_str().ok());
+                let result = match etag {
+                    Some(etag) => cache.insert(url, etag, &bytes),
+                    // Without an ETag the cached version could never be revalidated.
+                    None => cache.remove(url),
+                };
+                if let Err(err) = result {
+                    warn!("Failed to update the cached version of {url}: {err}");
+                }
+            }
+            Ok(bytes)
+        }
+        (304, Some(cached)) => cached.into_bytes().map_err(FetchError::Io),
+        (404, _) => {
+            if let Some(Err(err)) = cache.map(|cache| cache.remove(url)) {
+                warn!("Failed to remove the cached version of {url}: {err}");
+            }
+            Err(FetchError::NotFound)
+        }
+        (status, _) => Err(FetchError::Status(status)),
+    }
+}
+
+impl AssetReader for HttpAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.fetch(path, self.url(path)).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let meta_path = get_meta_path(path);
+        self.fetch(&meta_path, self.url(&meta_path)).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::empty());
+        error!("Reading directories is not supported with the HttpAssetReader");
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, _path: &'a Path) -> Result<bool, AssetReaderError> {
+        error!("Reading directories is not supported with the HttpAssetReader");
+        Ok(false)
+    }
+}
+
+/// An on-disk cache for assets fetched by an [`HttpAssetReader`], keyed by URL and `ETag`.
+///
+/// Responses are only cached if they have an `ETag` header. Cached assets are revalidated with
+/// the server using the `If-None-Match` header before being used, unless the server cannot be
+/// reached.
+///
+/// [`HttpCache`] can be shared by several readers, including across runs of the app.
+#[derive(Clone, Debug)]
+pub struct HttpCache {
+    root: PathBuf,
+}
+
+/// A cached response whose `ETag` has been read, but not its body.
+struct CachedResponse {
+    etag: String,
+    /// The entry, positioned at the start of the body. Keeping it open ensures the body matches
+    /// `etag` even if the entry is replaced in the meantime.
+    file: std::fs::File,
+}
+
+impl CachedResponse {
+    fn into_bytes(mut self) -> std::io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.file.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl HttpCache {
+    /// Creates a new [`HttpCache`] storing assets in the directory at `root`, which is created when
+    /// the first asset is cached.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the directory assets are cached in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        self.root
+            .join(blake3::hash(url.as_bytes()).to_hex().as_str())
+    }
+
+    /// Returns `true` if a version of the asset at `url` is cached.
+    pub fn contains(&self, url: &str) -> bool {
+        self.entry_path(url).is_file()
+    }
+
+    /// Returns the `ETag` of the cached version of the asset at `url`, if any.
+    pub fn etag(&self, url: &str) -> Option<String> {
+        self.get(url).map(|cached| cached.etag)
+    }
+
+    fn get(&self, url: &str) -> Option<CachedResponse> {
+        let mut file = std::fs::File::open(self.entry_path(url)).ok()?;
+        let mut len = [0; 4];
+        file.read_exact(&mut len).ok()?;
+        let mut etag = alloc::vec![0; u32::from_le_bytes(len) as usize];
+        file.read_exact(&mut etag).ok()?;
+        Some(CachedResponse {
+            etag: String::from_utf8(etag).ok()?,
+            file,
+        })
+    }
+
+    fn insert(&self, url: &str, etag: &str, bytes: &[u8]) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.root)?;
+        let path = self.entry_path(url);
+        // Write to a temporary file first so that readers never observe a partial entry.
+        let temp_path = path.with_extension("tmp");
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(&(etag.len() as u32).to_le_bytes())?;
+        file.write_all(etag.as_bytes())?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        std::fs::rename(temp_path, path)
+    }
+
+    /// Removes the cached version of the asset at `url`, if any.
+    pub fn remove(&self, url: &str) -> std::io::Result<()> {
+        match std::fs::remove_file(self.entry_path(url)) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes every cached asset.
+    pub fn clear(&self) -> std::io::Result<()> {
+        match std::fs::remove_dir_all(&self.root) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, string::ToString, sync::Arc, vec};
+    use bevy_tasks::block_on;
+    use parking_lot::Mutex;
+    use std::{
+        io::BufRead,
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// The path and `If-None-Match` header of each request received by [`serve`].
+    type Requests = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+    /// Serves `connections` requests on a local port, stopping afterwards.
+    fn serve(connections: usize) -> (String, Requests, JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Requests::default();
+        let server_requests = requests.clone();
+        let server = thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let mut stream = stream.unwrap();
+                let mut reader = std::io::BufReader::new(&mut stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split(' ').nth(1).unwrap().to_string();
+                let mut if_none_match = None;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let (name, value) = header.split_once(':').unwrap();
+                    if name.eq_ignore_ascii_case("if-none-match") {
+                        if_none_match = Some(value.trim().to_string());
+                    }
+                }
+
+                let response = match (path.as_str(), if_none_match.as_deref()) {
+                    ("/a.txt", Some("\"v1\"")) => "HTTP/1.1 304 Not Modified\r\n\r\n".to_string(),
+                    ("/a.txt", _) => {
+                        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 1\r\n\r\na".to_string()
+                    }
+                    ("/b.txt", _) => "HTTP/1.1 200 OK\r\nContent-Length: 1\r\n\r\nb".to_string(),
+                    ("/error.txt", _) => {
+                        "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n"
+                            .to_string()
+                    }
+                    _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string(),
+                };
+                server_requests.lock().push((path, if_none_match));
+                stream
+                    .write_all(
+                        response
+                            .replace("\r\n\r\n", "\r\nConnection: close\r\n\r\n")
+                            .as_bytes(),
+                    )
+                    .unwrap();
+            }
+        });
+        (base_url, requests, server)
+    }
+
+    fn cache(name: &str) -> HttpCache {
+        let cache = HttpCache::new(
+            std::env::temp_dir().join(format!("bevy_asset_http_{name}_{}", std::process::id())),
+        );
+        cache.clear().unwrap();
+        cache
+    }
+
+    fn read(reader: &HttpAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            AssetReader::read(reader, Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    #[test]
+    fn revalidates_cached_assets() {
+        let (base_url, requests, server) = serve(4);
+        let cache = cache("revalidate");
+        let reader = HttpAssetReader::new(&base_url).with_cache(cache.clone());
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(
+            cache.etag(&format!("{base_url}/a.txt")).as_deref(),
+            Some("\"v1\"")
+        );
+        // Served from the cache after the server answered with `304 Not Modified`.
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        // Responses without an ETag are not cached.
+        assert_eq!(read(&reader, "b.txt").unwrap(), "b");
+        assert_eq!(read(&reader, "b.txt").unwrap(), "b");
+        assert!(!cache.contains(&format!("{base_url}/b.txt")));
+
+        server.join().unwrap();
+        assert_eq!(
+            *requests.lock(),
+            vec![
+                ("/a.txt".to_string(), None),
+                ("/a.txt".to_string(), Some("\"v1\"".to_string())),
+                ("/b.txt".to_string(), None),
+                ("/b.txt".to_string(), None),
+            ]
+        );
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn serves_cached_assets_when_offline() {
+        let (base_url, _, server) = serve(2);
+        let cache = cache("offline");
+        let reader = HttpAssetReader::new(&base_url).with_cache(cache.clone());
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(read(&reader, "b.txt").unwrap(), "b");
+        server.join().unwrap();
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert!(matches!(
+            read(&reader, "b.txt"),
+            Err(AssetReaderError::Io(_))
+        ));
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn body_size_limit() {
+        let (base_url, _, server) = serve(2);
+        let reader = HttpAssetReader::new(&base_url);
+        assert_eq!(reader.max_body_size(), DEFAULT_MAX_BODY_SIZE);
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert!(matches!(
+            read(&reader.with_max_body_size(0), "a.txt"),
+            Err(AssetReaderError::Io(_))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn http_errors() {
+        let (base_url, requests, server) = serve(3);
+        let reader = HttpAssetReader::new(format!("{base_url}/"));
+
+        assert_eq!(
+            read(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(PathBuf::from("missing.txt")))
+        );
+        assert_eq!(
+            read(&reader, "error.txt"),
+            Err(AssetReaderError::HttpError(500))
+        );
+        assert!(matches!(
+            block_on(AssetReader::read_meta_bytes(&reader, Path::new("a.txt"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        server.join().unwrap();
+        assert_eq!(requests.lock()[2].0, "/a.txt.meta");
+    }
+}
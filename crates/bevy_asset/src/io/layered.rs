@@ -0,0 +1,638 @@
+//! Layered asset sources, which stack several asset sources so that higher layers (such as mods or
+//! DLC) override assets of lower layers (such as the base game) by path.
+
+use crate::io::{
+    AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceEvent, AssetWatcher,
+    ErasedAssetReader, PathStream, Reader,
+};
+use alloc::{borrow::ToOwned, boxed::Box, string::String, sync::Arc, vec::Vec};
+use bevy_platform::collections::{HashMap, HashSet};
+use crossbeam_channel::{Receiver, Select, Sender};
+use futures_lite::StreamExt;
+use parking_lot::{Mutex, RwLock};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Records which layer of a [`LayeredAssetReader`] served each asset.
+///
+/// [`ServedLayers`] can be cloned. It is backed by an [`Arc`] so clones will share state, which makes it
+/// possible to query readers after they have been registered as an asset source.
+#[derive(Clone, Default)]
+pub struct ServedLayers {
+    names: Arc<[String]>,
+    served: Arc<RwLock<HashMap<PathBuf, usize>>>,
+}
+
+impl ServedLayers {
+    fn new(names: Vec<String>) -> Self {
+        Self {
+            names: names.into(),
+            served: Default::default(),
+        }
+    }
+
+    /// Returns the names of the layers, in increasing priority.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the name of the layer that last served the asset at `path`, if it has been read.
+    pub fn get(&self, path: &Path) -> Option<&str> {
+        let layer = *self.served.read().get(path)?;
+        Some(&self.names[layer])
+    }
+}
+
+/// An [`AssetReader`] stacking several readers ("layers"), such as the base game, a DLC pack and a
+/// user mod folder.
+///
+/// Layers are ordered in increasing priority: assets are read from the highest layer containing
+/// them, and meta files are read from that same layer, so that a mod overriding an asset without
+/// providing a meta file uses the default meta rather than the one of the overridden asset.
+/// Directories contain the entries of the directory in every layer.
+///
+/// The layer serving each asset is logged at the debug level and recorded in [`ServedLayers`].
+///
+/// Layered asset sources are usually built with [`LayeredAssetSource`].
+pub struct LayeredAssetReader {
+    layers: Vec<Box<dyn ErasedAssetReader>>,
+    served: ServedLayers,
+}
+
+impl LayeredAssetReader {
+    /// Creates a new [`LayeredAssetReader`] from named readers, in increasing priority.
+    pub fn new(layers: impl IntoIterator<Item = (String, Box<dyn ErasedAssetReader>)>) -> Self {
+        let (names, layers) = layers.into_iter().unzip();
+        Self {
+            layers,
+            served: ServedLayers::new(names),
+        }
+    }
+
+    /// Returns the record of which layer served each asset.
+    pub fn served_layers(&self) -> &ServedLayers {
+        &self.served
+    }
+
+    /// Iterates the layers from the highest priority to the lowest, with their index.
+    fn layers_by_priority(&self) -> impl Iterator<Item = (usize, &dyn ErasedAssetReader)> {
+        self.layers.iter().map(|reader| &**reader).enumerate().rev()
+    }
+
+    /// Reads the asset at `path` from the highest layer containing it, and records that layer.
+    async fn read_served<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        for (layer, reader) in self.layers_by_priority() {
+            match reader.read(path).await {
+                Ok(reader) => {
+                    debug!(
+                        "Serving {} from asset layer {}",
+                        path.display(),
+                        self.served.names[layer]
+                    );
+                    self.served.served.write().insert(path.to_owned(), layer);
+                    return Ok(reader);
+                }
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.served.served.write().remove(path);
+        Err(AssetReaderError::NotFound(path.to_owned()))
+    }
+
+    /// Returns `true` if the asset at `path` is served, or could be served, by a layer other than
+    /// `layer`.
+    async fn is_in_other_layer(&self, layer: usize, path: &Path) -> bool {
+        let served = self.served.served.read().get(path).copied();
+        if served.is_some_and(|served| served != layer) {
+            return true;
+        }
+        for (other, reader) in self.layers_by_priority() {
+            if other != layer && reader.read(path).await.is_ok() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Maps an event of the layer at index `layer` to the event of the layered source.
+    ///
+    /// Adding or removing an asset that another layer contains changes which layer serves it,
+    /// so it is reported as a modification of the asset.
+    async fn map_event(&self, layer: usize, event: AssetSourceEvent) -> AssetSourceEvent {
+        match event {
+            AssetSourceEvent::AddedAsset(path) | AssetSourceEvent::RemovedAsset(path)
+                if self.is_in_other_layer(layer, &path).await =>
+            {
+                AssetSourceEvent::ModifiedAsset(path)
+            }
+            event => event,
+        }
+    }
+}
+
+impl AssetReader for LayeredAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_served(path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        // Assets are read before their meta file, which records the layer serving them. Meta files
+        // of other layers do not apply to the asset, so only that layer is used.
+        let served = self.served.served.read().get(path).copied();
+        let layer = match served {
+            Some(layer) => layer,
+            None => {
+                self.read_served(path).await?;
+                self.served
+                    .served
+                    .read()
+                    .get(path)
+                    .copied()
+                    .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?
+            }
+        };
+        self.layers[layer].read_meta(path).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let mut found = false;
+        let mut seen = HashSet::new();
+        let mut paths = Vec::new();
+        for (_, reader) in self.layers_by_priority() {
+            match reader.read_directory(path).await {
+                Ok(mut stream) => {
+                    found = true;
+                    while let Some(path) = stream.next().await {
+                        if seen.insert(path.clone()) {
+                            paths.push(path);
+                        }
+                    }
+                }
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        if !found {
+            return Err(AssetReaderError::NotFound(path.to_owned()));
+        }
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(paths));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        let mut result = Err(AssetReaderError::NotFound(path.to_owned()));
+        for (_, reader) in self.layers_by_priority() {
+            match reader.is_directory(path).await {
+                Ok(true) => return Ok(true),
+                Ok(false) => result = Ok(false),
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        result
+    }
+}
+
+/// An [`AssetWatcher`] keeping the watchers of every layer of a layered asset source alive.
+///
+/// The events of each layer are forwarded to the layered source by a thread, which stops once every
+/// layer watcher has been dropped.
+struct LayeredAssetWatcher(
+    #[expect(dead_code, reason = "Only kept alive")] Vec<Box<dyn AssetWatcher>>,
+);
+
+impl AssetWatcher for LayeredAssetWatcher {}
+
+type ReaderFn = Box<dyn FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync>;
+type WatcherFn =
+    Box<dyn FnMut(Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>> + Send + Sync>;
+
+/// Builds a layered asset source, which serves assets from a stack of asset sources ("layers") with
+/// a [`LayeredAssetReader`].
+///
+/// Each layer is described by its own [`AssetSourceBuilder`], so any kind of source can be used as a
+/// layer. The watchers of every layer are combined, so hot reloading works for every layer. Adding
+/// or removing an asset that another layer also contains, such as a mod overriding an asset of the
+/// base game, is reported as a modification of that asset so that it is reloaded. Note that a change
+/// to an asset overridden by a higher layer still emits an event.
+///
+/// Layered sources are read-only: writers of the layers are ignored.
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::{layered::LayeredAssetSource, AssetSourceBuilder, AssetSourceId}, AssetApp};
+/// # let mut app = App::new();
+/// let layers = LayeredAssetSource::new()
+///     .with_layer("base", AssetSourceBuilder::platform_default("assets", None))
+///     .with_layer("dlc", AssetSourceBuilder::platform_default("dlc/winter", None))
+///     .with_layer("mod", AssetSourceBuilder::platform_default("mods/hd_textures", None));
+/// let served_layers = layers.served_layers();
+/// app.register_asset_source(AssetSourceId::Default, layers.build());
+/// ```
+#[derive(Default)]
+pub struct LayeredAssetSource {
+    layers: Vec<(String, AssetSourceBuilder)>,
+    served: Arc<RwLock<HashMap<PathBuf, usize>>>,
+}
+
+impl LayeredAssetSource {
+    /// Creates a new [`LayeredAssetSource`] without layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer with a higher priority than every layer added before.
+    pub fn with_layer(mut self, name: impl Into<String>, source: AssetSourceBuilder) -> Self {
+        self.layers.push((name.into(), source));
+        self
+    }
+
+    /// Returns the record of which layer served each asset of the unprocessed readers built from
+    /// this source.
+    pub fn served_layers(&self) -> ServedLayers {
+        // Layers without a reader are skipped when building readers.
+        ServedLayers {
+            names: self
+                .layers
+                .iter()
+                .filter(|(_, source)| source.reader.is_some())
+                .map(|(name, _)| name.clone())
+                .collect(),
+            served: self.served.clone(),
+        }
+    }
+
+    /// Builds the [`AssetSourceBuilder`] of the layered source, which can then be registered like
+    /// any other asset source.
+    ///
+    /// The source has a processed reader if any of the layers has one.
+    pub fn build(self) -> AssetSourceBuilder {
+        let served = self.served_layers();
+        let mut readers = Vec::new();
+        let mut processed_readers = Vec::new();
+        let mut watchers = Vec::new();
+        let mut processed_watchers = Vec::new();
+        let mut watch_warning = None;
+        let mut processed_watch_warning = None;
+        for (name, mut source) in self.layers {
+            // The index of the layer among the readers, which events of its watchers refer to.
+            let layer = source.reader.is_some().then_some(readers.len());
+            let processed_layer = source
+                .processed_reader
+                .is_some()
+                .then_some(processed_readers.len());
+            if let Some(reader) = source.reader.take() {
+                readers.push((name.clone(), reader));
+            }
+            if let Some(reader) = source.processed_reader.take() {
+                processed_readers.push((name, reader));
+            }
+            watchers.extend(source.watcher.take().map(|watcher| (layer, watcher)));
+            processed_watchers.extend(
+                source
+                    .processed_watcher
+                    .take()
+                    .map(|watcher| (processed_layer, watcher)),
+            );
+            watch_warning = watch_warning.or(source.watch_warning);
+            processed_watch_warning = processed_watch_warning.or(source.processed_watch_warning);
+        }
+
+        let readers = Arc::new(Mutex::new(readers));
+        let processed_readers = Arc::new(Mutex::new(processed_readers));
+        let new_reader = {
+            let readers = readers.clone();
+            move || {
+                let mut reader = build_reader(&mut readers.lock());
+                reader.served = served.clone();
+                reader
+            }
+        };
+        let new_processed_reader = {
+            let processed_readers = processed_readers.clone();
+            move || build_reader(&mut processed_readers.lock())
+        };
+
+        let mut builder = AssetSourceBuilder::default().with_reader({
+            let new_reader = new_reader.clone();
+            move || Box::new(new_reader())
+        });
+        if !processed_readers.lock().is_empty() {
+            builder = builder.with_processed_reader({
+                let new_processed_reader = new_processed_reader.clone();
+                move || Box::new(new_processed_reader())
+            });
+        }
+        if !watchers.is_empty() {
+            builder = builder.with_watcher(build_watcher(watchers, new_reader));
+        }
+        if !processed_watchers.is_empty() {
+            builder = builder
+                .with_processed_watcher(build_watcher(processed_watchers, new_processed_reader));
+        }
+        builder.watch_warning = watch_warning;
+        builder.processed_watch_warning = processed_watch_warning;
+        builder
+    }
+}
+
+fn build_reader(readers: &mut [(String, ReaderFn)]) -> LayeredAssetReader {
+    LayeredAssetReader::new(
+        readers
+            .iter_mut()
+            .map(|(name, reader)| (name.clone(), reader())),
+    )
+}
+
+/// Combines the watchers of the layers, given with the index of their layer among the readers
+/// built by `new_reader`, if the layer has a reader.
+fn build_watcher(
+    mut watchers: Vec<(Option<usize>, WatcherFn)>,
+    new_reader: impl Fn() -> LayeredAssetReader + Send + Sync + 'static,
+) -> impl FnMut(Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>> + Send + Sync + 'static {
+    move |sender| {
+        let mut receivers = Vec::new();
+        let watchers: Vec<_> = watchers
+            .iter_mut()
+            .filter_map(|(layer, watcher)| {
+                let (layer_sender, receiver) = crossbeam_channel::unbounded();
+                let watcher = watcher(layer_sender)?;
+                receivers.push((*layer, receiver));
+                Some(watcher)
+            })
+            .collect();
+        if watchers.is_empty() {
+            return None;
+        }
+        let reader = new_reader();
+        std::thread::spawn(move || forward_events(&reader, receivers, &sender));
+        let watcher: Box<dyn AssetWatcher> = Box::new(LayeredAssetWatcher(watchers));
+        Some(watcher)
+    }
+}
+
+/// Forwards the events of the layer watchers to `sender`, until every layer watcher has been
+/// dropped or the layered source stops listening.
+fn forward_events(
+    reader: &LayeredAssetReader,
+    mut receivers: Vec<(Option<usize>, Receiver<AssetSourceEvent>)>,
+    sender: &Sender<AssetSourceEvent>,
+) {
+    while !receivers.is_empty() {
+        let mut select = Select::new();
+        for (_, receiver) in &receivers {
+            select.recv(receiver);
+        }
+        let operation = select.select();
+        let index = operation.index();
+        let (layer, receiver) = &receivers[index];
+        let Ok(event) = operation.recv(receiver) else {
+            receivers.remove(index);
+            continue;
+        };
+        let event = match layer {
+            Some(layer) => bevy_tasks::block_on(reader.map_event(*layer, event)),
+            None => event,
+        };
+        if sender.send(event).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetSourceId,
+    };
+    use bevy_tasks::block_on;
+    use core::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn layer(assets: &[(&str, &str)], metas: &[(&str, &str)]) -> AssetSourceBuilder {
+        let dir = Dir::default();
+        for (path, text) in assets {
+            dir.insert_asset_text(Path::new(path), text);
+        }
+        for (path, text) in metas {
+            dir.insert_meta_text(Path::new(path), text);
+        }
+        AssetSourceBuilder::default()
+            .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() }))
+    }
+
+    fn read(reader: &dyn ErasedAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    fn read_meta(reader: &dyn ErasedAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        let bytes = block_on(reader.read_meta_bytes(Path::new(path)))?;
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+
+    #[test]
+    fn higher_layers_override_lower_layers() {
+        let layers = LayeredAssetSource::new()
+            .with_layer(
+                "base",
+                layer(
+                    &[
+                        ("a.txt", "base a"),
+                        ("b.txt", "base b"),
+                        ("x/c.txt", "base c"),
+                    ],
+                    &[("a.txt", "base a meta"), ("b.txt", "base b meta")],
+                ),
+            )
+            .with_layer(
+                "mod",
+                layer(
+                    &[
+                        ("a.txt", "mod a"),
+                        ("x/d.txt", "mod d"),
+                        ("y/e.txt", "mod e"),
+                    ],
+                    &[],
+                ),
+            );
+        let served = layers.served_layers();
+        assert_eq!(served.names(), ["base", "mod"]);
+        let source = layers
+            .build()
+            .build(AssetSourceId::Default, false, false)
+            .unwrap();
+        let reader = source.reader();
+
+        assert_eq!(read(reader, "a.txt").unwrap(), "mod a");
+        assert_eq!(read(reader, "b.txt").unwrap(), "base b");
+        assert_eq!(served.get(Path::new("a.txt")), Some("mod"));
+        assert_eq!(served.get(Path::new("b.txt")), Some("base"));
+        assert_eq!(served.get(Path::new("x/c.txt")), None);
+        assert!(matches!(
+            read(reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        // The base meta does not apply to the asset overridden by the mod.
+        assert!(matches!(
+            read_meta(reader, "a.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        assert_eq!(read_meta(reader, "b.txt").unwrap(), "base b meta");
+        // Meta files can be read before their asset.
+        served.served.write().clear();
+        assert!(matches!(
+            read_meta(reader, "a.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        assert_eq!(served.get(Path::new("a.txt")), Some("mod"));
+        assert_eq!(read_meta(reader, "b.txt").unwrap(), "base b meta");
+
+        let mut root: Vec<_> = block_on(async {
+            reader
+                .read_directory(Path::new(""))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        root.sort();
+        assert_eq!(
+            root,
+            ["a.txt", "b.txt", "x", "y"].map(PathBuf::from).to_vec()
+        );
+        let mut x: Vec<_> = block_on(async {
+            reader
+                .read_directory(Path::new("x"))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        x.sort();
+        assert_eq!(x, ["x/c.txt", "x/d.txt"].map(PathBuf::from).to_vec());
+        assert!(block_on(reader.is_directory(Path::new("y"))).unwrap());
+    }
+
+    #[test]
+    fn watches_every_layer() {
+        struct TestWatcher;
+        impl AssetWatcher for TestWatcher {}
+
+        fn watched_layer(path: &'static str) -> AssetSourceBuilder {
+            layer(&[], &[]).with_watcher(move |sender| {
+                sender
+                    .send(AssetSourceEvent::ModifiedAsset(path.into()))
+                    .unwrap();
+                Some(Box::new(TestWatcher))
+            })
+        }
+
+        let source = LayeredAssetSource::new()
+            .with_layer("base", watched_layer("a.txt"))
+            .with_layer("unwatched", layer(&[], &[]))
+            .with_layer("mod", watched_layer("b.txt"))
+            .build()
+            .build(AssetSourceId::Default, true, false)
+            .unwrap();
+        let receiver = source.event_receiver().unwrap();
+        let mut events: Vec<_> = (0..2)
+            .map(|_| match receiver.recv_timeout(TIMEOUT).unwrap() {
+                AssetSourceEvent::ModifiedAsset(path) => path,
+                event => panic!("unexpected event {event:?}"),
+            })
+            .collect();
+        events.sort();
+        assert_eq!(events, ["a.txt", "b.txt"].map(PathBuf::from).to_vec());
+    }
+
+    #[test]
+    fn overrides_are_reported_as_modifications() {
+        #[derive(Clone, Default)]
+        struct Watched {
+            dir: Dir,
+            sender: Arc<Mutex<Option<Sender<AssetSourceEvent>>>>,
+        }
+        struct TestWatcher;
+        impl AssetWatcher for TestWatcher {}
+
+        impl Watched {
+            fn layer(&self) -> AssetSourceBuilder {
+                let dir = self.dir.clone();
+                let sender = self.sender.clone();
+                AssetSourceBuilder::default()
+                    .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() }))
+                    .with_watcher(move |event_sender| {
+                        *sender.lock() = Some(event_sender);
+                        Some(Box::new(TestWatcher))
+                    })
+            }
+
+            fn send(&self, event: AssetSourceEvent) {
+                self.sender.lock().as_ref().unwrap().send(event).unwrap();
+            }
+        }
+
+        let base = Watched::default();
+        base.dir.insert_asset_text(Path::new("a.txt"), "base a");
+        base.dir.insert_asset_text(Path::new("b.txt"), "base b");
+        let mod_layer = Watched::default();
+        let source = LayeredAssetSource::new()
+            .with_layer("base", base.layer())
+            .with_layer("mod", mod_layer.layer())
+            .build()
+            .build(AssetSourceId::Default, true, false)
+            .unwrap();
+        let receiver = source.event_receiver().unwrap();
+        assert_eq!(read(source.reader(), "a.txt").unwrap(), "base a");
+
+        // The mod overrides an asset of the base layer.
+        mod_layer.dir.insert_asset_text(Path::new("a.txt"), "mod a");
+        mod_layer.send(AssetSourceEvent::AddedAsset("a.txt".into()));
+        assert_eq!(
+            receiver.recv_timeout(TIMEOUT).unwrap(),
+            AssetSourceEvent::ModifiedAsset("a.txt".into())
+        );
+        assert_eq!(read(source.reader(), "a.txt").unwrap(), "mod a");
+
+        // The base layer still serves the asset once the override is removed.
+        mod_layer.dir.remove_asset(Path::new("a.txt"));
+        mod_layer.send(AssetSourceEvent::RemovedAsset("a.txt".into()));
+        assert_eq!(
+            receiver.recv_timeout(TIMEOUT).unwrap(),
+            AssetSourceEvent::ModifiedAsset("a.txt".into())
+        );
+
+        // Assets that no other layer contains are added and removed as usual.
+        mod_layer.dir.insert_asset_text(Path::new("c.txt"), "mod c");
+        mod_layer.send(AssetSourceEvent::AddedAsset("c.txt".into()));
+        assert_eq!(
+            receiver.recv_timeout(TIMEOUT).unwrap(),
+            AssetSourceEvent::AddedAsset("c.txt".into())
+        );
+        base.dir.remove_asset(Path::new("b.txt"));
+        base.send(AssetSourceEvent::RemovedAsset("b.txt".into()));
+        assert_eq!(
+            receiver.recv_timeout(TIMEOUT).unwrap(),
+            AssetSourceEvent::RemovedAsset("b.txt".into())
+        );
+    }
+}
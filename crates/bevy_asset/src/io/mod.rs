@@ -11,7 +11,11 @@ pub mod embedded;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod file;
 pub mod gated;
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+pub mod http;
+pub mod layered;
 pub mod memory;
+pub mod packed;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
@@ -0,0 +1,845 @@
+//! Packed asset archives, which store many assets (and their meta files) in a single file.
+//!
+//! Archives are written with a [`PackedArchiveBuilder`], typically from the processed output of the
+//! [`AssetProcessor`](crate::processor::AssetProcessor) using
+//! [`AssetProcessor::pack_processed`](crate::processor::AssetProcessor::pack_processed), and are
+//! read back with a [`PackedAssetReader`], which can be registered as an asset source with
+//! [`AssetSourceBuilder::packed`](crate::io::AssetSourceBuilder::packed).
+//!
+//! # Format
+//!
+//! All integers are little endian.
+//!
+//! | Section | Contents                                                                    |
+//! |---------|-----------------------------------------------------------------------------|
+//! | Header  | [`PACKED_ARCHIVE_MAGIC`], version (`u32`), entry count (`u32`), index offset (`u64`) |
+//! | Data    | The (possibly compressed) bytes of every entry                              |
+//! | Index   | For each entry: path length (`u32`), `/`-separated UTF-8 path, kind (`u8`, 0 for assets and 1 for meta), compression (`u8`), offset (`u64`), stored length (`u64`), length (`u64`) |
+
+use crate::io::{
+    memory::Value, AssetReader, AssetReaderError, ErasedAssetReader, PathStream, Reader, VecReader,
+};
+use alloc::{
+    borrow::ToOwned, boxed::Box, collections::BTreeMap, string::String, sync::Arc, vec, vec::Vec,
+};
+use bevy_platform::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+
+/// The bytes every packed archive starts with.
+pub const PACKED_ARCHIVE_MAGIC: [u8; 8] = *b"BEVYPAK\0";
+
+/// The version of the packed archive format written by [`PackedArchiveBuilder`].
+pub const PACKED_ARCHIVE_VERSION: u32 = 1;
+
+const HEADER_LEN: u64 = 24;
+
+/// The compression applied to an entry of a packed archive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PackedCompression {
+    /// The entry is stored as is.
+    #[default]
+    None,
+    /// The entry is compressed with zlib.
+    #[cfg(feature = "zlib")]
+    Zlib,
+}
+
+impl PackedCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PackedCompression::None => 0,
+            #[cfg(feature = "zlib")]
+            PackedCompression::Zlib => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, PackedArchiveError> {
+        match byte {
+            0 => Ok(PackedCompression::None),
+            #[cfg(feature = "zlib")]
+            1 => Ok(PackedCompression::Zlib),
+            _ => Err(PackedArchiveError::UnsupportedCompression(byte)),
+        }
+    }
+
+    /// Compresses `bytes`, returning [`None`] if this is [`PackedCompression::None`].
+    #[cfg_attr(
+        not(feature = "zlib"),
+        expect(
+            unused_variables,
+            reason = "`bytes` is only used by compression methods behind cargo features."
+        )
+    )]
+    fn compress(self, bytes: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            PackedCompression::None => None,
+            #[cfg(feature = "zlib")]
+            PackedCompression::Zlib => {
+                use std::io::Write;
+                let mut encoder =
+                    flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                // Writing to a `Vec` cannot fail.
+                encoder.write_all(bytes).ok()?;
+                encoder.finish().ok()
+            }
+        }
+    }
+
+    fn decompress(self, stored: Vec<u8>, len: u64) -> Result<Vec<u8>, PackedArchiveError> {
+        let bytes = match self {
+            PackedCompression::None => stored,
+            #[cfg(feature = "zlib")]
+            PackedCompression::Zlib => {
+                use std::io::Read;
+                // `len` comes from the archive, so it is only trusted to bound the output: reading
+                // one byte past it is enough to detect entries that decompress to more data.
+                let mut bytes = Vec::new();
+                flate2::read::ZlibDecoder::new(stored.as_slice())
+                    .take(len.saturating_add(1))
+                    .read_to_end(&mut bytes)?;
+                bytes
+            }
+        };
+        if bytes.len() as u64 != len {
+            return Err(PackedArchiveError::InvalidIndex(
+                "entry length does not match its data",
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+/// An error that occurs while reading or writing a packed archive.
+#[derive(Error, Debug)]
+pub enum PackedArchiveError {
+    /// Encountered an I/O error while reading or writing the archive.
+    #[error("encountered an io error while accessing a packed archive: {0}")]
+    Io(#[from] std::io::Error),
+    /// The data does not start with [`PACKED_ARCHIVE_MAGIC`].
+    #[error("the data is not a packed asset archive")]
+    InvalidMagic,
+    /// The archive was written with an unsupported version of the format.
+    #[error("unsupported packed archive version {0}, expected {PACKED_ARCHIVE_VERSION}")]
+    UnsupportedVersion(u32),
+    /// The index of the archive is malformed.
+    #[error("the packed archive index is invalid: {0}")]
+    InvalidIndex(&'static str),
+    /// An entry uses a compression method that is unknown or whose cargo feature is not enabled.
+    #[error("unsupported packed archive compression method {0}, consider enabling the `zlib` cargo feature")]
+    UnsupportedCompression(u8),
+}
+
+impl From<PackedArchiveError> for AssetReaderError {
+    fn from(value: PackedArchiveError) -> Self {
+        match value {
+            PackedArchiveError::Io(error) => error.into(),
+            error => std::io::Error::new(std::io::ErrorKind::InvalidData, error).into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PackedEntry {
+    offset: u64,
+    stored_len: u64,
+    len: u64,
+    compression: PackedCompression,
+}
+
+enum ArchiveData {
+    Memory(Value),
+    #[cfg(any(unix, windows))]
+    File(Arc<std::fs::File>),
+}
+
+impl ArchiveData {
+    async fn read(&self, offset: u64, len: u64) -> std::io::Result<Vec<u8>> {
+        match self {
+            ArchiveData::Memory(value) => read_bytes(value, offset, len),
+            // Without `multi_threaded`, files are read on the calling thread, like `FileAssetReader` does.
+            #[cfg(all(any(unix, windows), not(feature = "multi_threaded")))]
+            ArchiveData::File(file) => read_file_at(file, offset, len),
+            #[cfg(all(any(unix, windows), feature = "multi_threaded"))]
+            ArchiveData::File(file) => {
+                let file = file.clone();
+                blocking::unblock(move || read_file_at(&file, offset, len)).await
+            }
+        }
+    }
+}
+
+fn read_bytes(value: &Value, offset: u64, len: u64) -> std::io::Result<Vec<u8>> {
+    let bytes = match value {
+        Value::Vec(bytes) => bytes.as_slice(),
+        Value::Static(bytes) => bytes,
+    };
+    usize::try_from(offset)
+        .ok()
+        .zip(
+            offset
+                .checked_add(len)
+                .and_then(|end| usize::try_from(end).ok()),
+        )
+        .and_then(|(start, end)| bytes.get(start..end))
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| std::io::ErrorKind::UnexpectedEof.into())
+}
+
+/// Reads `len` bytes at `offset` without moving the cursor of `file`, so that entries can be read
+/// concurrently from a shared handle.
+#[cfg(any(unix, windows))]
+fn read_file_at(file: &std::fs::File, offset: u64, len: u64) -> std::io::Result<Vec<u8>> {
+    let mut bytes = vec![0; len as usize];
+    #[cfg(unix)]
+    std::os::unix::fs::FileExt::read_exact_at(file, &mut bytes, offset)?;
+    #[cfg(windows)]
+    {
+        let mut filled = 0;
+        while filled < bytes.len() {
+            match std::os::windows::fs::FileExt::seek_read(
+                file,
+                &mut bytes[filled..],
+                offset + filled as u64,
+            )? {
+                0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+                read => filled += read,
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+/// A single packed asset archive, read with a [`PackedAssetReader`].
+///
+/// Only the index of the archive is kept in memory: entries are read from the archive when
+/// requested.
+pub struct PackedArchive {
+    data: ArchiveData,
+    assets: HashMap<PathBuf, PackedEntry>,
+    metas: HashMap<PathBuf, PackedEntry>,
+}
+
+impl PackedArchive {
+    /// Reads an archive from `bytes`, such as an archive embedded with [`include_bytes`].
+    pub fn from_bytes(bytes: impl Into<Value>) -> Result<Self, PackedArchiveError> {
+        let bytes = bytes.into();
+        let len = match &bytes {
+            Value::Vec(bytes) => bytes.len(),
+            Value::Static(bytes) => bytes.len(),
+        } as u64;
+        let header =
+            read_bytes(&bytes, 0, HEADER_LEN).map_err(|_| PackedArchiveError::InvalidMagic)?;
+        let index_offset = parse_header(&header)?.1;
+        let index_len = len
+            .checked_sub(index_offset)
+            .ok_or(PackedArchiveError::InvalidIndex(
+                "index offset is out of bounds",
+            ))?;
+        let index = read_bytes(&bytes, index_offset, index_len)?;
+        Self::new(ArchiveData::Memory(bytes), &header, &index)
+    }
+
+    /// Opens the archive file at `path`.
+    #[cfg(any(unix, windows))]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PackedArchiveError> {
+        use std::io::{Read, Seek, SeekFrom};
+        let mut file = std::fs::File::open(path)?;
+        let mut header = [0; HEADER_LEN as usize];
+        file.read_exact(&mut header)
+            .map_err(|_| PackedArchiveError::InvalidMagic)?;
+        let index_offset = parse_header(&header)?.1;
+        if index_offset > file.metadata()?.len() {
+            return Err(PackedArchiveError::InvalidIndex(
+                "index offset is out of bounds",
+            ));
+        }
+        file.seek(SeekFrom::Start(index_offset))?;
+        let mut index = Vec::new();
+        file.read_to_end(&mut index)?;
+        Self::new(ArchiveData::File(Arc::new(file)), &header, &index)
+    }
+
+    fn new(data: ArchiveData, header: &[u8], index: &[u8]) -> Result<Self, PackedArchiveError> {
+        let (entry_count, index_offset) = parse_header(header)?;
+        let mut archive = PackedArchive {
+            data,
+            assets: HashMap::default(),
+            metas: HashMap::default(),
+        };
+        let mut cursor = index;
+        for _ in 0..entry_count {
+            let path_len = take_u32(&mut cursor)? as usize;
+            let path = take(&mut cursor, path_len)?;
+            let path = core::str::from_utf8(path)
+                .map_err(|_| PackedArchiveError::InvalidIndex("entry path is not valid UTF-8"))?;
+            let kind = take(&mut cursor, 1)?[0];
+            let compression = PackedCompression::from_byte(take(&mut cursor, 1)?[0])?;
+            let entry = PackedEntry {
+                offset: take_u64(&mut cursor)?,
+                stored_len: take_u64(&mut cursor)?,
+                len: take_u64(&mut cursor)?,
+                compression,
+            };
+            if entry.offset < HEADER_LEN
+                || entry
+                    .offset
+                    .checked_add(entry.stored_len)
+                    .is_none_or(|end| end > index_offset)
+            {
+                return Err(PackedArchiveError::InvalidIndex(
+                    "entry data is out of bounds",
+                ));
+            }
+            let entries = match kind {
+                0 => &mut archive.assets,
+                1 => &mut archive.metas,
+                _ => return Err(PackedArchiveError::InvalidIndex("unknown entry kind")),
+            };
+            entries.insert(PathBuf::from(path), entry);
+        }
+        if !cursor.is_empty() {
+            return Err(PackedArchiveError::InvalidIndex(
+                "unexpected data after the last entry",
+            ));
+        }
+        Ok(archive)
+    }
+
+    /// Returns the number of assets in this archive.
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    /// Returns `true` if this archive contains no assets.
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Iterates the paths of the assets in this archive, in no particular order.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.assets.keys().map(PathBuf::as_path)
+    }
+
+    /// Returns `true` if this archive contains an asset at `path`.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.assets.contains_key(path)
+    }
+
+    async fn read_entry(&self, entry: &PackedEntry) -> Result<Vec<u8>, PackedArchiveError> {
+        let stored = self.data.read(entry.offset, entry.stored_len).await?;
+        entry.compression.decompress(stored, entry.len)
+    }
+}
+
+fn parse_header(header: &[u8]) -> Result<(u32, u64), PackedArchiveError> {
+    let mut cursor = header;
+    if take(&mut cursor, PACKED_ARCHIVE_MAGIC.len())? != PACKED_ARCHIVE_MAGIC {
+        return Err(PackedArchiveError::InvalidMagic);
+    }
+    let version = take_u32(&mut cursor)?;
+    if version != PACKED_ARCHIVE_VERSION {
+        return Err(PackedArchiveError::UnsupportedVersion(version));
+    }
+    let entry_count = take_u32(&mut cursor)?;
+    let index_offset = take_u64(&mut cursor)?;
+    if index_offset < HEADER_LEN {
+        return Err(PackedArchiveError::InvalidIndex(
+            "index offset is out of bounds",
+        ));
+    }
+    Ok((entry_count, index_offset))
+}
+
+fn take<'a>(cursor: &mut &'a [u8], len: usize) -> Result<&'a [u8], PackedArchiveError> {
+    if cursor.len() < len {
+        return Err(PackedArchiveError::InvalidIndex("unexpected end of index"));
+    }
+    let (bytes, rest) = cursor.split_at(len);
+    *cursor = rest;
+    Ok(bytes)
+}
+
+fn take_u32(cursor: &mut &[u8]) -> Result<u32, PackedArchiveError> {
+    Ok(u32::from_le_bytes(take(cursor, 4)?.try_into().unwrap()))
+}
+
+fn take_u64(cursor: &mut &[u8]) -> Result<u64, PackedArchiveError> {
+    Ok(u64::from_le_bytes(take(cursor, 8)?.try_into().unwrap()))
+}
+
+struct PackedIndex {
+    archives: Vec<PackedArchive>,
+    /// The archive serving each asset path, and its entry.
+    assets: HashMap<PathBuf, (usize, PackedEntry)>,
+    metas: HashMap<PathBuf, (usize, PackedEntry)>,
+    /// The sorted direct children (assets and directories) of each directory.
+    directories: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+/// An [`AssetReader`] serving assets from one or more [`PackedArchive`]s.
+///
+/// When several archives contain the same path, the archive added last wins. This makes it
+/// possible to ship patches as small archives that override the base archive.
+///
+/// Directories are inferred from the paths of the assets, so [`AssetServer::load_folder`](crate::AssetServer::load_folder)
+/// works as it does for loose files.
+///
+/// [`PackedAssetReader`] can be cloned. It is backed by an [`Arc`] so clones will share the open archives.
+#[derive(Clone)]
+pub struct PackedAssetReader {
+    index: Arc<PackedIndex>,
+}
+
+impl PackedAssetReader {
+    /// Creates a new [`PackedAssetReader`] serving the assets of `archives`, in increasing priority.
+    pub fn new(archives: impl IntoIterator<Item = PackedArchive>) -> Self {
+        let archives: Vec<_> = archives.into_iter().collect();
+        let mut assets = <HashMap<_, _>>::default();
+        let mut metas = <HashMap<_, _>>::default();
+        for (i, archive) in archives.iter().enumerate() {
+            for (path, entry) in &archive.assets {
+                assets.insert(path.clone(), (i, *entry));
+                // A meta file from a lower-priority archive must not apply to an overriding asset.
+                metas.remove(path);
+            }
+            for (path, entry) in &archive.metas {
+                metas.insert(path.clone(), (i, *entry));
+            }
+        }
+
+        let mut children = <HashMap<PathBuf, HashSet<PathBuf>>>::default();
+        children.insert(PathBuf::new(), HashSet::default());
+        for path in assets.keys() {
+            let mut child = path.as_path();
+            while let Some(parent) = child.parent() {
+                let inserted = children
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .insert(child.to_owned());
+                if !inserted {
+                    break;
+                }
+                child = parent;
+            }
+        }
+        let directories = children
+            .into_iter()
+            .map(|(path, children)| {
+                let mut children: Vec<_> = children.into_iter().collect();
+                children.sort();
+                (path, children)
+            })
+            .collect();
+
+        Self {
+            index: Arc::new(PackedIndex {
+                archives,
+                assets,
+                metas,
+                directories,
+            }),
+        }
+    }
+
+    /// Opens the archive files at `paths` and creates a [`PackedAssetReader`] serving their assets,
+    /// in increasing priority.
+    #[cfg(any(unix, windows))]
+    pub fn open<P: AsRef<Path>>(
+        paths: impl IntoIterator<Item = P>,
+    ) -> Result<Self, PackedArchiveError> {
+        let archives = paths
+            .into_iter()
+            .map(PackedArchive::open)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(archives))
+    }
+
+    /// Returns the archives served by this reader, in increasing priority.
+    pub fn archives(&self) -> &[PackedArchive] {
+        &self.index.archives
+    }
+
+    /// Returns the index of the archive serving the asset at `path`, if any.
+    pub fn archive_of(&self, path: &Path) -> Option<usize> {
+        self.index.assets.get(path).map(|(archive, _)| *archive)
+    }
+
+    async fn read_entry(
+        &self,
+        entries: &HashMap<PathBuf, (usize, PackedEntry)>,
+        path: &Path,
+    ) -> Result<VecReader, AssetReaderError> {
+        let (archive, entry) = entries
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        let bytes = self.index.archives[*archive].read_entry(entry).await?;
+        Ok(VecReader::new(bytes))
+    }
+}
+
+impl AssetReader for PackedAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_entry(&self.index.assets, path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_entry(&self.index.metas, path).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let children = self
+            .index
+            .directories
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children.clone()));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        if self.index.directories.contains_key(path) {
+            Ok(true)
+        } else if self.index.assets.contains_key(path) {
+            Ok(false)
+        } else {
+            Err(AssetReaderError::NotFound(path.to_owned()))
+        }
+    }
+}
+
+struct PendingEntry {
+    stored: Vec<u8>,
+    len: u64,
+    compression: PackedCompression,
+}
+
+/// Builds a packed archive, which can then be read with a [`PackedArchive`].
+///
+/// Entries are kept in memory until the archive is written with [`PackedArchiveBuilder::write`].
+#[derive(Default)]
+pub struct PackedArchiveBuilder {
+    assets: BTreeMap<String, PendingEntry>,
+    metas: BTreeMap<String, PendingEntry>,
+}
+
+impl PackedArchiveBuilder {
+    /// Creates a new empty [`PackedArchiveBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the asset `bytes` at `path`, replacing any asset previously added at that path.
+    ///
+    /// The entry is stored uncompressed if `compression` does not make it smaller.
+    pub fn insert_asset(&mut self, path: &Path, bytes: &[u8], compression: PackedCompression) {
+        self.assets
+            .insert(normalize_path(path), PendingEntry::new(bytes, compression));
+    }
+
+    /// Adds the asset meta `bytes` for the asset at `path`, replacing any meta previously added for
+    /// that path. This _should not_ include storage specific extensions like `.meta`.
+    ///
+    /// The entry is stored uncompressed if `compression` does not make it smaller.
+    pub fn insert_meta(&mut self, path: &Path, bytes: &[u8], compression: PackedCompression) {
+        self.metas
+            .insert(normalize_path(path), PendingEntry::new(bytes, compression));
+    }
+
+    /// Returns the number of assets added to this builder.
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    /// Returns `true` if no asset has been added to this builder.
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Adds every asset (and its meta, if any) in the directory at `path` of `reader`, recursively.
+    pub async fn insert_directory(
+        &mut self,
+        reader: &dyn ErasedAssetReader,
+        path: &Path,
+        compression: PackedCompression,
+    ) -> Result<(), AssetReaderError> {
+        use futures_lite::StreamExt;
+
+        let mut directories = vec![path.to_owned()];
+        while let Some(directory) = directories.pop() {
+            let mut entries = reader.read_directory(&directory).await?;
+            while let Some(entry) = entries.next().await {
+                if reader.is_directory(&entry).await? {
+                    directories.push(entry);
+                    continue;
+                }
+                let mut bytes = Vec::new();
+                reader.read(&entry).await?.read_to_end(&mut bytes).await?;
+                self.insert_asset(&entry, &bytes, compression);
+                match reader.read_meta_bytes(&entry).await {
+                    Ok(meta) => self.insert_meta(&entry, &meta, compression),
+                    Err(AssetReaderError::NotFound(_)) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the archive to `writer`.
+    pub fn write(&self, mut writer: impl std::io::Write) -> Result<(), PackedArchiveError> {
+        let entries = self
+            .assets
+            .iter()
+            .map(|(path, entry)| (path, 0u8, entry))
+            .chain(self.metas.iter().map(|(path, entry)| (path, 1u8, entry)));
+        let entry_count = u32::try_from(self.assets.len() + self.metas.len())
+            .map_err(|_| PackedArchiveError::InvalidIndex("too many entries"))?;
+        let data_len: u64 = entries
+            .clone()
+            .map(|(_, _, entry)| entry.stored.len() as u64)
+            .sum();
+
+        writer.write_all(&PACKED_ARCHIVE_MAGIC)?;
+        writer.write_all(&PACKED_ARCHIVE_VERSION.to_le_bytes())?;
+        writer.write_all(&entry_count.to_le_bytes())?;
+        writer.write_all(&(HEADER_LEN + data_len).to_le_bytes())?;
+        for (_, _, entry) in entries.clone() {
+            writer.write_all(&entry.stored)?;
+        }
+        let mut offset = HEADER_LEN;
+        for (path, kind, entry) in entries {
+            writer.write_all(&(path.len() as u32).to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&[kind, entry.compression.to_byte()])?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&(entry.stored.len() as u64).to_le_bytes())?;
+            writer.write_all(&entry.len.to_le_bytes())?;
+            offset += entry.stored.len() as u64;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the archive to a new [`Vec<u8>`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // Writing to a `Vec` cannot fail.
+        self.write(&mut bytes).unwrap();
+        bytes
+    }
+}
+
+impl PendingEntry {
+    fn new(bytes: &[u8], compression: PackedCompression) -> Self {
+        match compression.compress(bytes) {
+            Some(stored) if stored.len() < bytes.len() => Self {
+                stored,
+                len: bytes.len() as u64,
+                compression,
+            },
+            _ => Self {
+                stored: bytes.to_vec(),
+                len: bytes.len() as u64,
+                compression: PackedCompression::None,
+            },
+        }
+    }
+}
+
+/// Returns `path` with `/` separators, which is how paths are stored in archives.
+fn normalize_path(path: &Path) -> String {
+    let mut normalized = String::new();
+    for component in path.components() {
+        if let Component::Normal(component) = component {
+            if !normalized.is_empty() {
+                normalized.push('/');
+            }
+            normalized.push_str(&component.to_string_lossy());
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::{Dir, MemoryAssetReader};
+    use bevy_tasks::block_on;
+    use futures_lite::StreamExt;
+
+    fn read(reader: &PackedAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            AssetReader::read(reader, Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    fn read_directory(reader: &PackedAssetReader, path: &str) -> Vec<PathBuf> {
+        block_on(async {
+            AssetReader::read_directory(reader, Path::new(path))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        })
+    }
+
+    #[test]
+    fn read_packed_archive() {
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("a.txt"), "a");
+        dir.insert_meta_text(Path::new("a.txt"), "a meta");
+        dir.insert_asset_text(Path::new("x/b.txt"), "b");
+        dir.insert_asset_text(Path::new("x/y/c.txt"), "c");
+        let mut builder = PackedArchiveBuilder::new();
+        block_on(builder.insert_directory(
+            &MemoryAssetReader { root: dir },
+            Path::new(""),
+            PackedCompression::None,
+        ))
+        .unwrap();
+        assert_eq!(builder.len(), 3);
+
+        let archive = PackedArchive::from_bytes(builder.to_bytes()).unwrap();
+        assert_eq!(archive.len(), 3);
+        assert!(archive.contains(Path::new("x/y/c.txt")));
+        let reader = PackedAssetReader::new([archive]);
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(read(&reader, "x/y/c.txt").unwrap(), "c");
+        assert_eq!(
+            block_on(AssetReader::read_meta_bytes(&reader, Path::new("a.txt"))).unwrap(),
+            b"a meta"
+        );
+        assert!(matches!(
+            block_on(AssetReader::read_meta_bytes(&reader, Path::new("x/b.txt"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        assert!(matches!(
+            read(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        assert_eq!(
+            read_directory(&reader, ""),
+            [PathBuf::from("a.txt"), PathBuf::from("x")]
+        );
+        assert_eq!(
+            read_directory(&reader, "x"),
+            [PathBuf::from("x/b.txt"), PathBuf::from("x/y")]
+        );
+        assert!(block_on(AssetReader::is_directory(&reader, Path::new("x/y"))).unwrap());
+        assert!(!block_on(AssetReader::is_directory(&reader, Path::new("x/b.txt"))).unwrap());
+    }
+
+    #[test]
+    fn later_archives_override_earlier_ones() {
+        let mut base = PackedArchiveBuilder::new();
+        base.insert_asset(Path::new("a.txt"), b"base a", PackedCompression::None);
+        base.insert_meta(Path::new("a.txt"), b"base meta", PackedCompression::None);
+        base.insert_asset(Path::new("b.txt"), b"base b", PackedCompression::None);
+        let mut patch = PackedArchiveBuilder::new();
+        patch.insert_asset(Path::new("a.txt"), b"patched a", PackedCompression::None);
+
+        let reader = PackedAssetReader::new([
+            PackedArchive::from_bytes(base.to_bytes()).unwrap(),
+            PackedArchive::from_bytes(patch.to_bytes()).unwrap(),
+        ]);
+        assert_eq!(read(&reader, "a.txt").unwrap(), "patched a");
+        assert_eq!(read(&reader, "b.txt").unwrap(), "base b");
+        assert_eq!(reader.archive_of(Path::new("a.txt")), Some(1));
+        assert_eq!(reader.archive_of(Path::new("b.txt")), Some(0));
+        // The base meta file does not apply to the patched asset.
+        assert!(matches!(
+            block_on(AssetReader::read_meta_bytes(&reader, Path::new("a.txt"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+    }
+
+    #[cfg(any(unix, windows))]
+    #[test]
+    fn read_archive_file() {
+        let mut builder = PackedArchiveBuilder::new();
+        builder.insert_asset(Path::new("a.txt"), b"a", PackedCompression::None);
+        builder.insert_asset(Path::new("b.txt"), b"b", PackedCompression::None);
+        let path =
+            std::env::temp_dir().join(alloc::format!("bevy_packed_{}.pak", std::process::id()));
+        std::fs::write(&path, builder.to_bytes()).unwrap();
+
+        let reader = PackedAssetReader::open([&path]).unwrap();
+        // Entries are read at their offset, whatever was read before.
+        let b = read(&reader, "b.txt");
+        let a = read(&reader, "a.txt");
+        drop(reader);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(b.unwrap(), "b");
+        assert_eq!(a.unwrap(), "a");
+    }
+
+    #[test]
+    fn invalid_archives() {
+        assert!(matches!(
+            PackedArchive::from_bytes(b"not an archive".to_vec()),
+            Err(PackedArchiveError::InvalidMagic)
+        ));
+
+        let mut builder = PackedArchiveBuilder::new();
+        builder.insert_asset(Path::new("a.txt"), b"a", PackedCompression::None);
+        let mut bytes = builder.to_bytes();
+        bytes.truncate(bytes.len() - 1);
+        assert!(matches!(
+            PackedArchive::from_bytes(bytes),
+            Err(PackedArchiveError::InvalidIndex(_))
+        ));
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn compressed_entries() {
+        let text = "compressible ".repeat(100);
+        let mut builder = PackedArchiveBuilder::new();
+        builder.insert_asset(Path::new("a.txt"), text.as_bytes(), PackedCompression::Zlib);
+        // Not worth compressing, so stored as is.
+        builder.insert_asset(Path::new("b.txt"), b"b", PackedCompression::Zlib);
+        let bytes = builder.to_bytes();
+        assert!(bytes.len() < text.len());
+
+        let reader = PackedAssetReader::new([PackedArchive::from_bytes(bytes).unwrap()]);
+        assert_eq!(read(&reader, "a.txt").unwrap(), text);
+        assert_eq!(read(&reader, "b.txt").unwrap(), "b");
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn forged_entry_lengths() {
+        let text = "compressible ".repeat(100);
+        let mut builder = PackedArchiveBuilder::new();
+        builder.insert_asset(Path::new("a.txt"), text.as_bytes(), PackedCompression::Zlib);
+        let bytes = builder.to_bytes();
+
+        // The length of the only entry is the last field of the index.
+        let forge = |len: u64| {
+            let mut bytes = bytes.clone();
+            let at = bytes.len() - 8;
+            bytes[at..].copy_from_slice(&len.to_le_bytes());
+            let archive = PackedArchive::from_bytes(bytes).unwrap();
+            let entry = archive.assets[Path::new("a.txt")];
+            block_on(archive.read_entry(&entry))
+        };
+        assert_eq!(forge(text.len() as u64).unwrap(), text.as_bytes());
+        for len in [0, text.len() as u64 - 1, text.len() as u64 + 1, u64::MAX] {
+            assert!(matches!(
+                forge(len),
+                Err(PackedArchiveError::InvalidIndex(_))
+            ));
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    io::{processor_gated::ProcessorGatedReader, AssetSourceEvent, AssetWatcher},
+    io::{
+        packed::PackedAssetReader, processor_gated::ProcessorGatedReader, AssetSourceEvent,
+        AssetWatcher,
+    },
     processor::AssetProcessorData,
 };
 use alloc::{
@@ -291,6 +294,26 @@ impl AssetSourceBuilder {
         self
     }
 
+    /// Returns a builder serving both unprocessed and processed assets from the given [`PackedAssetReader`],
+    /// such as the processed assets of a shipping build packed with
+    /// [`AssetProcessor::pack_processed`](crate::processor::AssetProcessor::pack_processed).
+    ///
+    /// Packed sources are read-only and cannot be watched.
+    pub fn packed(reader: PackedAssetReader) -> Self {
+        let processed_reader = reader.clone();
+        Self::default()
+            .with_reader(move || Box::new(reader.clone()))
+            .with_processed_reader(move || Box::new(processed_reader.clone()))
+    }
+
+    /// Returns a builder serving assets fetched over HTTP by the given [`HttpAssetReader`](crate::io::http::HttpAssetReader).
+    ///
+    /// HTTP sources are read-only, cannot be watched and are not processed.
+    #[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+    pub fn http(reader: super::http::HttpAssetReader) -> Self {
+        Self::default().with_reader(move || Box::new(reader.clone()))
+    }
+
     /// Returns a builder containing the "platform default source" for the given `path` and `processed_path`.
     /// For most platforms, this will use [`FileAssetReader`](crate::io::file::FileAssetReader) / [`FileAssetWriter`](crate::io::file::FileAssetWriter),
     /// but some platforms (such as Android) have their own default readers / writers / watchers.
@@ -256,6 +256,11 @@ pub struct AssetPlugin {
     /// Approved folders are [`AssetPlugin::file_path`] and the folder of each
     /// [`AssetSource`](io::AssetSource). Subfolders within these folders are also valid.
     pub unapproved_path_mode: UnapprovedPathMode,
+    /// The maximum number of asset loads that may run at once, or [`None`] for no limit. Loads requested beyond this
+    /// limit are queued and started in [`LoadPriority`] order.
+    ///
+    /// This can be changed at runtime with [`AssetServer::set_max_concurrent_loads`].
+    pub max_concurrent_loads: Option<usize>,
 }
 
 /// Determines how to react to attempts to load assets not inside the approved folders.
@@ -332,6 +337,7 @@ impl Default for AssetPlugin {
             watch_for_changes_override: None,
             meta_check: AssetMetaCheck::default(),
             unapproved_path_mode: UnapprovedPathMode::default(),
+            max_concurrent_loads: None,
         }
     }
 }
@@ -409,6 +415,9 @@ impl Plugin for AssetPlugin {
                 }
             }
         }
+        app.world()
+            .resource::<AssetServer>()
+            .set_max_concurrent_loads(self.max_concurrent_loads);
         app.insert_resource(embedded)
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
@@ -676,8 +685,9 @@ mod tests {
             AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, LoadState, UnapprovedPathMode,
+        Asset, AssetApp, AssetDependencyNode, AssetEvent, AssetId, AssetLoadError,
+        AssetLoadFailedEvent, AssetPath, AssetPlugin, AssetServer, Assets, LoadPriority, LoadState,
+        UnapprovedPathMode,
     };
     use alloc::{
         boxed::Box,
@@ -1573,6 +1583,149 @@ mod tests {
         assert_eq!(events, expected_events);
     }
 
+    #[test]
+    fn load_priorities_and_cancellation() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+        let paths = ["a.cool.ron", "b.cool.ron", "c.cool.ron", "d.cool.ron"];
+        for path in paths {
+            dir.insert_asset_text(Path::new(path), SIMPLE_TEXT);
+        }
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        asset_server.set_max_concurrent_loads(Some(1));
+
+        let a: Handle<CoolText> = asset_server.load(paths[0]);
+        let b: Handle<CoolText> = asset_server.load(paths[1]);
+        let c: Handle<CoolText> = asset_server.load(paths[2]);
+        let d: Handle<CoolText> = asset_server.load(paths[3]);
+        let d_id = d.id();
+        assert_eq!(asset_server.get_load_priority(&a), None);
+        assert_eq!(asset_server.queued_load_count(), 3);
+        assert!(asset_server.set_load_priority(&c, LoadPriority(5)));
+        assert_eq!(asset_server.get_load_priority(&c), Some(LoadPriority(5)));
+
+        // dropping the only handle to a queued asset cancels its load
+        drop(d);
+        run_app_until(&mut app, |_| {
+            (asset_server.queued_load_count() == 2).then_some(())
+        });
+        assert!(asset_server.get_load_state(d_id).is_none());
+
+        gate_opener.open(paths[0]);
+        run_app_until(&mut app, |world| {
+            get::<CoolText>(world, a.id())?;
+            asset_server.get_load_priority(&c).is_none().then_some(())
+        });
+        // c has a higher priority, so it runs before b
+        assert_eq!(
+            asset_server.get_load_priority(&b),
+            Some(LoadPriority::DEFAULT)
+        );
+        assert_eq!(asset_server.running_load_count(), 1);
+
+        gate_opener.open(paths[2]);
+        run_app_until(&mut app, |world| {
+            get::<CoolText>(world, c.id())?;
+            asset_server.get_load_priority(&b).is_none().then_some(())
+        });
+
+        gate_opener.open(paths[1]);
+        run_app_until(&mut app, |world| {
+            get::<CoolText>(world, b.id())?;
+            (asset_server.running_load_count() == 0).then_some(())
+        });
+        assert_eq!(asset_server.queued_load_count(), 0);
+    }
+
+    #[test]
+    fn dependency_graph() {
+        let dir = Dir::default();
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+        let b_path = "b.cool.ron";
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), SIMPLE_TEXT);
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader);
+        for path in [a_path, b_path, c_path] {
+            gate_opener.open(path);
+        }
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load(a_path);
+        run_app_until(&mut app, |_| {
+            asset_server.is_loaded_with_dependencies(&a).then_some(())
+        });
+        let b = asset_server.get_handle::<CoolText>(b_path).unwrap();
+        let c = asset_server.get_handle::<CoolText>(c_path).unwrap();
+
+        let mut a_deps = asset_server.get_dependencies(&a).unwrap();
+        a_deps.sort_by_key(|id| asset_server.get_path(*id).unwrap().to_string());
+        assert_eq!(a_deps, vec![b.id().untyped(), c.id().untyped()]);
+        let mut b_dependents = asset_server.get_dependents(&b);
+        b_dependents.sort_by_key(|id| asset_server.get_path(*id).unwrap().to_string());
+        assert_eq!(b_dependents, vec![a.id().untyped(), c.id().untyped()]);
+
+        // the graph around c reaches its dependency b and its dependent a
+        let graph = asset_server.get_dependency_graph(&c).unwrap();
+        let paths = graph
+            .nodes
+            .iter()
+            .map(AssetDependencyNode::label)
+            .collect::<Vec<_>>();
+        assert_eq!(paths, [c_path, a_path, b_path]);
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(
+            graph.dependents_of(b.id().untyped()).count(),
+            2,
+            "both a and c depend on b"
+        );
+        assert_eq!(
+            graph.to_dot(),
+            "digraph assets {
+    n0 [label=\"c.cool.ron\", style=bold, tooltip=\"Loaded\"];
+    n1 [label=\"a.cool.ron\", tooltip=\"Loaded\"];
+    n2 [label=\"b.cool.ron\", tooltip=\"Loaded\"];
+    n0 -> n2;
+    n1 -> n0;
+    n1 -> n2;
+}
+"
+        );
+        let ron = ron::ser::to_string(&graph).unwrap();
+        assert!(ron.contains(r#"path:Some("a.cool.ron"),load_state:"Loaded""#));
+        let root = c.id().untyped().to_string();
+        assert!(ron.starts_with(&alloc::format!("(root:{root:?},")));
+    }
+
     #[test]
     fn load_folder() {
         // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
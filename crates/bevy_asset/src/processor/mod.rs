@@ -45,9 +45,10 @@ pub use process::*;
 
 use crate::{
     io::{
+        packed::{PackedArchiveBuilder, PackedCompression},
         AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent, AssetSourceId,
         AssetSources, AssetWriterError, ErasedAssetReader, ErasedAssetWriter,
-        MissingAssetSourceError,
+        MissingAssetSourceError, MissingProcessedAssetReaderError,
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
@@ -99,6 +100,8 @@ pub struct AssetProcessor {
 pub struct AssetProcessorData {
     pub(crate) asset_infos: async_lock::RwLock<ProcessorAssetInfos>,
     log: async_lock::RwLock<Option<ProcessorTransactionLog>>,
+    /// The path of the [`ProcessorTransactionLog`] file.
+    log_path: PathBuf,
     processors: RwLock<HashMap<&'static str, Arc<dyn ErasedProcessor>>>,
     /// Default processors for file extensions
     default_processors: RwLock<HashMap<Box<str>, &'static str>>,
@@ -113,7 +116,14 @@ pub struct AssetProcessorData {
 impl AssetProcessor {
     /// Creates a new [`AssetProcessor`] instance.
     pub fn new(source: &mut AssetSourceBuilders) -> Self {
-        let data = Arc::new(AssetProcessorData::new(source.build_sources(true, false)));
+        Self::new_with_log_path(source, ProcessorTransactionLog::default_path())
+    }
+
+    /// Creates a new [`AssetProcessor`] instance writing its [`ProcessorTransactionLog`] to `log_path`.
+    pub(crate) fn new_with_log_path(source: &mut AssetSourceBuilders, log_path: PathBuf) -> Self {
+        let mut data = AssetProcessorData::new(source.build_sources(true, false));
+        data.log_path = log_path;
+        let data = Arc::new(data);
         // The asset processor uses its own asset server with its own id space
         let mut sources = source.build_sources(false, false);
         sources.gate_on_processor(data.clone());
@@ -262,6 +272,63 @@ impl AssetProcessor {
         }
     }
 
+    /// Waits until processing has finished, then packs the processed assets of the given source
+    /// (and their meta files) into a [`PackedArchiveBuilder`], ready to be written for a shipping build.
+    ///
+    /// The resulting archive can be served with a [`PackedAssetReader`](crate::io::packed::PackedAssetReader).
+    pub async fn pack_processed<'a>(
+        &self,
+        source: impl Into<AssetSourceId<'a>>,
+        compression: PackedCompression,
+    ) -> Result<PackedArchiveBuilder, PackProcessedError> {
+        self.data.wait_until_finished().await;
+        let reader = self.get_source(source)?.processed_reader()?;
+        let mut builder = PackedArchiveBuilder::new();
+        builder
+            .insert_directory(reader, Path::new(""), compression)
+            .await?;
+        Ok(builder)
+    }
+
+    /// Waits until processing has finished, then reports the assets in the given source that no processed asset
+    /// references, either as a process dependency or as a dependency of the asset loaded by
+    /// [`ProcessContext::load_source_asset`] (for example through [`LoadContext::load`](crate::LoadContext::load)).
+    ///
+    /// Dependencies of loaded assets are only known for assets processed during this run, and assets that are not
+    /// loaded while being processed have none. Assets only referenced by those will be listed as well. Use
+    /// [`AssetServer::get_dependency_graph`] in the running app to check those.
+    pub async fn unreferenced_assets<'a>(
+        &self,
+        source: impl Into<AssetSourceId<'a>>,
+    ) -> Result<UnreferencedAssetReport, MissingAssetSourceError> {
+        let source = source.into();
+        self.get_source(source.clone())?;
+        self.data.wait_until_finished().await;
+        let infos = self.data.asset_infos.read().await;
+        let loaded_dependencies = infos
+            .infos
+            .values()
+            .flat_map(|info| &info.loaded_dependencies)
+            .collect::<HashSet<_>>();
+        let mut total = 0;
+        let mut unreferenced = infos
+            .infos
+            .iter()
+            .filter(|(path, _)| *path.source() == source)
+            .inspect(|_| total += 1)
+            .filter(|(path, info)| {
+                info.dependents.is_empty() && !loaded_dependencies.contains(path)
+            })
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        unreferenced.sort_by(|a, b| a.path().cmp(b.path()));
+        Ok(UnreferencedAssetReport {
+            source: source.into_owned(),
+            total,
+            unreferenced,
+        })
+    }
+
     /// Writes the default meta file for the provided `path`.
     ///
     /// This function generates the appropriate meta file to process `path` with the default
@@ -925,12 +992,13 @@ impl AssetProcessor {
         self.log_begin_processing(asset_path).await;
         if let Some(processor) = processor {
             let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
-            let mut processed_meta = {
+            let (mut processed_meta, loaded_dependencies) = {
                 let mut context =
                     ProcessContext::new(self, asset_path, &asset_bytes, &mut new_processed_info);
-                processor
+                let processed_meta = processor
                     .process(&mut context, source_meta, &mut *writer)
-                    .await?
+                    .await?;
+                (processed_meta, context.loaded_dependencies)
             };
 
             writer
@@ -955,6 +1023,8 @@ impl AssetProcessor {
                 .write_meta_bytes(path, &meta_bytes)
                 .await
                 .map_err(writer_err)?;
+            self.set_loaded_dependencies(asset_path, loaded_dependencies)
+                .await;
         } else {
             processed_writer
                 .write_bytes(path, &asset_bytes)
@@ -966,14 +1036,27 @@ impl AssetProcessor {
                 .write_meta_bytes(path, &meta_bytes)
                 .await
                 .map_err(writer_err)?;
+            self.set_loaded_dependencies(asset_path, HashSet::default())
+                .await;
         }
         self.log_end_processing(asset_path).await;
 
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Records the dependencies of the loaded source asset at `asset_path`, which are reported as
+    /// references by [`AssetProcessor::unreferenced_assets`].
+    async fn set_loaded_dependencies(
+        &self,
+        asset_path: &AssetPath<'static>,
+        loaded_dependencies: HashSet<AssetPath<'static>>,
+    ) {
+        let mut infos = self.data.asset_infos.write().await;
+        infos.get_or_insert(asset_path.clone()).loaded_dependencies = loaded_dependencies;
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
-        if let Err(err) = ProcessorTransactionLog::validate().await {
+        if let Err(err) = ProcessorTransactionLog::validate(&self.data.log_path).await {
             let state_is_valid = match err {
                 ValidateLogError::ReadLogError(err) => {
                     error!("Failed to read processor log file. Processed assets cannot be validated so they must be re-generated {err}");
@@ -1051,7 +1134,7 @@ impl AssetProcessor {
             }
         }
         let mut log = self.data.log.write().await;
-        *log = match ProcessorTransactionLog::new().await {
+        *log = match ProcessorTransactionLog::new(&self.data.log_path).await {
             Ok(log) => Some(log),
             Err(err) => panic!("Failed to initialize asset processor log. This cannot be recovered. Try restarting. If that doesn't work, try deleting processed asset folder. {}", err),
         };
@@ -1076,6 +1159,7 @@ impl AssetProcessorData {
             initialized_receiver,
             state: async_lock::RwLock::new(ProcessorState::Initializing),
             log: Default::default(),
+            log_path: ProcessorTransactionLog::default_path(),
             processors: Default::default(),
             asset_infos: Default::default(),
             default_processors: Default::default(),
@@ -1190,6 +1274,8 @@ pub(crate) struct ProcessorAssetInfo {
     processed_info: Option<ProcessedInfo>,
     /// Paths of assets that depend on this asset when they are being processed.
     dependents: HashSet<AssetPath<'static>>,
+    /// Paths of the assets the loaded source asset depended on when this asset was last processed.
+    loaded_dependencies: HashSet<AssetPath<'static>>,
     status: Option<ProcessStatus>,
     /// A lock that controls read/write access to processed asset files. The lock is shared for both the asset bytes and the meta bytes.
     /// _This lock must be locked whenever a read or write to processed assets occurs_
@@ -1214,6 +1300,7 @@ impl Default for ProcessorAssetInfo {
         Self {
             processed_info: Default::default(),
             dependents: Default::default(),
+            loaded_dependencies: Default::default(),
             file_transaction_lock: Default::default(),
             status: None,
             status_sender,
@@ -1422,6 +1509,7 @@ impl ProcessorAssetInfos {
             let dependents: Vec<AssetPath<'static>> = {
                 let new_info = self.get_or_insert(new.clone());
                 new_info.processed_info = info.processed_info;
+                new_info.loaded_dependencies = info.loaded_dependencies;
                 new_info.status = info.status;
                 // Ensure things waiting on the new path are informed of the status of this asset
                 if let Some(status) = new_info.status {
@@ -1464,6 +1552,45 @@ pub enum ProcessorState {
     Finished,
 }
 
+/// An error that occurs when packing processed assets with [`AssetProcessor::pack_processed`].
+#[derive(Error, Debug)]
+pub enum PackProcessedError {
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingProcessedAssetReader(#[from] MissingProcessedAssetReaderError),
+    #[error("Failed to read processed asset: {0}")]
+    AssetReaderError(#[from] AssetReaderError),
+}
+
+/// The assets of an [`AssetSource`] that no processed asset references, as reported by
+/// [`AssetProcessor::unreferenced_assets`]. Its [`Display`](core::fmt::Display) implementation lists one path per line.
+#[derive(Clone, Debug)]
+pub struct UnreferencedAssetReport {
+    /// The source that was scanned.
+    pub source: AssetSourceId<'static>,
+    /// The number of assets in the source.
+    pub total: usize,
+    /// The assets that are not referenced, sorted by path.
+    pub unreferenced: Vec<AssetPath<'static>>,
+}
+
+impl core::fmt::Display for UnreferencedAssetReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "{} of {} assets in the `{}` asset source are not referenced by any processed asset",
+            self.unreferenced.len(),
+            self.total,
+            self.source.as_str().unwrap_or("default")
+        )?;
+        for path in &self.unreferenced {
+            writeln!(f, "  {path}")?;
+        }
+        Ok(())
+    }
+}
+
 /// An error that occurs when initializing the [`AssetProcessor`].
 #[derive(Error, Debug)]
 pub enum InitializeError {
@@ -1474,3 +1601,288 @@ pub enum InitializeError {
     #[error("Failed to validate asset log: {0}")]
     ValidateLogError(#[from] ValidateLogError),
 }
+
+#[cfg(all(test, not(target_arch = "wasm32"), feature = "multi_threaded"))]
+mod tests {
+    use super::*;
+    use crate::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            packed::{PackedArchive, PackedAssetReader},
+            AssetReader, AssetSourceBuilder, AssetWriter, Writer,
+        },
+        tests::{CoolText, CoolTextLoader, SubText},
+        Assets,
+    };
+    use alloc::string::String;
+    use bevy_tasks::{block_on, TaskPool};
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use futures_io::AsyncWrite;
+
+    /// Writes assets to a [`Dir`]. Only the operations used when processing assets are supported.
+    struct MemoryAssetWriter {
+        root: Dir,
+    }
+
+    struct DirWriter {
+        root: Dir,
+        path: PathBuf,
+        meta: bool,
+        bytes: Vec<u8>,
+    }
+
+    impl AsyncWrite for DirWriter {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.bytes.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            if self.meta {
+                self.root.insert_meta(&self.path, self.bytes.clone());
+            } else {
+                self.root.insert_asset(&self.path, self.bytes.clone());
+            }
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            self.poll_flush(cx)
+        }
+    }
+
+    fn unsupported() -> Result<(), AssetWriterError> {
+        Err(AssetWriterError::Io(ErrorKind::Unsupported.into()))
+    }
+
+    impl AssetWriter for MemoryAssetWriter {
+        async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+            Ok(Box::new(DirWriter {
+                root: self.root.clone(),
+                path: path.to_owned(),
+                meta: false,
+                bytes: Vec::new(),
+            }))
+        }
+
+        async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+            Ok(Box::new(DirWriter {
+                root: self.root.clone(),
+                path: path.to_owned(),
+                meta: true,
+                bytes: Vec::new(),
+            }))
+        }
+
+        async fn remove<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+            self.root.remove_asset(path);
+            Ok(())
+        }
+
+        async fn remove_meta<'a>(&'a self, _path: &'a Path) -> Result<(), AssetWriterError> {
+            unsupported()
+        }
+
+        async fn rename<'a>(
+            &'a self,
+            _old_path: &'a Path,
+            _new_path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            unsupported()
+        }
+
+        async fn rename_meta<'a>(
+            &'a self,
+            _old_path: &'a Path,
+            _new_path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            unsupported()
+        }
+
+        async fn create_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+            self.root.get_or_insert_dir(path);
+            Ok(())
+        }
+
+        async fn remove_directory<'a>(&'a self, _path: &'a Path) -> Result<(), AssetWriterError> {
+            unsupported()
+        }
+
+        async fn remove_empty_directory<'a>(
+            &'a self,
+            _path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            unsupported()
+        }
+
+        async fn remove_assets_in_directory<'a>(
+            &'a self,
+            _path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            unsupported()
+        }
+    }
+
+    /// Creates a processor for a default source reading from `source`, and processes its assets into a new [`Dir`].
+    ///
+    /// The transaction log of the processor is written to a temporary directory named after `name`, which is removed
+    /// afterwards.
+    fn process(
+        name: &str,
+        source: Dir,
+        setup: impl FnOnce(&AssetProcessor),
+    ) -> (AssetProcessor, Dir) {
+        IoTaskPool::get_or_init(TaskPool::default);
+        let processed = Dir::default();
+        let mut sources = AssetSourceBuilders::default();
+        let processed_reader = processed.clone();
+        let processed_writer = processed.clone();
+        sources.insert(
+            AssetSourceId::Default,
+            AssetSourceBuilder::default()
+                .with_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: source.clone(),
+                    })
+                })
+                .with_processed_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: processed_reader.clone(),
+                    })
+                })
+                .with_processed_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: processed_writer.clone(),
+                    }))
+                }),
+        );
+        let log_dir = std::env::temp_dir().join(alloc::format!(
+            "bevy_asset_processor_{name}_{}",
+            std::process::id()
+        ));
+        let processor = AssetProcessor::new_with_log_path(&mut sources, log_dir.join("log"));
+        setup(&processor);
+        processor.process_assets();
+        std::fs::remove_dir_all(log_dir).unwrap();
+        (processor, processed)
+    }
+
+    #[test]
+    fn pack_processed_assets() {
+        let source = Dir::default();
+        source.insert_asset_text(Path::new("a.txt"), "a");
+        source.insert_asset_text(Path::new("x/b.txt"), "b");
+        let (processor, processed) = process("pack", source, |_| {});
+
+        let builder =
+            block_on(processor.pack_processed(AssetSourceId::Default, PackedCompression::None))
+                .unwrap();
+        assert_eq!(builder.len(), 2);
+        let reader =
+            PackedAssetReader::new([PackedArchive::from_bytes(builder.to_bytes()).unwrap()]);
+
+        for (path, text) in [("a.txt", "a"), ("x/b.txt", "b")] {
+            let path = Path::new(path);
+            let mut bytes = Vec::new();
+            block_on(async {
+                AssetReader::read(&reader, path)
+                    .await
+                    .unwrap()
+                    .read_to_end(&mut bytes)
+                    .await
+            })
+            .unwrap();
+            assert_eq!(String::from_utf8(bytes).unwrap(), text);
+
+            let meta = block_on(AssetReader::read_meta_bytes(&reader, path)).unwrap();
+            let processed_meta = block_on(AssetReader::read_meta_bytes(
+                &MemoryAssetReader {
+                    root: processed.clone(),
+                },
+                path,
+            ))
+            .unwrap();
+            assert_eq!(meta, processed_meta);
+            let minimal: ProcessedInfoMinimal = ron::de::from_bytes(&meta).unwrap();
+            let infos = block_on(processor.data.asset_infos.read());
+            let info = infos.get(&AssetPath::from_path(path)).unwrap();
+            assert_eq!(
+                minimal.processed_info.unwrap().hash,
+                info.processed_info.as_ref().unwrap().hash
+            );
+        }
+    }
+
+    /// Loads `.cool.ron` assets while processing them, and writes them unchanged.
+    struct LoadCoolText;
+
+    impl Process for LoadCoolText {
+        type Settings = ();
+        type OutputLoader = CoolTextLoader;
+
+        async fn process(
+            &self,
+            context: &mut ProcessContext<'_>,
+            _meta: AssetMeta<(), Self>,
+            writer: &mut Writer,
+        ) -> Result<(), ProcessError> {
+            context
+                .load_source_asset(AssetMeta::<CoolTextLoader, ()>::new(AssetAction::Load {
+                    loader: core::any::type_name::<CoolTextLoader>().into(),
+                    settings: (),
+                }))
+                .await?;
+            writer
+                .write_all(context.asset_bytes())
+                .await
+                .map_err(|err| ProcessError::AssetWriterError {
+                    path: context.path().clone(),
+                    err: err.into(),
+                })
+        }
+    }
+
+    #[test]
+    fn loaded_dependencies_are_references() {
+        let source = Dir::default();
+        for (path, dependencies, embedded_dependencies) in [
+            // `b` is only referenced by the loaded `a`, and `d` is a process dependency of `c`.
+            ("a.cool.ron", r#""b.cool.ron""#, ""),
+            ("b.cool.ron", "", ""),
+            ("c.cool.ron", "", r#""d.cool.ron""#),
+            ("d.cool.ron", "", ""),
+        ] {
+            source.insert_asset_text(
+                Path::new(path),
+                &alloc::format!(
+                    r#"(text: "{path}", dependencies: [{dependencies}], embedded_dependencies: [{embedded_dependencies}], sub_texts: ["sub"])"#
+                ),
+            );
+        }
+        let (processor, _) = process("loaded_dependencies", source, |processor| {
+            processor.server().register_loader(CoolTextLoader);
+            processor
+                .server()
+                .register_asset(&Assets::<CoolText>::default());
+            processor
+                .server()
+                .register_asset(&Assets::<SubText>::default());
+            processor.register_processor(LoadCoolText);
+            processor.set_default_processor::<LoadCoolText>("cool.ron");
+        });
+
+        let report = block_on(processor.unreferenced_assets(AssetSourceId::Default)).unwrap();
+        assert_eq!(report.total, 4);
+        assert_eq!(
+            report.unreferenced,
+            [AssetPath::from("a.cool.ron"), AssetPath::from("c.cool.ron")]
+        );
+    }
+}
@@ -14,7 +14,9 @@ use alloc::{
     borrow::ToOwned,
     boxed::Box,
     string::{String, ToString},
+    vec,
 };
+use bevy_platform::collections::HashSet;
 use bevy_tasks::{BoxedFuture, ConditionalSendFuture};
 use core::marker::PhantomData;
 use serde::{Deserialize, Serialize};
@@ -281,6 +283,9 @@ pub struct ProcessContext<'a> {
     processor: &'a AssetProcessor,
     path: &'a AssetPath<'static>,
     asset_bytes: &'a [u8],
+    /// The paths of the assets the source asset depends on, as reported by the [`ErasedLoadedAsset`]s
+    /// returned by [`ProcessContext::load_source_asset`].
+    pub(crate) loaded_dependencies: HashSet<AssetPath<'static>>,
 }
 
 impl<'a> ProcessContext<'a> {
@@ -295,6 +300,7 @@ impl<'a> ProcessContext<'a> {
             path,
             asset_bytes,
             new_processed_info,
+            loaded_dependencies: HashSet::default(),
         }
     }
 
@@ -321,6 +327,25 @@ impl<'a> ProcessContext<'a> {
                     path: path.to_owned(),
                 });
         }
+        let mut assets = vec![&loaded_asset];
+        while let Some(asset) = assets.pop() {
+            for dependency in &asset.dependencies {
+                let Some(path) = server.get_path(*dependency) else {
+                    continue;
+                };
+                let path = path.without_label().into_owned();
+                // Labeled assets of the source asset are part of it.
+                if path != *self.path {
+                    self.loaded_dependencies.insert(path);
+                }
+            }
+            assets.extend(
+                asset
+                    .labeled_assets
+                    .values()
+                    .map(|labeled_asset| &labeled_asset.asset),
+            );
+        }
         Ok(loaded_asset)
     }
 
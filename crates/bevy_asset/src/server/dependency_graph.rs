@@ -0,0 +1,225 @@
+use super::info::AssetInfos;
+use crate::{AssetPath, LoadState, UntypedAssetId};
+use alloc::{
+    collections::VecDeque,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_platform::collections::{HashMap, HashSet};
+use core::fmt::Write;
+use serde::{Serialize, Serializer};
+
+/// A snapshot of the dependency graph around an asset, as tracked by the [`AssetServer`](crate::AssetServer).
+///
+/// The graph contains the root asset, everything it (recursively) depends on, and everything that (recursively)
+/// depends on it. Only dependencies reported by an asset's [`LoadedAsset`](crate::LoadedAsset) are tracked,
+/// so assets that have not finished loading do not have outgoing edges yet.
+///
+/// Retrieved with [`AssetServer::get_dependency_graph`](crate::AssetServer::get_dependency_graph). The graph can be
+/// rendered with [`to_dot`](Self::to_dot), or serialized with `serde`, in which case asset ids are written as strings.
+#[derive(Clone, Debug, Serialize)]
+pub struct AssetDependencyGraph {
+    /// The asset this graph was built around.
+    #[serde(serialize_with = "serialize_id")]
+    pub root: UntypedAssetId,
+    /// Every asset in the graph. The root asset comes first.
+    pub nodes: Vec<AssetDependencyNode>,
+    /// Every dependency in the graph, as `(dependent, dependency)` pairs.
+    #[serde(serialize_with = "serialize_edges")]
+    pub edges: Vec<(UntypedAssetId, UntypedAssetId)>,
+}
+
+/// An asset in an [`AssetDependencyGraph`].
+#[derive(Clone, Debug, Serialize)]
+pub struct AssetDependencyNode {
+    /// The id of the asset.
+    #[serde(serialize_with = "serialize_id")]
+    pub id: UntypedAssetId,
+    /// The path of the asset, if it has one.
+    pub path: Option<AssetPath<'static>>,
+    /// The [`LoadState`] of the asset at the time the graph was built. Dependencies that are unknown to the
+    /// [`AssetServer`](crate::AssetServer) are [`LoadState::NotLoaded`].
+    #[serde(serialize_with = "serialize_load_state")]
+    pub load_state: LoadState,
+}
+
+impl AssetDependencyGraph {
+    pub(crate) fn new(infos: &AssetInfos, root: UntypedAssetId) -> Option<Self> {
+        infos.get(root)?;
+
+        let mut dependents = <HashMap<UntypedAssetId, Vec<UntypedAssetId>>>::default();
+        for (id, info) in infos.iter() {
+            for dependency in &info.dependencies {
+                dependents.entry(*dependency).or_default().push(id);
+            }
+        }
+
+        let mut visited = <HashSet<UntypedAssetId>>::default();
+        visited.insert(root);
+        let mut queue = VecDeque::from([root]);
+        while let Some(id) = queue.pop_front() {
+            if let Some(info) = infos.get(id) {
+                queue.extend(info.dependencies.iter().filter(|dep| visited.insert(**dep)));
+            }
+        }
+        queue.push_back(root);
+        // the root was already visited while walking dependencies, so track dependents separately
+        let mut visited_dependents = <HashSet<UntypedAssetId>>::default();
+        visited_dependents.insert(root);
+        while let Some(id) = queue.pop_front() {
+            if let Some(ids) = dependents.get(&id) {
+                queue.extend(ids.iter().filter(|dep| visited_dependents.insert(**dep)));
+            }
+        }
+        visited.extend(visited_dependents);
+
+        let mut nodes = visited
+            .iter()
+            .map(|id| {
+                let info = infos.get(*id);
+                AssetDependencyNode {
+                    id: *id,
+                    path: info.and_then(|info| info.path.clone()),
+                    load_state: info.map_or(LoadState::NotLoaded, |info| info.load_state.clone()),
+                }
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by_cached_key(|node| (node.id != root, node.label()));
+
+        let mut edges = Vec::new();
+        for node in &nodes {
+            if let Some(info) = infos.get(node.id) {
+                edges.extend(
+                    info.dependencies
+                        .iter()
+                        .filter(|dep| visited.contains(*dep))
+                        .map(|dep| (node.id, *dep)),
+                );
+            }
+        }
+
+        let mut graph = Self { root, nodes, edges };
+        let index = graph.node_indices();
+        graph
+            .edges
+            .sort_by_key(|(from, to)| (index[from], index[to]));
+        Some(graph)
+    }
+
+    /// Returns the node for the asset with the given `id`, if it is part of this graph.
+    pub fn get(&self, id: UntypedAssetId) -> Option<&AssetDependencyNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Returns the direct dependencies of the asset with the given `id`.
+    pub fn dependencies_of(&self, id: UntypedAssetId) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.edges
+            .iter()
+            .filter(move |(from, _)| *from == id)
+            .map(|(_, to)| *to)
+    }
+
+    /// Returns the assets that directly depend on the asset with the given `id`.
+    pub fn dependents_of(&self, id: UntypedAssetId) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.edges
+            .iter()
+            .filter(move |(_, to)| *to == id)
+            .map(|(from, _)| *from)
+    }
+
+    /// Renders this graph in the Graphviz DOT format. Edges point from dependents to their dependencies.
+    pub fn to_dot(&self) -> String {
+        let index = self.node_indices();
+        let mut dot = String::from("digraph assets {\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let _ = write!(dot, "    n{i} [label=\"{}\"", escape_dot(&node.label()));
+            if node.id == self.root {
+                dot.push_str(", style=bold");
+            }
+            let _ = writeln!(dot, ", tooltip=\"{}\"];", load_state_name(&node.load_state));
+        }
+        for (from, to) in &self.edges {
+            let _ = writeln!(dot, "    n{} -> n{};", index[from], index[to]);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn node_indices(&self) -> HashMap<UntypedAssetId, usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id, i))
+            .collect()
+    }
+}
+
+impl AssetDependencyNode {
+    /// Returns the path of the asset, or its id if it has no path.
+    pub fn label(&self) -> String {
+        match &self.path {
+            Some(path) => path.to_string(),
+            None => self.id.to_string(),
+        }
+    }
+}
+
+fn load_state_name(load_state: &LoadState) -> &'static str {
+    match load_state {
+        LoadState::NotLoaded => "NotLoaded",
+        LoadState::Loading => "Loading",
+        LoadState::Loaded => "Loaded",
+        LoadState::Failed(_) => "Failed",
+    }
+}
+
+fn serialize_id<S: Serializer>(id: &UntypedAssetId, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(id)
+}
+
+fn serialize_edges<S: Serializer>(
+    edges: &[(UntypedAssetId, UntypedAssetId)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(
+        edges
+            .iter()
+            .map(|(from, to)| (from.to_string(), to.to_string())),
+    )
+}
+
+fn serialize_load_state<S: Serializer>(
+    load_state: &LoadState,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(load_state_name(load_state))
+}
+
+/// Escapes a string to be used in a quoted DOT label.
+///
+/// Line feeds become `\n` line breaks. Other control characters, such as `\r` which Graphviz would read as a
+/// right-justified line break, are replaced with spaces.
+fn escape_dot(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_dot;
+
+    #[test]
+    fn dot_labels_are_escaped() {
+        assert_eq!(escape_dot("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+        assert_eq!(escape_dot("a\rb\tc"), "a b c");
+    }
+}
@@ -1,3 +1,4 @@
+use super::load_queue::LoadQueue;
 use crate::{
     meta::{AssetHash, MetaTransform},
     Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
@@ -27,6 +28,10 @@ pub(crate) struct AssetInfo {
     pub(crate) load_state: LoadState,
     pub(crate) dep_load_state: DependencyLoadState,
     pub(crate) rec_dep_load_state: RecursiveDependencyLoadState,
+    /// Every dependency of this asset, as reported by its [`LoadedAsset`] when it finished loading.
+    ///
+    /// [`LoadedAsset`]: crate::loader::LoadedAsset
+    pub(crate) dependencies: HashSet<UntypedAssetId>,
     loading_dependencies: HashSet<UntypedAssetId>,
     failed_dependencies: HashSet<UntypedAssetId>,
     loading_rec_dependencies: HashSet<UntypedAssetId>,
@@ -55,6 +60,7 @@ impl AssetInfo {
             load_state: LoadState::NotLoaded,
             dep_load_state: DependencyLoadState::NotLoaded,
             rec_dep_load_state: RecursiveDependencyLoadState::NotLoaded,
+            dependencies: HashSet::default(),
             loading_dependencies: HashSet::default(),
             failed_dependencies: HashSet::default(),
             loading_rec_dependencies: HashSet::default(),
@@ -86,6 +92,8 @@ pub(crate) struct AssetInfos {
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, UntypedAssetId, AssetPath<'static>, AssetLoadError)>,
     pub(crate) pending_tasks: HashMap<UntypedAssetId, Task<()>>,
+    /// Gates the start of load tasks spawned by the [`AssetServer`](crate::AssetServer).
+    pub(crate) load_queue: Arc<LoadQueue>,
 }
 
 impl core::fmt::Debug for AssetInfos {
@@ -283,6 +291,11 @@ impl AssetInfos {
         self.infos.get(&id)
     }
 
+    /// Iterates over every tracked asset along with its [`AssetInfo`].
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (UntypedAssetId, &AssetInfo)> {
+        self.infos.iter().map(|(id, info)| (*id, info))
+    }
+
     pub(crate) fn contains_key(&self, id: UntypedAssetId) -> bool {
         self.infos.contains_key(&id)
     }
@@ -373,6 +386,7 @@ impl AssetInfos {
             &mut self.loader_dependents,
             &mut self.living_labeled_assets,
             &mut self.pending_tasks,
+            &self.load_queue,
             self.watching_for_changes,
             id,
         )
@@ -393,6 +407,7 @@ impl AssetInfos {
 
         loaded_asset.value.insert(loaded_asset_id, world);
         let mut loading_deps = loaded_asset.dependencies;
+        let dependencies = loading_deps.clone();
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
         let mut loading_rec_deps = loading_deps.clone();
@@ -488,6 +503,7 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_id)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
@@ -677,6 +693,7 @@ impl AssetInfos {
         loader_dependents: &mut HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<Box<str>>>,
         pending_tasks: &mut HashMap<UntypedAssetId, Task<()>>,
+        load_queue: &LoadQueue,
         watching_for_changes: bool,
         id: UntypedAssetId,
     ) -> bool {
@@ -692,6 +709,7 @@ impl AssetInfos {
         }
 
         pending_tasks.remove(&id);
+        load_queue.cancel(id);
 
         let type_id = entry.key().type_id();
 
@@ -736,6 +754,7 @@ impl AssetInfos {
                         &mut self.loader_dependents,
                         &mut self.living_labeled_assets,
                         &mut self.pending_tasks,
+                        &self.load_queue,
                         self.watching_for_changes,
                         id.untyped(provider.type_id),
                     );
@@ -0,0 +1,315 @@
+use crate::UntypedAssetId;
+use alloc::sync::Arc;
+use bevy_platform::collections::HashMap;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use parking_lot::Mutex;
+
+/// The priority of a pending asset load. When [`AssetServer::set_max_concurrent_loads`] limits the number of loads
+/// that can run at once, queued loads with a higher priority start before queued loads with a lower priority.
+/// Loads with equal priority start in the order they were requested.
+///
+/// The priority of a pending load can be changed after it was requested with [`AssetServer::set_load_priority`].
+///
+/// [`AssetServer::set_max_concurrent_loads`]: crate::AssetServer::set_max_concurrent_loads
+/// [`AssetServer::set_load_priority`]: crate::AssetServer::set_load_priority
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LoadPriority(pub i32);
+
+impl LoadPriority {
+    /// The lowest possible priority.
+    pub const MIN: Self = Self(i32::MIN);
+    /// The priority loads are requested with by default.
+    pub const DEFAULT: Self = Self(0);
+    /// The highest possible priority.
+    pub const MAX: Self = Self(i32::MAX);
+}
+
+/// Gates the start of asset loads so that at most `max_concurrent` of them run at once.
+#[derive(Default)]
+pub(crate) struct LoadQueue {
+    state: Mutex<LoadQueueState>,
+}
+
+#[derive(Default)]
+struct LoadQueueState {
+    /// [`None`] means that loads are never queued.
+    max_concurrent: Option<usize>,
+    running: usize,
+    next_ticket: u64,
+    entries: HashMap<u64, QueuedLoad>,
+}
+
+struct QueuedLoad {
+    id: UntypedAssetId,
+    priority: LoadPriority,
+    status: QueuedLoadStatus,
+    waker: Option<Waker>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum QueuedLoadStatus {
+    Waiting,
+    Started,
+    Cancelled,
+}
+
+impl LoadQueueState {
+    /// Starts waiting loads in priority order until the concurrency limit is reached.
+    fn dispatch(&mut self) {
+        loop {
+            if self
+                .max_concurrent
+                .is_some_and(|max_concurrent| self.running >= max_concurrent)
+            {
+                return;
+            }
+            let Some(next) = self
+                .entries
+                .iter_mut()
+                .filter(|(_, entry)| entry.status == QueuedLoadStatus::Waiting)
+                // ties are broken by the lowest ticket, which is the oldest request
+                .max_by_key(|(ticket, entry)| (entry.priority, core::cmp::Reverse(**ticket)))
+                .map(|(_, entry)| entry)
+            else {
+                return;
+            };
+            next.status = QueuedLoadStatus::Started;
+            if let Some(waker) = next.waker.take() {
+                waker.wake();
+            }
+            self.running += 1;
+        }
+    }
+
+    fn release(&mut self) {
+        self.running -= 1;
+        self.dispatch();
+    }
+}
+
+impl LoadQueue {
+    /// Queues a load of the asset with the given `id`. The returned future resolves to a [`LoadPermit`] once the load is
+    /// allowed to start, or to [`None`] if the load was cancelled before that.
+    pub(crate) fn enqueue(self: &Arc<Self>, id: UntypedAssetId) -> QueuedLoadFuture {
+        let mut state = self.state.lock();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.entries.insert(
+            ticket,
+            QueuedLoad {
+                id,
+                priority: LoadPriority::DEFAULT,
+                status: QueuedLoadStatus::Waiting,
+                waker: None,
+            },
+        );
+        state.dispatch();
+        QueuedLoadFuture {
+            queue: self.clone(),
+            ticket: Some(ticket),
+        }
+    }
+
+    pub(crate) fn max_concurrent(&self) -> Option<usize> {
+        self.state.lock().max_concurrent
+    }
+
+    pub(crate) fn set_max_concurrent(&self, max_concurrent: Option<usize>) {
+        let mut state = self.state.lock();
+        state.max_concurrent = max_concurrent;
+        state.dispatch();
+    }
+
+    /// Returns the priority of the queued load of the asset with the given `id`, if it has not started yet.
+    pub(crate) fn priority(&self, id: UntypedAssetId) -> Option<LoadPriority> {
+        self.state
+            .lock()
+            .entries
+            .values()
+            .find(|entry| entry.id == id && entry.status == QueuedLoadStatus::Waiting)
+            .map(|entry| entry.priority)
+    }
+
+    /// Sets the priority of the queued load of the asset with the given `id`. Returns `false` if there is no such load
+    /// waiting to start.
+    pub(crate) fn set_priority(&self, id: UntypedAssetId, priority: LoadPriority) -> bool {
+        let mut state = self.state.lock();
+        let mut found = false;
+        for entry in state.entries.values_mut() {
+            if entry.id == id && entry.status == QueuedLoadStatus::Waiting {
+                entry.priority = priority;
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Cancels any queued load of the asset with the given `id` that has not started yet.
+    pub(crate) fn cancel(&self, id: UntypedAssetId) {
+        let mut state = self.state.lock();
+        for entry in state.entries.values_mut() {
+            if entry.id == id && entry.status == QueuedLoadStatus::Waiting {
+                entry.status = QueuedLoadStatus::Cancelled;
+                if let Some(waker) = entry.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+
+    /// Returns the number of loads that are waiting to start.
+    pub(crate) fn waiting(&self) -> usize {
+        self.state
+            .lock()
+            .entries
+            .values()
+            .filter(|entry| entry.status == QueuedLoadStatus::Waiting)
+            .count()
+    }
+
+    /// Returns the number of loads that have started and not finished yet.
+    pub(crate) fn running(&self) -> usize {
+        self.state.lock().running
+    }
+}
+
+/// A future that waits for a queued load to be allowed to start. Dropping it removes the load from the queue.
+pub(crate) struct QueuedLoadFuture {
+    queue: Arc<LoadQueue>,
+    ticket: Option<u64>,
+}
+
+impl Future for QueuedLoadFuture {
+    type Output = Option<LoadPermit>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(ticket) = self.ticket else {
+            return Poll::Ready(None);
+        };
+        let mut state = self.queue.state.lock();
+        let Some(entry) = state.entries.get_mut(&ticket) else {
+            return Poll::Ready(None);
+        };
+        match entry.status {
+            QueuedLoadStatus::Waiting => {
+                entry.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            QueuedLoadStatus::Started => {
+                state.entries.remove(&ticket);
+                drop(state);
+                self.ticket = None;
+                Poll::Ready(Some(LoadPermit {
+                    queue: self.queue.clone(),
+                }))
+            }
+            QueuedLoadStatus::Cancelled => {
+                state.entries.remove(&ticket);
+                drop(state);
+                self.ticket = None;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl Drop for QueuedLoadFuture {
+    fn drop(&mut self) {
+        let Some(ticket) = self.ticket else {
+            return;
+        };
+        let mut state = self.queue.state.lock();
+        let started = state
+            .entries
+            .remove(&ticket)
+            .is_some_and(|entry| entry.status == QueuedLoadStatus::Started);
+        if started {
+            // the load was allowed to start but never did, so hand its slot to the next one
+            state.release();
+        }
+    }
+}
+
+/// Occupies one of the [`LoadQueue`]'s concurrent load slots until dropped.
+pub(crate) struct LoadPermit {
+    queue: Arc<LoadQueue>,
+}
+
+impl Drop for LoadPermit {
+    fn drop(&mut self) {
+        self.queue.state.lock().release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AssetId;
+    use futures_lite::future::{block_on, poll_once};
+
+    fn id(index: u128) -> UntypedAssetId {
+        AssetId::<()>::Uuid {
+            uuid: uuid::Uuid::from_u128(index),
+        }
+        .untyped()
+    }
+
+    #[test]
+    fn starts_loads_by_priority() {
+        let queue = Arc::new(LoadQueue::default());
+        queue.set_max_concurrent(Some(1));
+
+        let mut first = queue.enqueue(id(0));
+        let mut low = queue.enqueue(id(1));
+        let mut high = queue.enqueue(id(2));
+        let mut raised = queue.enqueue(id(3));
+        assert!(queue.set_priority(id(2), LoadPriority(10)));
+        assert!(queue.set_priority(id(3), LoadPriority(20)));
+        assert_eq!(queue.priority(id(3)), Some(LoadPriority(20)));
+
+        let permit = block_on(poll_once(&mut first)).unwrap().unwrap();
+        assert!(block_on(poll_once(&mut low)).is_none());
+        assert!(block_on(poll_once(&mut high)).is_none());
+        assert!(block_on(poll_once(&mut raised)).is_none());
+        assert_eq!(queue.waiting(), 3);
+
+        drop(permit);
+        let permit = block_on(poll_once(&mut raised)).unwrap().unwrap();
+        assert!(block_on(poll_once(&mut high)).is_none());
+        drop(permit);
+        let permit = block_on(poll_once(&mut high)).unwrap().unwrap();
+        drop(permit);
+        assert!(block_on(poll_once(&mut low)).unwrap().is_some());
+        assert_eq!(queue.waiting(), 0);
+    }
+
+    #[test]
+    fn cancelled_loads_release_their_slot() {
+        let queue = Arc::new(LoadQueue::default());
+        queue.set_max_concurrent(Some(1));
+
+        let first = queue.enqueue(id(0));
+        let mut cancelled = queue.enqueue(id(1));
+        let mut last = queue.enqueue(id(2));
+        queue.cancel(id(1));
+        assert!(block_on(poll_once(&mut cancelled)).unwrap().is_none());
+
+        // a future that is dropped after being allowed to start hands its slot on
+        drop(first);
+        assert_eq!(queue.running(), 1);
+        let permit = block_on(poll_once(&mut last)).unwrap().unwrap();
+        drop(permit);
+        assert_eq!(queue.running(), 0);
+
+        // without a limit, loads start immediately
+        queue.set_max_concurrent(None);
+        let _permits = (0..4)
+            .map(|i| block_on(queue.enqueue(id(i))).unwrap())
+            .collect::<alloc::vec::Vec<_>>();
+        assert_eq!(queue.running(), 4);
+    }
+}
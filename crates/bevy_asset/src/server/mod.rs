@@ -1,4 +1,6 @@
+mod dependency_graph;
 mod info;
+mod load_queue;
 mod loaders;
 
 use crate::{
@@ -34,6 +36,9 @@ use either::Either;
 use futures_lite::{FutureExt, StreamExt};
 use info::*;
 use loaders::*;
+
+pub use dependency_graph::{AssetDependencyGraph, AssetDependencyNode};
+pub use load_queue::LoadPriority;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -518,13 +523,23 @@ impl AssetServer {
         infos: RwLockWriteGuard<AssetInfos>,
         guard: G,
     ) {
+        let id = handle.id();
+        let queued = infos.load_queue.enqueue(id);
+
         // drop the lock on `AssetInfos` before spawning a task that may block on it in single-threaded
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
         drop(infos);
 
-        let owned_handle = handle.clone();
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
+            // The task must not keep the asset alive while it waits for its turn, so the strong handle is only
+            // retrieved once the load starts. If every strong handle was dropped in the meantime, the load is cancelled.
+            let Some(_permit) = queued.await else {
+                return;
+            };
+            let Some(owned_handle) = server.get_id_handle_untyped(id) else {
+                return;
+            };
             if let Err(err) = server
                 .load_internal(Some(owned_handle), path, false, None)
                 .await
@@ -575,17 +590,21 @@ impl AssetServer {
             meta_transform,
         );
 
-        // drop the lock on `AssetInfos` before spawning a task that may block on it in single-threaded
-        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
-        drop(infos);
-
         if !should_load {
             return handle;
         }
         let id = handle.id().untyped();
+        let queued = infos.load_queue.enqueue(id);
+
+        // drop the lock on `AssetInfos` before spawning a task that may block on it in single-threaded
+        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+        drop(infos);
 
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
+            let Some(_permit) = queued.await else {
+                return;
+            };
             let path_clone = path.clone();
             match server.load_untyped_async(path).await {
                 Ok(handle) => server.send_asset_event(InternalAssetEvent::Loaded {
@@ -1047,6 +1066,66 @@ impl AssetServer {
             .detach();
     }
 
+    /// Begins loading an [`Asset`] of type `A` stored at `path`, like [`AssetServer::load`], and queues it with the given
+    /// [`LoadPriority`]. This is equivalent to calling [`AssetServer::load`] followed by [`AssetServer::set_load_priority`].
+    ///
+    /// The priority only matters when [`AssetServer::set_max_concurrent_loads`] limits the number of concurrent loads.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        priority: LoadPriority,
+    ) -> Handle<A> {
+        let handle = self.load(path);
+        self.set_load_priority(&handle, priority);
+        handle
+    }
+
+    /// Changes the [`LoadPriority`] of the load of the asset with the given `id`. This only affects loads that are
+    /// still waiting for a free slot (see [`AssetServer::set_max_concurrent_loads`]). Returns `false` if there is no
+    /// such load.
+    pub fn set_load_priority(&self, id: impl Into<UntypedAssetId>, priority: LoadPriority) -> bool {
+        self.data
+            .infos
+            .read()
+            .load_queue
+            .set_priority(id.into(), priority)
+    }
+
+    /// Returns the [`LoadPriority`] of the load of the asset with the given `id`, if that load is still waiting for
+    /// a free slot.
+    pub fn get_load_priority(&self, id: impl Into<UntypedAssetId>) -> Option<LoadPriority> {
+        self.data.infos.read().load_queue.priority(id.into())
+    }
+
+    /// Returns the maximum number of asset loads that may run at once, or [`None`] if there is no limit.
+    pub fn max_concurrent_loads(&self) -> Option<usize> {
+        self.data.infos.read().load_queue.max_concurrent()
+    }
+
+    /// Limits the number of asset loads that may run at once. Loads requested beyond this limit wait in a queue and
+    /// start in [`LoadPriority`] order as running loads finish. Queued loads are cancelled if every strong handle to
+    /// their asset is dropped before they start. [`None`] removes the limit.
+    ///
+    /// This defaults to [`AssetPlugin::max_concurrent_loads`](crate::AssetPlugin::max_concurrent_loads).
+    pub fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        self.data
+            .infos
+            .read()
+            .load_queue
+            .set_max_concurrent(max_concurrent_loads);
+    }
+
+    /// Returns the number of asset loads that are waiting for a free slot.
+    pub fn queued_load_count(&self) -> usize {
+        self.data.infos.read().load_queue.waiting()
+    }
+
+    /// Returns the number of asset loads that are currently running.
+    pub fn running_load_count(&self) -> usize {
+        self.data.infos.read().load_queue.running()
+    }
+
     fn send_asset_event(&self, event: InternalAssetEvent) {
         self.data.asset_event_sender.send(event).unwrap();
     }
@@ -1190,6 +1269,38 @@ impl AssetServer {
         self.data.infos.read().get_id_handle(id)
     }
 
+    /// Returns the direct dependencies of the asset with the given `id`, as reported by its [`LoadedAsset`] when it
+    /// finished loading. Returns [`None`] if the asset is not managed by this [`AssetServer`].
+    pub fn get_dependencies(&self, id: impl Into<UntypedAssetId>) -> Option<Vec<UntypedAssetId>> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .map(|info| info.dependencies.iter().copied().collect())
+    }
+
+    /// Returns the loaded assets that directly depend on the asset with the given `id`.
+    pub fn get_dependents(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        let id = id.into();
+        self.data
+            .infos
+            .read()
+            .iter()
+            .filter(|(_, info)| info.dependencies.contains(&id))
+            .map(|(dependent, _)| dependent)
+            .collect()
+    }
+
+    /// Returns the [`AssetDependencyGraph`] around the asset with the given `id`: everything it recursively depends on,
+    /// and everything that recursively depends on it. The graph can be exported with [`AssetDependencyGraph::to_dot`]
+    /// or serialized with `serde`. Returns [`None`] if the asset is not managed by this [`AssetServer`].
+    pub fn get_dependency_graph(
+        &self,
+        id: impl Into<UntypedAssetId>,
+    ) -> Option<AssetDependencyGraph> {
+        AssetDependencyGraph::new(&self.data.infos.read(), id.into())
+    }
+
     /// Returns `true` if the given `id` corresponds to an asset that is managed by this [`AssetServer`].
     /// Otherwise, returns `false`.
     pub fn is_managed(&self, id: impl Into<UntypedAssetId>) -> bool {
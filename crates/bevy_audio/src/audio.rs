@@ -3,6 +3,7 @@ use bevy_asset::{Asset, Handle};
 use bevy_ecs::prelude::*;
 use bevy_math::Vec3;
 use bevy_reflect::prelude::*;
+use core::time::Duration;
 
 /// The way Bevy manages the sound playback.
 #[derive(Debug, Clone, Copy, Reflect)]
@@ -57,6 +58,18 @@ pub struct PlaybackSettings {
     /// Optional scale factor applied to the positions of this audio source and the listener,
     /// overriding the default value configured on [`AudioPlugin::default_spatial_scale`](crate::AudioPlugin::default_spatial_scale).
     pub spatial_scale: Option<SpatialScale>,
+    /// Delay before the audio starts playing, counted from when its sink is created.
+    ///
+    /// The start is scheduled on a clock shared by all audio, which counts the frames played by
+    /// the output. Sounds whose sinks are created in the same frame therefore start exactly their
+    /// delays apart, unlike when waiting a number of frames before spawning the [`AudioPlayer`].
+    ///
+    /// A sink is only created once the audio source has loaded, and the audio thread picks it up
+    /// on its next buffer. If that happens after the scheduled start, the audio starts right away,
+    /// so delays shorter than the audio buffer may be extended.
+    pub start_delay: Duration,
+    /// Another audio entity to crossfade from when this audio starts playing.
+    pub crossfade: Option<Crossfade>,
 }
 
 impl Default for PlaybackSettings {
@@ -81,6 +94,8 @@ impl PlaybackSettings {
         muted: false,
         spatial: false,
         spatial_scale: None,
+        start_delay: Duration::ZERO,
+        crossfade: None,
     };
 
     /// Will play the associated audio source in a loop.
@@ -136,6 +151,32 @@ impl PlaybackSettings {
         self.spatial_scale = Some(spatial_scale);
         self
     }
+
+    /// Helper to delay the start of playback.
+    pub const fn with_start_delay(mut self, start_delay: Duration) -> Self {
+        self.start_delay = start_delay;
+        self
+    }
+
+    /// Helper to crossfade from the audio playing on the `from` entity over `duration`.
+    pub const fn with_crossfade(mut self, from: Entity, duration: Duration) -> Self {
+        self.crossfade = Some(Crossfade { from, duration });
+        self
+    }
+}
+
+/// A transition from the audio playing on another entity, configured in [`PlaybackSettings`].
+///
+/// When the new audio starts playing, after its [start delay](PlaybackSettings::start_delay),
+/// it fades in while the audio of the `from` entity fades out and stops. Both fades start on the
+/// same frame of the output.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct Crossfade {
+    /// The entity whose audio fades out.
+    pub from: Entity,
+    /// The duration of the fades.
+    pub duration: Duration,
 }
 
 /// Settings for the listener for spatial audio sources.
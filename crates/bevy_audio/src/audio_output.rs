@@ -1,12 +1,20 @@
 use crate::{
-    AudioPlayer, Decodable, DefaultSpatialScale, GlobalVolume, PlaybackMode, PlaybackSettings,
-    SpatialAudioSink, SpatialListener,
+    clock::{AudioClock, StartAt},
+    mixer::{BusParameters, BusSource},
+    AudioBus, AudioMixer, AudioPlayer, Crossfade, Decodable, DefaultSpatialScale, GlobalVolume,
+    PlaybackMode, PlaybackSettings, SpatialAudioSink, SpatialListener,
 };
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_math::Vec3;
 use bevy_transform::prelude::GlobalTransform;
-use rodio::{OutputStream, OutputStreamHandle, Sink, Source, SpatialSink};
+use core::time::Duration;
+use rodio::{
+    dynamic_mixer::{self, DynamicMixer, DynamicMixerController},
+    OutputStream, OutputStreamHandle, PlayError, Sink, Source, SpatialSink,
+};
+use std::sync::Mutex;
 use tracing::warn;
 
 use crate::{AudioSink, AudioSinkPlayback};
@@ -26,25 +34,128 @@ use crate::{AudioSink, AudioSinkPlayback};
 #[derive(Resource)]
 pub(crate) struct AudioOutput {
     stream_handle: Option<OutputStreamHandle>,
+    null_mixer: Option<Arc<DynamicMixerController<f32>>>,
+    /// The clock used to schedule the start of sinks and crossfades.
+    clock: AudioClock,
 }
 
+/// The sample rate of the [`AudioClock`] of audio devices.
+const DEVICE_CLOCK_SAMPLE_RATE: u32 = 48_000;
+
 impl Default for AudioOutput {
     fn default() -> Self {
+        let clock = AudioClock::new(DEVICE_CLOCK_SAMPLE_RATE);
         if let Ok((stream, stream_handle)) = OutputStream::try_default() {
             // We leak `OutputStream` to prevent the audio from stopping.
             core::mem::forget(stream);
+            // The clock is played first, so that it advances before the sinks play each frame.
+            if let Err(err) = stream_handle.play_raw(clock.source(1)) {
+                warn!("Error starting the audio clock: {err:?}");
+            }
             Self {
                 stream_handle: Some(stream_handle),
+                null_mixer: None,
+                clock,
             }
         } else {
             warn!("No audio device found.");
             Self {
                 stream_handle: None,
+                null_mixer: None,
+                clock,
             }
         }
     }
 }
 
+impl AudioOutput {
+    /// Plays audio into the given [`NullAudioOutput`] instead of an audio device.
+    pub(crate) fn null(output: &NullAudioOutput) -> Self {
+        let clock = AudioClock::new(output.sample_rate);
+        output.controller.add(clock.source(output.channels));
+        Self {
+            stream_handle: None,
+            null_mixer: Some(output.controller.clone()),
+            clock,
+        }
+    }
+
+    fn try_new_sink(&self) -> Result<Sink, PlayError> {
+        if let Some(stream_handle) = &self.stream_handle {
+            return Sink::try_new(stream_handle);
+        }
+        let Some(controller) = &self.null_mixer else {
+            return Err(PlayError::NoDevice);
+        };
+        let (sink, queue) = Sink::new_idle();
+        controller.add(queue);
+        Ok(sink)
+    }
+}
+
+/// The audio device used by the [`AudioPlugin`](crate::AudioPlugin).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AudioOutputDevice {
+    /// Plays audio on the default device of the system, if there is one.
+    #[default]
+    Default,
+    /// Mixes audio into a [`NullAudioOutput`] resource instead of a device.
+    ///
+    /// This is useful in tests and on headless servers. Spatial audio is played without
+    /// spatialization.
+    Null {
+        /// The number of interleaved channels of the output.
+        channels: u16,
+        /// The number of frames mixed per second.
+        sample_rate: u32,
+    },
+}
+
+/// An audio output that doesn't play on any device, added by
+/// [`AudioOutputDevice::Null`].
+///
+/// Audio only advances when it is [rendered](Self::render), which makes the output fully
+/// deterministic: tests can render a number of frames and check the mixed samples.
+#[derive(Resource, Clone)]
+pub struct NullAudioOutput {
+    controller: Arc<DynamicMixerController<f32>>,
+    mixer: Arc<Mutex<DynamicMixer<f32>>>,
+    channels: u16,
+    sample_rate: u32,
+}
+
+impl NullAudioOutput {
+    /// Creates an output with the given number of channels and sample rate.
+    pub fn new(channels: u16, sample_rate: u32) -> Self {
+        let (controller, mixer) = dynamic_mixer::mixer(channels, sample_rate);
+        Self {
+            controller,
+            mixer: Arc::new(Mutex::new(mixer)),
+            channels,
+            sample_rate,
+        }
+    }
+
+    /// Mixes the next `frames` frames of all playing audio, returning the interleaved samples.
+    ///
+    /// Silence is returned when nothing is playing.
+    pub fn render(&self, frames: usize) -> Vec<f32> {
+        let mut mixer = self.mixer.lock().unwrap();
+        let samples = frames * self.channels() as usize;
+        (0..samples).map(|_| mixer.next().unwrap_or(0.0)).collect()
+    }
+
+    /// Returns the number of interleaved channels.
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Returns the number of frames mixed per second.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
 /// Marker for internal use, to despawn entities when playback finishes.
 #[derive(Component, Default)]
 pub struct PlaybackDespawnMarker;
@@ -103,26 +214,45 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
             &AudioPlayer<Source>,
             &PlaybackSettings,
             Option<&GlobalTransform>,
+            Option<&AudioBus>,
         ),
         (Without<AudioSink>, Without<SpatialAudioSink>),
     >,
+    playing: Query<AnyOf<(&AudioSink, &SpatialAudioSink)>>,
+    mixer: Res<AudioMixer>,
     ear_positions: EarPositions,
     default_spatial_scale: Res<DefaultSpatialScale>,
     mut commands: Commands,
 ) where
     f32: rodio::cpal::FromSample<Source::DecoderItem>,
 {
-    let Some(stream_handle) = audio_output.stream_handle.as_ref() else {
-        // audio output unavailable; cannot play sound
-        return;
-    };
-
-    for (entity, source_handle, settings, maybe_emitter_transform) in &query_nonplaying {
+    for (entity, source_handle, settings, maybe_emitter_transform, bus) in &query_nonplaying {
         let Some(audio_source) = audio_sources.get(&source_handle.0) else {
             continue;
         };
+        let bus = bus.map_or(AudioMixer::MASTER, |bus| bus.0.as_ref());
+        let bus = Arc::new(BusParameters::new(mixer.output(bus)));
+        let crossfade = settings
+            .crossfade
+            .map(|crossfade| (crossfade_buses(&playing, crossfade), crossfade.duration));
+        let source = bus_source(
+            audio_source,
+            settings,
+            bus.clone(),
+            &audio_output.clock,
+            crossfade,
+        );
+        // Spatial audio needs a device, the null output plays it without spatialization.
+        let spatial_stream = if settings.spatial {
+            if audio_output.stream_handle.is_none() {
+                warn!("Spatial audio is not supported by the null audio output. Playing {entity} without spatialization.");
+            }
+            audio_output.stream_handle.as_ref()
+        } else {
+            None
+        };
         // audio data is available (has loaded), begin playback and insert sink component
-        if settings.spatial {
+        if let Some(stream_handle) = spatial_stream {
             let (left_ear, right_ear) = ear_positions.get();
 
             // We can only use one `SpatialListener`. If there are more than that, then
@@ -156,14 +286,10 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 }
             };
 
-            match settings.mode {
-                PlaybackMode::Loop => sink.append(audio_source.decoder().repeat_infinite()),
-                PlaybackMode::Once | PlaybackMode::Despawn | PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
-                }
-            };
+            append_spatial(&sink, source);
 
             let mut sink = SpatialAudioSink::new(sink);
+            sink.bus = bus;
 
             if settings.muted {
                 sink.mute();
@@ -188,7 +314,7 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                     .insert((sink, PlaybackRemoveMarker)),
             };
         } else {
-            let sink = match Sink::try_new(stream_handle) {
+            let sink = match audio_output.try_new_sink() {
                 Ok(sink) => sink,
                 Err(err) => {
                     warn!("Error creating sink: {err:?}");
@@ -196,14 +322,10 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 }
             };
 
-            match settings.mode {
-                PlaybackMode::Loop => sink.append(audio_source.decoder().repeat_infinite()),
-                PlaybackMode::Once | PlaybackMode::Despawn | PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
-                }
-            };
+            append(&sink, source);
 
             let mut sink = AudioSink::new(sink);
+            sink.bus = bus;
 
             if settings.muted {
                 sink.mute();
@@ -231,6 +353,71 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
     }
 }
 
+/// Returns the buses of the sinks the new audio crossfades from.
+fn crossfade_buses(
+    playing: &Query<AnyOf<(&AudioSink, &SpatialAudioSink)>>,
+    crossfade: Crossfade,
+) -> Vec<Arc<BusParameters>> {
+    let Ok((sink, spatial_sink)) = playing.get(crossfade.from) else {
+        warn!(
+            "Cannot crossfade from {}, which is not playing audio.",
+            crossfade.from
+        );
+        return Vec::new();
+    };
+    sink.map(|sink| sink.bus.clone())
+        .into_iter()
+        .chain(spatial_sink.map(|sink| sink.bus.clone()))
+        .collect()
+}
+
+/// Decodes the audio source, applying the playback settings and the volume and effects of the
+/// mixer bus.
+///
+/// The source starts once `clock` reaches the start delay, counted from now, and the sinks using
+/// the `crossfade` buses start fading out on the same frame.
+fn bus_source<Source: Decodable>(
+    audio_source: &Source,
+    settings: &PlaybackSettings,
+    bus: Arc<BusParameters>,
+    clock: &AudioClock,
+    crossfade: Option<(Vec<Arc<BusParameters>>, Duration)>,
+) -> BusSource<Box<dyn rodio::Source<Item = f32> + Send>>
+where
+    f32: rodio::cpal::FromSample<Source::DecoderItem>,
+{
+    let mut decoder: Box<dyn rodio::Source<Item = f32> + Send> = match settings.mode {
+        PlaybackMode::Loop => Box::new(audio_source.decoder().repeat_infinite().convert_samples()),
+        PlaybackMode::Once | PlaybackMode::Despawn | PlaybackMode::Remove => {
+            Box::new(audio_source.decoder().convert_samples())
+        }
+    };
+    if let Some((_, duration)) = &crossfade {
+        decoder = Box::new(decoder.fade_in(*duration));
+    }
+    if !settings.start_delay.is_zero() || crossfade.is_some() {
+        let start = clock
+            .frames()
+            .saturating_add(clock.frames_in(settings.start_delay));
+        let mut start_at = StartAt::new(decoder, clock.clone(), start);
+        if let Some((buses, duration)) = crossfade {
+            start_at = start_at.with_crossfade(buses, duration);
+        }
+        decoder = Box::new(start_at);
+    }
+    BusSource::new(decoder, bus, clock.clone())
+}
+
+// These are separate functions, as the `FromSample` bound of the systems would otherwise be
+// picked over the implementation for `f32` when appending.
+fn append(sink: &Sink, source: BusSource<Box<dyn Source<Item = f32> + Send>>) {
+    sink.append(source);
+}
+
+fn append_spatial(sink: &SpatialSink, source: BusSource<Box<dyn Source<Item = f32> + Send>>) {
+    sink.append(source);
+}
+
 pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
     mut commands: Commands,
     query_nonspatial_despawn: Query<
@@ -284,7 +471,7 @@ pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
 
 /// Run Condition to only play audio if the audio output is available
 pub(crate) fn audio_output_available(audio_output: Res<AudioOutput>) -> bool {
-    audio_output.stream_handle.is_some()
+    audio_output.stream_handle.is_some() || audio_output.null_mixer.is_some()
 }
 
 /// Updates spatial audio sinks when emitter positions change.
@@ -332,3 +519,79 @@ pub(crate) fn update_listener_positions(
         sink.set_ears_position(left_ear * scale, right_ear * scale);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AudioOutputDevice, AudioPlayer, AudioPlugin, AudioSink, AudioSinkPlayback, AudioStream,
+        NullAudioOutput, PlaybackSettings,
+    };
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_asset::{AssetPlugin, Assets};
+    use core::time::Duration;
+
+    const SAMPLE_RATE: u32 = 48_000;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            AudioPlugin {
+                output: AudioOutputDevice::Null {
+                    channels: 1,
+                    sample_rate: SAMPLE_RATE,
+                },
+                ..Default::default()
+            },
+        ));
+        app
+    }
+
+    fn stream(app: &mut App, value: f32) -> AudioPlayer<AudioStream> {
+        let stream = AudioStream::new(1, SAMPLE_RATE, 4800);
+        stream.push(&[value; 4800]);
+        let mut streams = app.world_mut().resource_mut::<Assets<AudioStream>>();
+        AudioPlayer(streams.add(stream))
+    }
+
+    #[test]
+    fn null_output_start_delay() {
+        let mut app = app();
+        let player = stream(&mut app, 0.5);
+        app.world_mut().spawn((
+            player,
+            PlaybackSettings::ONCE.with_start_delay(Duration::from_millis(10)),
+        ));
+        app.update();
+
+        let output = app.world().resource::<NullAudioOutput>().clone();
+        let samples = output.render(960);
+        assert!(samples[..480].iter().all(|sample| *sample == 0.0));
+        assert!(samples[480..].iter().all(|sample| *sample == 0.5));
+    }
+
+    #[test]
+    fn null_output_crossfade() {
+        let mut app = app();
+        let output = app.world().resource::<NullAudioOutput>().clone();
+        let player = stream(&mut app, 1.0);
+        let from = app.world_mut().spawn(player).id();
+        app.update();
+        let samples = output.render(480);
+        assert!(samples.iter().all(|sample| *sample == 1.0));
+
+        let player = stream(&mut app, 0.5);
+        app.world_mut().spawn((
+            player,
+            PlaybackSettings::ONCE.with_crossfade(from, Duration::from_millis(10)),
+        ));
+        app.update();
+
+        // The first stream fades out while the second one fades in.
+        let samples = output.render(960);
+        assert!((samples[240] - 0.75).abs() < 1e-2);
+        assert!(samples[480..].iter().all(|sample| *sample == 0.5));
+        assert!(app.world().get::<AudioSink>(from).unwrap().empty());
+    }
+}
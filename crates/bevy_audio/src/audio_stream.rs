@@ -0,0 +1,216 @@
+use crate::Decodable;
+use alloc::{collections::VecDeque, sync::Arc};
+use bevy_asset::Asset;
+use bevy_reflect::TypePath;
+use core::time::Duration;
+use std::sync::Mutex;
+
+/// A source of audio samples pushed at runtime, for example by a synthesizer or voice chat.
+///
+/// The samples are stored in a ring buffer shared between all clones of the stream, so a clone
+/// can be kept to [`push`](Self::push) samples after adding the stream to the
+/// [`Assets<AudioStream>`](bevy_asset::Assets) and playing it with an
+/// [`AudioPlayer`](crate::AudioPlayer).
+///
+/// When the buffer runs out of samples, the stream plays silence until more are pushed, so that
+/// playback continues. It only ends once it has been [closed](Self::close) and all its samples
+/// have been played.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::Assets;
+/// # use bevy_audio::{AudioPlayer, AudioStream};
+/// #[derive(Resource)]
+/// struct Synth(AudioStream);
+///
+/// fn start_synth(mut streams: ResMut<Assets<AudioStream>>, mut commands: Commands) {
+///     let stream = AudioStream::new(1, 48_000, 48_000);
+///     commands.spawn(AudioPlayer(streams.add(stream.clone())));
+///     commands.insert_resource(Synth(stream));
+/// }
+///
+/// fn play_synth(synth: Res<Synth>) {
+///     let samples = (0..480).map(|i| (i as f32 * 0.1).sin() * 0.2).collect::<Vec<_>>();
+///     synth.0.push(&samples);
+/// }
+/// ```
+#[derive(Asset, Debug, Clone, TypePath)]
+pub struct AudioStream {
+    buffer: Arc<Mutex<StreamBuffer>>,
+    channels: u16,
+    sample_rate: u32,
+}
+
+#[derive(Debug)]
+struct StreamBuffer {
+    samples: VecDeque<f32>,
+    capacity: usize,
+    closed: bool,
+}
+
+impl AudioStream {
+    /// Creates a stream of interleaved samples with the given number of channels and sample rate,
+    /// buffering at most `capacity` samples.
+    pub fn new(channels: u16, sample_rate: u32, capacity: usize) -> Self {
+        assert!(channels > 0, "an audio stream needs at least one channel");
+        Self {
+            buffer: Arc::new(Mutex::new(StreamBuffer {
+                samples: VecDeque::with_capacity(capacity),
+                capacity,
+                closed: false,
+            })),
+            channels,
+            sample_rate,
+        }
+    }
+
+    /// Pushes interleaved samples to the stream, returning how many fitted in the buffer.
+    ///
+    /// Samples should be pushed in whole frames, with one sample per channel, so that the
+    /// channels stay in order.
+    pub fn push(&self, samples: &[f32]) -> usize {
+        let mut buffer = self.buffer.lock().unwrap();
+        let free = buffer.capacity - buffer.samples.len();
+        // Only accept whole frames, to keep the channels in order.
+        let count = samples.len().min(free) / self.channels as usize * self.channels as usize;
+        buffer.samples.extend(&samples[..count]);
+        count
+    }
+
+    /// Returns the number of samples waiting to be played.
+    pub fn len(&self) -> usize {
+        self.buffer.lock().unwrap().samples.len()
+    }
+
+    /// Returns `true` if there are no samples waiting to be played.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of samples that can be pushed before the buffer is full.
+    pub fn free(&self) -> usize {
+        let buffer = self.buffer.lock().unwrap();
+        buffer.capacity - buffer.samples.len()
+    }
+
+    /// Returns the duration of the samples waiting to be played.
+    pub fn buffered_duration(&self) -> Duration {
+        let frames = self.len() / self.channels as usize;
+        Duration::from_secs_f64(frames as f64 / self.sample_rate as f64)
+    }
+
+    /// Closes the stream, which ends once the samples already pushed have been played.
+    pub fn close(&self) {
+        self.buffer.lock().unwrap().closed = true;
+    }
+
+    /// Returns `true` if the stream has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.buffer.lock().unwrap().closed
+    }
+
+    /// Returns the number of interleaved channels.
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Returns the number of frames played per second.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+/// The number of samples moved out of the shared buffer at once, to lock it less often.
+const STREAM_CHUNK: usize = 256;
+
+/// The [`rodio::Source`] playing an [`AudioStream`].
+pub struct AudioStreamDecoder {
+    stream: AudioStream,
+    chunk: VecDeque<f32>,
+    /// The number of silent samples to play before checking the buffer again.
+    silence: usize,
+}
+
+impl Iterator for AudioStreamDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if let Some(sample) = self.chunk.pop_front() {
+            return Some(sample);
+        }
+        if self.silence > 0 {
+            self.silence -= 1;
+            return Some(0.0);
+        }
+
+        let mut buffer = self.stream.buffer.lock().unwrap();
+        let count = buffer.samples.len().min(STREAM_CHUNK);
+        if count == 0 {
+            if buffer.closed {
+                return None;
+            }
+            // Play a whole frame of silence, to keep the channels in order.
+            self.silence = self.stream.channels as usize - 1;
+            return Some(0.0);
+        }
+        self.chunk.extend(buffer.samples.drain(..count));
+        drop(buffer);
+        self.chunk.pop_front()
+    }
+}
+
+impl rodio::Source for AudioStreamDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.stream.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.stream.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl Decodable for AudioStream {
+    type DecoderItem = f32;
+    type Decoder = AudioStreamDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        AudioStreamDecoder {
+            stream: self.clone(),
+            chunk: VecDeque::with_capacity(STREAM_CHUNK),
+            silence: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn stream_buffer() {
+        let stream = AudioStream::new(2, 10, 6);
+        // Only whole frames fitting in the buffer are accepted.
+        assert_eq!(stream.push(&[1.0, 2.0, 3.0]), 2);
+        assert_eq!(stream.push(&[3.0, 4.0, 5.0, 6.0, 7.0, 8.0]), 4);
+        assert_eq!(stream.free(), 0);
+        assert_eq!(stream.buffered_duration(), Duration::from_millis(300));
+
+        let mut decoder = stream.decoder();
+        let samples = decoder.by_ref().take(8).collect::<Vec<_>>();
+        // The stream plays silence when it runs out of samples.
+        assert_eq!(samples, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 0.0, 0.0]);
+
+        stream.push(&[9.0, 10.0]);
+        stream.close();
+        assert_eq!(decoder.collect::<Vec<_>>(), [9.0, 10.0]);
+    }
+}
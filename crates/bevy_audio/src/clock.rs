@@ -0,0 +1,238 @@
+use crate::mixer::BusParameters;
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use rodio::{source::SeekError, Source};
+
+/// Counts the frames played by the audio output, so that sinks can start and fade out on the
+/// same frame.
+///
+/// The clock is advanced by its [`ClockSource`], which is played by the output before any sink.
+#[derive(Clone, Debug)]
+pub(crate) struct AudioClock {
+    frames: Arc<AtomicU64>,
+    sample_rate: u32,
+}
+
+impl AudioClock {
+    /// Creates a clock counting frames at the given sample rate.
+    pub(crate) fn new(sample_rate: u32) -> Self {
+        Self {
+            frames: Arc::new(AtomicU64::new(0)),
+            sample_rate,
+        }
+    }
+
+    /// Returns the number of frames the output started playing.
+    ///
+    /// Outside of the audio thread, this is the index of the next frame to be played.
+    pub(crate) fn frames(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of frames of the clock in `duration`.
+    pub(crate) fn frames_in(&self, duration: Duration) -> u64 {
+        (duration.as_nanos() * self.sample_rate as u128 / 1_000_000_000)
+            .try_into()
+            .unwrap_or(u64::MAX)
+    }
+
+    /// Returns `true` once the output plays the frame at index `frame`, or a later one.
+    ///
+    /// This is only meaningful on the audio thread, where the clock has already advanced for the
+    /// frame being played.
+    pub(crate) fn reached(&self, frame: u64) -> bool {
+        self.frames() > frame
+    }
+
+    /// Returns the number of frames played since the frame at index `frame`, not counting the frame
+    /// being played.
+    ///
+    /// This is only meaningful on the audio thread, like [`reached`](Self::reached).
+    pub(crate) fn frames_since(&self, frame: u64) -> u64 {
+        self.frames().saturating_sub(frame + 1)
+    }
+
+    /// Returns the number of frames counted per second.
+    pub(crate) fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the silent source advancing this clock, with the given number of channels.
+    pub(crate) fn source(&self, channels: u16) -> ClockSource {
+        ClockSource {
+            frames: self.frames.clone(),
+            sample_rate: self.sample_rate,
+            channels: channels.max(1),
+            channel: 0,
+        }
+    }
+}
+
+/// An endless silent [`Source`] advancing an [`AudioClock`] at the start of every frame it plays.
+pub(crate) struct ClockSource {
+    frames: Arc<AtomicU64>,
+    sample_rate: u32,
+    channels: u16,
+    channel: u16,
+}
+
+impl Iterator for ClockSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel == 0 {
+            self.frames.fetch_add(1, Ordering::Relaxed);
+        }
+        self.channel = (self.channel + 1) % self.channels;
+        Some(0.0)
+    }
+}
+
+impl Source for ClockSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// A [`Source`] playing silence until its [`AudioClock`] reaches a frame, then playing its input.
+///
+/// If the source is played after that frame, the input starts right away.
+pub(crate) struct StartAt<S> {
+    input: S,
+    clock: AudioClock,
+    start: u64,
+    /// The buses to fade out when the input starts, and the duration of their fade.
+    crossfade: Option<(Vec<Arc<BusParameters>>, Duration)>,
+    started: bool,
+    channel: u16,
+}
+
+impl<S> StartAt<S>
+where
+    S: Source<Item = f32>,
+{
+    pub(crate) fn new(input: S, clock: AudioClock, start: u64) -> Self {
+        Self {
+            input,
+            clock,
+            start,
+            crossfade: None,
+            started: false,
+            channel: 0,
+        }
+    }
+
+    /// Fades out the sinks using the given buses over `duration`, starting on the frame the input
+    /// starts.
+    pub(crate) fn with_crossfade(
+        mut self,
+        buses: Vec<Arc<BusParameters>>,
+        duration: Duration,
+    ) -> Self {
+        self.crossfade = Some((buses, duration));
+        self
+    }
+}
+
+impl<S> Iterator for StartAt<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if !self.started {
+            // Only start on the first channel, so that the channels of the input stay in place.
+            if self.channel != 0 || !self.clock.reached(self.start) {
+                self.channel = (self.channel + 1) % self.input.channels().max(1);
+                return Some(0.0);
+            }
+            self.started = true;
+            if let Some((buses, duration)) = self.crossfade.take() {
+                // The clock already advanced for the frame being played.
+                let frame = self.clock.frames().saturating_sub(1);
+                for bus in buses {
+                    bus.fade_out(frame, duration);
+                }
+            }
+        }
+        self.input.next()
+    }
+}
+
+impl<S> Source for StartAt<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        if self.started {
+            self.input.current_frame_len()
+        } else {
+            // Silence is played frame by frame until the input starts.
+            Some((self.input.channels().max(1) - self.channel) as usize)
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        if self.started {
+            self.input.total_duration()
+        } else {
+            None
+        }
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn start_at_clock_frame() {
+        let clock = AudioClock::new(1000);
+        let mut ticks = clock.source(2);
+        // The output played two frames before the sources are created.
+        ticks.by_ref().take(4).for_each(drop);
+        let play = |start| {
+            let input = SamplesBuffer::new(2, 1000, [1.0f32; 4].as_slice());
+            let mut source = StartAt::new(input, clock.clone(), start);
+            let mut ticks = clock.source(2);
+            core::iter::from_fn(|| {
+                ticks.next();
+                source.next()
+            })
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(play(4), [0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+        // Sources played after their start frame start right away.
+        assert_eq!(play(1), [1.0; 4]);
+    }
+}
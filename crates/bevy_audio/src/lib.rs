@@ -32,6 +32,9 @@ extern crate alloc;
 mod audio;
 mod audio_output;
 mod audio_source;
+mod audio_stream;
+mod clock;
+mod mixer;
 mod pitch;
 mod sinks;
 mod volume;
@@ -42,13 +45,17 @@ mod volume;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, Decodable, GlobalVolume, Pitch,
-        PlaybackSettings, SpatialAudioSink, SpatialListener,
+        AudioBus, AudioMixer, AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, AudioStream,
+        Decodable, GlobalVolume, MixerBus, Pitch, PlaybackSettings, SpatialAudioSink,
+        SpatialListener,
     };
 }
 
 pub use audio::*;
+pub use audio_output::{AudioOutputDevice, NullAudioOutput};
 pub use audio_source::*;
+pub use audio_stream::*;
+pub use mixer::{AudioBus, AudioMixer, BusOutput, MixerBus};
 pub use pitch::*;
 pub use volume::*;
 
@@ -61,6 +68,7 @@ use bevy_ecs::prelude::*;
 use bevy_transform::TransformSystems;
 
 use audio_output::*;
+use mixer::{advance_mixer_fades, update_bus_outputs};
 
 /// Set for the audio playback systems, so they can share a run condition
 #[derive(SystemSet, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
@@ -76,6 +84,8 @@ pub struct AudioPlugin {
     /// The scale factor applied to the positions of audio sources and listeners for
     /// spatial audio.
     pub default_spatial_scale: SpatialScale,
+    /// The device audio is played on.
+    pub output: AudioOutputDevice,
 }
 
 impl Plugin for AudioPlugin {
@@ -86,7 +96,11 @@ impl Plugin for AudioPlugin {
             .register_type::<DefaultSpatialScale>()
             .register_type::<PlaybackMode>()
             .register_type::<PlaybackSettings>()
+            .register_type::<Crossfade>()
+            .register_type::<AudioBus>()
+            .register_type::<AudioMixer>()
             .insert_resource(self.global_volume)
+            .init_resource::<AudioMixer>()
             .insert_resource(DefaultSpatialScale(self.default_spatial_scale))
             .configure_sets(
                 PostUpdate,
@@ -96,9 +110,28 @@ impl Plugin for AudioPlugin {
             )
             .add_systems(
                 PostUpdate,
-                (update_emitter_positions, update_listener_positions).in_set(AudioPlaybackSystems),
+                (
+                    update_emitter_positions,
+                    update_listener_positions,
+                    update_bus_outputs.after(advance_mixer_fades),
+                )
+                    .in_set(AudioPlaybackSystems),
             )
-            .init_resource::<AudioOutput>();
+            .add_systems(PostUpdate, advance_mixer_fades);
+
+        match self.output {
+            AudioOutputDevice::Default => {
+                app.init_resource::<AudioOutput>();
+            }
+            AudioOutputDevice::Null {
+                channels,
+                sample_rate,
+            } => {
+                let output = NullAudioOutput::new(channels, sample_rate);
+                app.insert_resource(AudioOutput::null(&output))
+                    .insert_resource(output);
+            }
+        }
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         {
@@ -107,6 +140,7 @@ impl Plugin for AudioPlugin {
         }
 
         app.add_audio_source::<Pitch>();
+        app.add_audio_source::<AudioStream>();
     }
 }
 
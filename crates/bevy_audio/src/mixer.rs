@@ -0,0 +1,755 @@
+use crate::{clock::AudioClock, AudioSink, SpatialAudioSink, Volume};
+use alloc::{borrow::Cow, sync::Arc, vec::Vec};
+use bevy_ecs::prelude::*;
+use bevy_math::ops;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::prelude::*;
+use bevy_time::{Real, Time};
+use core::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+    time::Duration,
+};
+use rodio::{source::SeekError, Source};
+
+/// Routes the audio of an entity to a bus of the [`AudioMixer`].
+///
+/// Audio players without this component, or routed to a bus that does not exist, play through
+/// the [master bus](AudioMixer::MASTER). The route can be changed while the audio is playing.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::AssetServer;
+/// # use bevy_audio::{AudioBus, AudioPlayer, PlaybackSettings};
+/// fn play_music(asset_server: Res<AssetServer>, mut commands: Commands) {
+///     commands.spawn((
+///         AudioPlayer::new(asset_server.load("music.ogg")),
+///         PlaybackSettings::LOOP,
+///         AudioBus::new("music"),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Clone, Debug, PartialEq, Hash)]
+pub struct AudioBus(pub Cow<'static, str>);
+
+impl AudioBus {
+    /// Creates a route to the bus with the given name.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+}
+
+impl Default for AudioBus {
+    fn default() -> Self {
+        Self::new(AudioMixer::MASTER)
+    }
+}
+
+/// The named buses audio is mixed through, with their volume and effects.
+///
+/// Each bus outputs to a parent bus, up to the [master bus](Self::MASTER), so that the
+/// volume and effects of a bus apply to all the buses below it. Changes to the mixer apply to
+/// audio that is already playing.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioMixer, MixerBus, Volume};
+/// # use core::time::Duration;
+/// fn setup_mixer(mut mixer: ResMut<AudioMixer>) {
+///     mixer.add_bus("music", MixerBus::default().with_volume(Volume::Linear(0.8)));
+///     mixer.add_bus("sfx", MixerBus::default());
+///     mixer.add_bus("voice", MixerBus::default());
+/// }
+///
+/// fn duck_music_under_dialogue(mut mixer: ResMut<AudioMixer>) {
+///     if let Some(music) = mixer.bus_mut("music") {
+///         music.fade_to(Volume::Linear(0.2), Duration::from_millis(300));
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default, Clone, Debug)]
+pub struct AudioMixer {
+    buses: HashMap<Cow<'static, str>, MixerBus>,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        let mut buses = HashMap::default();
+        buses.insert(
+            Cow::Borrowed(Self::MASTER),
+            MixerBus {
+                parent: None,
+                ..MixerBus::default()
+            },
+        );
+        Self { buses }
+    }
+}
+
+impl AudioMixer {
+    /// The name of the bus all other buses output to by default.
+    pub const MASTER: &'static str = "master";
+
+    /// Adds a bus with the given name, returning the bus it replaced if any.
+    pub fn add_bus(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        bus: MixerBus,
+    ) -> Option<MixerBus> {
+        self.buses.insert(name.into(), bus)
+    }
+
+    /// Removes the bus with the given name, returning it.
+    ///
+    /// The master bus cannot be removed.
+    pub fn remove_bus(&mut self, name: &str) -> Option<MixerBus> {
+        if name == Self::MASTER {
+            return None;
+        }
+        self.buses.remove(name)
+    }
+
+    /// Returns the bus with the given name.
+    pub fn bus(&self, name: &str) -> Option<&MixerBus> {
+        self.buses.get(name)
+    }
+
+    /// Returns the bus with the given name mutably.
+    pub fn bus_mut(&mut self, name: &str) -> Option<&mut MixerBus> {
+        self.buses.get_mut(name)
+    }
+
+    /// Returns the master bus.
+    pub fn master(&self) -> &MixerBus {
+        &self.buses[Self::MASTER]
+    }
+
+    /// Returns the master bus mutably.
+    pub fn master_mut(&mut self) -> &mut MixerBus {
+        self.buses.get_mut(Self::MASTER).unwrap()
+    }
+
+    /// Returns an iterator over the buses and their names.
+    pub fn buses(&self) -> impl Iterator<Item = (&str, &MixerBus)> {
+        self.buses.iter().map(|(name, bus)| (name.as_ref(), bus))
+    }
+
+    /// Returns the combined volume and effects of the bus with the given name and its parents.
+    ///
+    /// Unknown buses, including unknown parents, are treated as the master bus.
+    pub fn output(&self, name: &str) -> BusOutput {
+        let bus = |name: &str| self.buses.get(name).unwrap_or_else(|| self.master());
+        let mut output = BusOutput::default();
+        let mut current = Some(bus(name));
+        // Bounded by the number of buses, in case the parents form a cycle.
+        for _ in 0..self.buses.len() {
+            let Some(current_bus) = current else {
+                break;
+            };
+            if current_bus.muted {
+                output.gain = 0.0;
+            }
+            output.gain *= current_bus.volume.to_linear();
+            output.low_pass = match (output.low_pass, current_bus.low_pass) {
+                (Some(cutoff), Some(parent_cutoff)) => Some(cutoff.min(parent_cutoff)),
+                (cutoff, parent_cutoff) => cutoff.or(parent_cutoff),
+            };
+            output.reverb_send = output.reverb_send.max(current_bus.reverb_send);
+            current = current_bus.parent.as_deref().map(bus);
+        }
+        output
+    }
+
+    /// Advances the fades of every bus by `delta`.
+    pub fn advance_fades(&mut self, delta: Duration) {
+        for bus in self.buses.values_mut() {
+            bus.advance_fade(delta);
+        }
+    }
+
+    fn is_fading(&self) -> bool {
+        self.buses.values().any(MixerBus::is_fading)
+    }
+}
+
+/// A bus of the [`AudioMixer`].
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default, Clone, Debug)]
+pub struct MixerBus {
+    volume: Volume,
+    muted: bool,
+    parent: Option<Cow<'static, str>>,
+    low_pass: Option<f32>,
+    reverb_send: f32,
+    fade: Option<VolumeFade>,
+}
+
+impl Default for MixerBus {
+    fn default() -> Self {
+        Self {
+            volume: Volume::Linear(1.0),
+            muted: false,
+            parent: Some(Cow::Borrowed(AudioMixer::MASTER)),
+            low_pass: None,
+            reverb_send: 0.0,
+            fade: None,
+        }
+    }
+}
+
+impl MixerBus {
+    /// Helper to set the volume of the bus.
+    pub fn with_volume(mut self, volume: Volume) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Helper to set the bus this bus outputs to.
+    pub fn with_parent(mut self, parent: impl Into<Cow<'static, str>>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+
+    /// Helper to add a low-pass filter to the bus.
+    pub fn with_low_pass(mut self, cutoff: f32) -> Self {
+        self.low_pass = Some(cutoff);
+        self
+    }
+
+    /// Helper to send some of the audio of the bus to the reverb.
+    pub fn with_reverb_send(mut self, amount: f32) -> Self {
+        self.set_reverb_send(amount);
+        self
+    }
+
+    /// Returns the volume of the bus.
+    pub fn volume(&self) -> Volume {
+        self.volume
+    }
+
+    /// Changes the volume of the bus, stopping any fade.
+    pub fn set_volume(&mut self, volume: Volume) {
+        self.volume = volume;
+        self.fade = None;
+    }
+
+    /// Fades the volume of the bus to `volume` over `duration`.
+    ///
+    /// The fade progresses with [`Time<Real>`], so that it continues while the game is paused.
+    pub fn fade_to(&mut self, volume: Volume, duration: Duration) {
+        self.fade = Some(VolumeFade {
+            from: self.volume,
+            to: volume,
+            duration,
+            elapsed: Duration::ZERO,
+        });
+        self.advance_fade(Duration::ZERO);
+    }
+
+    /// Returns `true` if the volume of the bus is fading.
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    /// Returns `true` if the bus is muted.
+    ///
+    /// Muting a bus keeps its volume, which is restored when it is unmuted.
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Mutes or unmutes the bus.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    /// Returns the name of the bus this bus outputs to, or `None` if it outputs directly.
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// Changes the bus this bus outputs to, or `None` to output directly.
+    pub fn set_parent(&mut self, parent: Option<Cow<'static, str>>) {
+        self.parent = parent;
+    }
+
+    /// Returns the cutoff frequency in hertz of the low-pass filter of the bus, if any.
+    pub fn low_pass(&self) -> Option<f32> {
+        self.low_pass
+    }
+
+    /// Changes the cutoff frequency in hertz of the low-pass filter of the bus, or removes it.
+    ///
+    /// When a bus and its parents have low-pass filters, the lowest cutoff is used.
+    pub fn set_low_pass(&mut self, cutoff: Option<f32>) {
+        self.low_pass = cutoff;
+    }
+
+    /// Returns the amount of audio sent to the reverb, between `0.0` and `1.0`.
+    pub fn reverb_send(&self) -> f32 {
+        self.reverb_send
+    }
+
+    /// Changes the amount of audio sent to the reverb, between `0.0` and `1.0`.
+    ///
+    /// When a bus and its parents send audio to the reverb, the largest amount is used.
+    pub fn set_reverb_send(&mut self, amount: f32) {
+        self.reverb_send = amount.clamp(0.0, 1.0);
+    }
+
+    fn advance_fade(&mut self, delta: Duration) {
+        let Some(fade) = &mut self.fade else {
+            return;
+        };
+        fade.elapsed += delta;
+        if fade.elapsed >= fade.duration {
+            self.volume = fade.to;
+            self.fade = None;
+        } else {
+            let t = fade.elapsed.as_secs_f32() / fade.duration.as_secs_f32();
+            let from = fade.from.to_linear();
+            self.volume = Volume::Linear(from + (fade.to.to_linear() - from) * t);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Clone, Debug)]
+struct VolumeFade {
+    from: Volume,
+    to: Volume,
+    duration: Duration,
+    elapsed: Duration,
+}
+
+/// The combined volume and effects of a bus of the [`AudioMixer`] and its parents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BusOutput {
+    /// The linear gain, including the mute state of the buses.
+    pub gain: f32,
+    /// The cutoff frequency in hertz of the low-pass filter, if any.
+    pub low_pass: Option<f32>,
+    /// The amount of audio sent to the reverb.
+    pub reverb_send: f32,
+}
+
+impl Default for BusOutput {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            low_pass: None,
+            reverb_send: 0.0,
+        }
+    }
+}
+
+/// The output of the bus a sink is routed to, shared with the audio thread.
+#[derive(Debug)]
+pub(crate) struct BusParameters {
+    gain: AtomicU32,
+    /// The cutoff of the low-pass filter, or `0.0` if there is none.
+    low_pass: AtomicU32,
+    reverb_send: AtomicU32,
+    /// Set once the sink should fade out, after the start frame and duration have been stored.
+    fade_out_requested: AtomicBool,
+    fade_out_start: AtomicU64,
+    fade_out_duration: AtomicU64,
+}
+
+impl Default for BusParameters {
+    fn default() -> Self {
+        Self::new(BusOutput::default())
+    }
+}
+
+impl BusParameters {
+    pub(crate) fn new(output: BusOutput) -> Self {
+        let parameters = Self {
+            gain: AtomicU32::new(0),
+            low_pass: AtomicU32::new(0),
+            reverb_send: AtomicU32::new(0),
+            fade_out_requested: AtomicBool::new(false),
+            fade_out_start: AtomicU64::new(0),
+            fade_out_duration: AtomicU64::new(0),
+        };
+        parameters.set(output);
+        parameters
+    }
+
+    pub(crate) fn set(&self, output: BusOutput) {
+        let store =
+            |atomic: &AtomicU32, value: f32| atomic.store(value.to_bits(), Ordering::Relaxed);
+        store(&self.gain, output.gain);
+        store(&self.low_pass, output.low_pass.unwrap_or(0.0));
+        store(&self.reverb_send, output.reverb_send);
+    }
+
+    /// Fades the sink out over `duration` once the [`AudioClock`] of the output reaches the frame
+    /// at index `start`, then ends it.
+    pub(crate) fn fade_out(&self, start: u64, duration: Duration) {
+        self.fade_out_start.store(start, Ordering::Relaxed);
+        self.fade_out_duration.store(
+            duration.as_nanos().try_into().unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        self.fade_out_requested.store(true, Ordering::Release);
+    }
+
+    fn gain(&self) -> f32 {
+        f32::from_bits(self.gain.load(Ordering::Relaxed))
+    }
+
+    fn low_pass(&self) -> f32 {
+        f32::from_bits(self.low_pass.load(Ordering::Relaxed))
+    }
+
+    fn reverb_send(&self) -> f32 {
+        f32::from_bits(self.reverb_send.load(Ordering::Relaxed))
+    }
+}
+
+/// How quickly the gain follows changes, per sample, to avoid clicks.
+const GAIN_SMOOTHING: f32 = 0.005;
+
+/// The delays of the comb filters making up the reverb, in seconds.
+const REVERB_DELAYS: [f32; 3] = [0.0297, 0.0371, 0.0411];
+
+/// How much of the reverb is fed back into it.
+const REVERB_FEEDBACK: f32 = 0.7;
+
+/// A [`Source`] applying the volume and effects of a bus to the samples of another source.
+pub(crate) struct BusSource<S> {
+    input: S,
+    parameters: Arc<BusParameters>,
+    clock: AudioClock,
+    gain: f32,
+    /// The sample rate and cutoff the low-pass coefficient was computed for.
+    low_pass_key: (u32, f32),
+    low_pass_coefficient: f32,
+    /// The last output of the low-pass filter for each channel.
+    low_pass_state: Vec<f32>,
+    /// The interleaved delay lines of the reverb, with their current position.
+    reverb: Vec<(Vec<f32>, usize)>,
+    /// The sample rate and channel count the delay lines were allocated for.
+    reverb_key: (u32, u16),
+    channel: usize,
+    fade_out: Option<FadeOut>,
+}
+
+/// The progress of a fade out, in samples.
+struct FadeOut {
+    /// The frame of the [`AudioClock`] the fade out starts on.
+    start: u64,
+    started: bool,
+    length: u64,
+    position: u64,
+}
+
+impl<S> BusSource<S>
+where
+    S: Source<Item = f32>,
+{
+    pub(crate) fn new(input: S, parameters: Arc<BusParameters>, clock: AudioClock) -> Self {
+        Self {
+            input,
+            gain: parameters.gain(),
+            parameters,
+            clock,
+            low_pass_key: (0, 0.0),
+            low_pass_coefficient: 1.0,
+            low_pass_state: Vec::new(),
+            reverb: Vec::new(),
+            reverb_key: (0, 0),
+            channel: 0,
+            fade_out: None,
+        }
+    }
+
+    fn low_pass(&mut self, sample: f32, sample_rate: u32, channels: u16) -> f32 {
+        if self.low_pass_state.len() != channels as usize {
+            self.low_pass_state = alloc::vec![0.0; channels as usize];
+        }
+        let state = &mut self.low_pass_state[self.channel];
+        let cutoff = self.parameters.low_pass();
+        if cutoff <= 0.0 {
+            // Keep tracking the input, so that enabling the filter does not click.
+            *state = sample;
+            return sample;
+        }
+        if self.low_pass_key != (sample_rate, cutoff) {
+            self.low_pass_key = (sample_rate, cutoff);
+            self.low_pass_coefficient = 1.0 - ops::exp(-TAU * cutoff / sample_rate as f32);
+        }
+        *state += self.low_pass_coefficient * (sample - *state);
+        *state
+    }
+
+    /// Returns the factor applied to the current sample by the fade out, or `None` once it ended.
+    fn fade_out(&mut self, sample_rate: u32, channels: u16) -> Option<f32> {
+        if self.fade_out.is_none() && self.parameters.fade_out_requested.load(Ordering::Acquire) {
+            // Count whole frames, so that every channel fades out the same way.
+            let nanos = self.parameters.fade_out_duration.load(Ordering::Relaxed) as u128;
+            self.fade_out = Some(FadeOut {
+                start: self.parameters.fade_out_start.load(Ordering::Relaxed),
+                started: false,
+                length: (nanos * sample_rate as u128 / 1_000_000_000) as u64 * channels as u64,
+                position: 0,
+            });
+        }
+        let Some(fade_out) = &mut self.fade_out else {
+            return Some(1.0);
+        };
+        if !fade_out.started {
+            // Only start on the first channel, so that every channel fades out the same way.
+            if self.channel != 0 || !self.clock.reached(fade_out.start) {
+                return Some(1.0);
+            }
+            fade_out.started = true;
+            // Skip the frames that already played, if this sink was played before the fade out was
+            // requested on its start frame.
+            let late = self.clock.frames_since(fade_out.start);
+            fade_out.position = (late as u128 * sample_rate as u128
+                / self.clock.sample_rate() as u128) as u64
+                * channels as u64;
+        }
+        if fade_out.position >= fade_out.length {
+            return None;
+        }
+        let frame = fade_out.position / channels as u64 * channels as u64;
+        fade_out.position += 1;
+        Some(1.0 - frame as f32 / fade_out.length as f32)
+    }
+
+    fn reverb(&mut self, sample: f32, sample_rate: u32, channels: u16) -> f32 {
+        let send = self.parameters.reverb_send();
+        if self.reverb.is_empty() && send <= 0.0 {
+            return sample;
+        }
+        if self.reverb_key != (sample_rate, channels) {
+            self.reverb_key = (sample_rate, channels);
+            self.reverb = REVERB_DELAYS
+                .iter()
+                .map(|delay| {
+                    let frames = ((delay * sample_rate as f32) as usize).max(1);
+                    (alloc::vec![0.0; frames * channels as usize], 0)
+                })
+                .collect();
+        }
+        let mut wet = 0.0;
+        for (line, position) in &mut self.reverb {
+            let delayed = line[*position];
+            line[*position] = sample * send + delayed * REVERB_FEEDBACK;
+            *position = (*position + 1) % line.len();
+            wet += delayed;
+        }
+        sample + wet / REVERB_DELAYS.len() as f32
+    }
+}
+
+impl<S> Iterator for BusSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.input.next()?;
+        let sample_rate = self.input.sample_rate().max(1);
+        let channels = self.input.channels().max(1);
+        if self.channel >= channels as usize {
+            self.channel = 0;
+        }
+
+        let fade = self.fade_out(sample_rate, channels)?;
+        let sample = self.low_pass(sample, sample_rate, channels);
+        let sample = self.reverb(sample, sample_rate, channels);
+        self.gain += (self.parameters.gain() - self.gain) * GAIN_SMOOTHING;
+
+        self.channel = (self.channel + 1) % channels as usize;
+        Some(sample * self.gain * fade)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<S> Source for BusSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+/// Advances the fades of the [`AudioMixer`] buses.
+pub(crate) fn advance_mixer_fades(mut mixer: ResMut<AudioMixer>, time: Option<Res<Time<Real>>>) {
+    let Some(time) = time else {
+        return;
+    };
+    // Avoid triggering change detection when nothing is fading.
+    if mixer.is_fading() {
+        mixer.advance_fades(time.delta());
+    }
+}
+
+/// Applies the output of the [`AudioMixer`] buses to the sinks routed to them.
+pub(crate) fn update_bus_outputs(
+    mixer: Res<AudioMixer>,
+    sinks: Query<(Option<&AudioBus>, AnyOf<(&AudioSink, &SpatialAudioSink)>)>,
+) {
+    let mut outputs = HashMap::<&str, BusOutput>::default();
+    for (bus, (sink, spatial_sink)) in &sinks {
+        let name = bus.map_or(AudioMixer::MASTER, |bus| bus.0.as_ref());
+        let output = *outputs.entry(name).or_insert_with(|| mixer.output(name));
+        if let Some(sink) = sink {
+            sink.bus.set(output);
+        }
+        if let Some(sink) = spatial_sink {
+            sink.bus.set(output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    fn bus_source<S: Source<Item = f32>>(input: S, parameters: Arc<BusParameters>) -> Vec<f32> {
+        let clock = AudioClock::new(input.sample_rate());
+        let mut ticks = clock.source(input.channels());
+        let mut source = BusSource::new(input, parameters, clock);
+        // Advance the clock before every frame, like the output does.
+        core::iter::from_fn(|| {
+            ticks.next();
+            source.next()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn bus_output() {
+        let mut mixer = AudioMixer::default();
+        mixer.master_mut().set_volume(Volume::Linear(0.5));
+        mixer.add_bus(
+            "music",
+            MixerBus::default()
+                .with_volume(Volume::Linear(0.5))
+                .with_low_pass(1000.0),
+        );
+        mixer.add_bus(
+            "underwater",
+            MixerBus::default()
+                .with_parent("music")
+                .with_low_pass(500.0)
+                .with_reverb_send(0.3),
+        );
+
+        assert_eq!(mixer.output(AudioMixer::MASTER).gain, 0.5);
+        assert_eq!(
+            mixer.output("underwater"),
+            BusOutput {
+                gain: 0.25,
+                low_pass: Some(500.0),
+                reverb_send: 0.3,
+            }
+        );
+        // Unknown buses play through the master bus.
+        assert_eq!(mixer.output("missing").gain, 0.5);
+
+        mixer.bus_mut("music").unwrap().set_muted(true);
+        assert_eq!(mixer.output("underwater").gain, 0.0);
+        assert_eq!(mixer.bus("music").unwrap().volume(), Volume::Linear(0.5));
+
+        assert!(mixer.remove_bus(AudioMixer::MASTER).is_none());
+        assert!(mixer.remove_bus("music").is_some());
+        assert_eq!(mixer.output("underwater").low_pass, Some(500.0));
+        assert_eq!(mixer.output("underwater").gain, 0.5);
+    }
+
+    #[test]
+    fn bus_fade() {
+        let mut mixer = AudioMixer::default();
+        mixer.add_bus("music", MixerBus::default());
+        let music = mixer.bus_mut("music").unwrap();
+        music.fade_to(Volume::Linear(0.0), Duration::from_secs(2));
+        assert!(music.is_fading());
+
+        mixer.advance_fades(Duration::from_secs(1));
+        assert_eq!(mixer.output("music").gain, 0.5);
+        mixer.advance_fades(Duration::from_secs(1));
+        assert_eq!(mixer.output("music").gain, 0.0);
+        assert!(!mixer.is_fading());
+
+        // Setting the volume stops the fade.
+        let music = mixer.bus_mut("music").unwrap();
+        music.fade_to(Volume::Linear(1.0), Duration::from_secs(2));
+        music.set_volume(Volume::Linear(0.2));
+        mixer.advance_fades(Duration::from_secs(1));
+        assert_eq!(mixer.bus("music").unwrap().volume(), Volume::Linear(0.2));
+    }
+
+    #[test]
+    fn bus_source_output() {
+        let parameters = Arc::new(BusParameters::new(BusOutput {
+            gain: 0.5,
+            ..BusOutput::default()
+        }));
+        let input = SamplesBuffer::new(1, 1000, [1.0f32; 4].as_slice());
+        let samples = bus_source(input, parameters.clone());
+        assert_eq!(samples, [0.5; 4]);
+
+        // The low-pass filter smooths the steps of the signal.
+        parameters.set(BusOutput {
+            low_pass: Some(100.0),
+            ..BusOutput::default()
+        });
+        let input = SamplesBuffer::new(2, 1000, [1.0f32; 8].as_slice());
+        let samples = bus_source(input, parameters.clone());
+        assert!(samples[0] > 0.0 && samples[0] < 1.0);
+        assert_eq!(samples[0], samples[1]);
+        assert!(samples[2] > samples[0] && samples[6] < 1.0);
+
+        // The reverb keeps the signal going after it stops.
+        parameters.set(BusOutput {
+            reverb_send: 1.0,
+            ..BusOutput::default()
+        });
+        let mut input = alloc::vec![0.0f32; 100];
+        input[0] = 1.0;
+        let input = SamplesBuffer::new(1, 1000, input);
+        let samples = bus_source(input, parameters);
+        assert_eq!(samples[0], 1.0);
+        assert!(samples[29..].iter().any(|sample| *sample > 0.0));
+    }
+
+    #[test]
+    fn bus_fade_out() {
+        let parameters = Arc::new(BusParameters::new(BusOutput::default()));
+        parameters.fade_out(2, Duration::from_millis(4));
+        let input = SamplesBuffer::new(1, 1000, [1.0f32; 10].as_slice());
+        let samples = bus_source(input, parameters);
+        // The source ends once it has faded out.
+        assert_eq!(samples, [1.0, 1.0, 1.0, 0.75, 0.5, 0.25]);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::Volume;
+use crate::{mixer::BusParameters, Volume};
+use alloc::sync::Arc;
 use bevy_ecs::component::Component;
 use bevy_math::Vec3;
 use bevy_transform::prelude::Transform;
@@ -143,6 +144,9 @@ pub struct AudioSink {
     /// user's intended volume setting, even if the underlying sink's volume is
     /// 0.
     pub(crate) managed_volume: Option<Volume>,
+
+    /// The output of the [`AudioMixer`](crate::AudioMixer) bus the sink is routed to.
+    pub(crate) bus: Arc<BusParameters>,
 }
 
 impl AudioSink {
@@ -151,8 +155,16 @@ impl AudioSink {
         Self {
             sink,
             managed_volume: None,
+            bus: Arc::default(),
         }
     }
+
+    /// Fades the audio out over `duration`, then ends it.
+    ///
+    /// This only applies to sinks playing an [`AudioPlayer`](crate::AudioPlayer).
+    pub fn fade_out(&self, duration: Duration) {
+        self.bus.fade_out(0, duration);
+    }
 }
 
 impl AudioSinkPlayback for AudioSink {
@@ -243,6 +255,9 @@ pub struct SpatialAudioSink {
     /// user's intended volume setting, even if the underlying sink's volume is
     /// 0.
     pub(crate) managed_volume: Option<Volume>,
+
+    /// The output of the [`AudioMixer`](crate::AudioMixer) bus the sink is routed to.
+    pub(crate) bus: Arc<BusParameters>,
 }
 
 impl SpatialAudioSink {
@@ -251,8 +266,16 @@ impl SpatialAudioSink {
         Self {
             sink,
             managed_volume: None,
+            bus: Arc::default(),
         }
     }
+
+    /// Fades the audio out over `duration`, then ends it.
+    ///
+    /// This only applies to sinks playing an [`AudioPlayer`](crate::AudioPlayer).
+    pub fn fade_out(&self, duration: Duration) {
+        self.bus.fade_out(0, duration);
+    }
 }
 
 impl AudioSinkPlayback for SpatialAudioSink {
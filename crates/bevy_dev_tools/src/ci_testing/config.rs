@@ -1,4 +1,11 @@
 use bevy_ecs::prelude::*;
+use bevy_input::{
+    gamepad::{GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    touch::TouchPhase,
+};
+use bevy_math::Vec2;
 use serde::Deserialize;
 
 /// A configuration struct for automated CI testing.
@@ -46,6 +53,92 @@ pub enum CiTestingEvent {
     AppExit,
     /// Sends a [`CiTestingCustomEvent`] using the given [`String`].
     Custom(String),
+    /// Presses a key, sending a [`KeyboardInput`] event to the primary window.
+    ///
+    /// The logical key of the event is [`Key::Unidentified`], so no text is input.
+    ///
+    /// [`KeyboardInput`]: bevy_input::keyboard::KeyboardInput
+    /// [`Key::Unidentified`]: bevy_input::keyboard::Key::Unidentified
+    KeyPress(KeyCode),
+    /// Releases a key, sending a [`KeyboardInput`] event to the primary window.
+    ///
+    /// [`KeyboardInput`]: bevy_input::keyboard::KeyboardInput
+    KeyRelease(KeyCode),
+    /// Presses a mouse button, sending a [`MouseButtonInput`] event to the primary window.
+    ///
+    /// [`MouseButtonInput`]: bevy_input::mouse::MouseButtonInput
+    MousePress(MouseButton),
+    /// Releases a mouse button, sending a [`MouseButtonInput`] event to the primary window.
+    ///
+    /// [`MouseButtonInput`]: bevy_input::mouse::MouseButtonInput
+    MouseRelease(MouseButton),
+    /// Moves the cursor to the given position in logical pixels, relative to the top-left corner
+    /// of the primary window.
+    ///
+    /// This updates the cursor position of the window, and sends [`CursorMoved`] and
+    /// [`MouseMotion`] events.
+    ///
+    /// [`CursorMoved`]: bevy_window::CursorMoved
+    /// [`MouseMotion`]: bevy_input::mouse::MouseMotion
+    CursorMove(Vec2),
+    /// Scrolls the mouse wheel by the given number of lines, sending a [`MouseWheel`] event.
+    ///
+    /// [`MouseWheel`]: bevy_input::mouse::MouseWheel
+    MouseWheel(Vec2),
+    /// Sends a [`TouchInput`] event to the primary window.
+    ///
+    /// [`TouchInput`]: bevy_input::touch::TouchInput
+    Touch {
+        /// The unique identifier of the finger.
+        id: u64,
+        /// The phase of the touch.
+        phase: TouchPhase,
+        /// The position of the finger in logical pixels.
+        position: Vec2,
+    },
+    /// Presses a button of a virtual gamepad.
+    ///
+    /// The virtual gamepad is connected the first time any gamepad input is sent.
+    GamepadPress(GamepadButton),
+    /// Releases a button of a virtual gamepad.
+    GamepadRelease(GamepadButton),
+    /// Moves an axis of a virtual gamepad to the given value.
+    GamepadAxis(GamepadAxis, f32),
+    /// Checks the value of a reflected resource, and stops the program with an error if it
+    /// doesn't match.
+    ///
+    /// The resource must be registered with `#[reflect(Resource)]`.
+    AssertResource {
+        /// The fully-qualified type name of the resource.
+        resource: String,
+        /// The [path] of the field to check within the resource. Checks the whole resource
+        /// if empty.
+        ///
+        /// [path]: bevy_reflect::GetPath
+        #[serde(default)]
+        path: String,
+        /// The expected value, in RON.
+        value: String,
+    },
+    /// Checks the value of a reflected component on every entity that has it, and stops the
+    /// program with an error if any of them doesn't match, or if no entity has it.
+    ///
+    /// The component must be registered with `#[reflect(Component)]`.
+    AssertComponent {
+        /// The fully-qualified type name of the component.
+        component: String,
+        /// Only check entities with this [`Name`](bevy_ecs::name::Name), if set.
+        #[serde(default)]
+        name: Option<String>,
+        /// The [path] of the field to check within the component. Checks the whole component
+        /// if empty.
+        ///
+        /// [path]: bevy_reflect::GetPath
+        #[serde(default)]
+        path: String,
+        /// The expected value, in RON.
+        value: String,
+    },
 }
 
 /// A custom event that can be configured from a configuration file for CI testing.
@@ -66,6 +159,11 @@ mod tests {
     events: [
         (100, Custom("Hello, world!")),
         (200, Screenshot),
+        (250, KeyPress(Enter)),
+        (251, CursorMove((10.0, 20.0))),
+        (252, Touch(id: 1, phase: Started, position: (5.0, 5.0))),
+        (253, GamepadAxis(LeftStickX, 0.5)),
+        (254, AssertResource(resource: "my_game::Score", path: ".0", value: "10")),
         (300, AppExit),
     ],
 )"#;
@@ -77,6 +175,28 @@ mod tests {
             events: vec![
                 CiTestingEventOnFrame(100, CiTestingEvent::Custom("Hello, world!".into())),
                 CiTestingEventOnFrame(200, CiTestingEvent::Screenshot),
+                CiTestingEventOnFrame(250, CiTestingEvent::KeyPress(KeyCode::Enter)),
+                CiTestingEventOnFrame(251, CiTestingEvent::CursorMove(Vec2::new(10.0, 20.0))),
+                CiTestingEventOnFrame(
+                    252,
+                    CiTestingEvent::Touch {
+                        id: 1,
+                        phase: TouchPhase::Started,
+                        position: Vec2::splat(5.0),
+                    },
+                ),
+                CiTestingEventOnFrame(
+                    253,
+                    CiTestingEvent::GamepadAxis(GamepadAxis::LeftStickX, 0.5),
+                ),
+                CiTestingEventOnFrame(
+                    254,
+                    CiTestingEvent::AssertResource {
+                        resource: "my_game::Score".into(),
+                        path: ".0".into(),
+                        value: "10".into(),
+                    },
+                ),
                 CiTestingEventOnFrame(300, CiTestingEvent::AppExit),
             ],
         };
@@ -1,10 +1,41 @@
 use super::config::*;
 use bevy_app::AppExit;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    world::EntityRef,
+};
+use bevy_input::{
+    gamepad::{
+        GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+        RawGamepadButtonChangedEvent, RawGamepadEvent,
+    },
+    keyboard::{Key, KeyCode, KeyboardInput, NativeKey},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    touch::TouchInput,
+    ButtonState,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{serde::TypedReflectDeserializer, GetPath, Reflect, TypeRegistry};
 use bevy_render::view::screenshot::{save_to_disk, Screenshot};
-use tracing::{debug, info};
+use bevy_window::{CursorMoved, PrimaryWindow, Window};
+use serde::de::DeserializeSeed;
+use tracing::{debug, error, info};
 
-pub(crate) fn send_events(world: &mut World, mut current_frame: Local<u32>) {
+/// The state of the input devices simulated by [`send_events`].
+#[derive(Default)]
+pub(crate) struct VirtualInput {
+    /// The virtual gamepad, once it has been connected.
+    gamepad: Option<Entity>,
+    /// The last position the cursor was moved to.
+    cursor_position: Option<Vec2>,
+}
+
+pub(crate) fn send_events(
+    world: &mut World,
+    mut current_frame: Local<u32>,
+    mut input: Local<VirtualInput>,
+) {
     let mut config = world.resource_mut::<CiTestingConfig>();
 
     // Take all events for the current frame, leaving all the remaining alone.
@@ -42,8 +73,286 @@ pub(crate) fn send_events(world: &mut World, mut current_frame: Local<u32>) {
             CiTestingEvent::Custom(event_string) => {
                 world.send_event(CiTestingCustomEvent(event_string));
             }
+            CiTestingEvent::KeyPress(key_code) => {
+                send_keyboard_input(world, key_code, ButtonState::Pressed);
+            }
+            CiTestingEvent::KeyRelease(key_code) => {
+                send_keyboard_input(world, key_code, ButtonState::Released);
+            }
+            CiTestingEvent::MousePress(button) => {
+                send_mouse_button_input(world, button, ButtonState::Pressed);
+            }
+            CiTestingEvent::MouseRelease(button) => {
+                send_mouse_button_input(world, button, ButtonState::Released);
+            }
+            CiTestingEvent::CursorMove(position) => {
+                let window = primary_window(world);
+                if let Ok(mut window) = world
+                    .query_filtered::<&mut Window, With<PrimaryWindow>>()
+                    .single_mut(world)
+                {
+                    window.set_cursor_position(Some(position));
+                }
+                let delta = input.cursor_position.map(|previous| position - previous);
+                input.cursor_position = Some(position);
+                world.send_event(CursorMoved {
+                    window,
+                    position,
+                    delta,
+                });
+                if let Some(delta) = delta {
+                    world.send_event(MouseMotion { delta });
+                }
+            }
+            CiTestingEvent::MouseWheel(lines) => {
+                let window = primary_window(world);
+                world.send_event(MouseWheel {
+                    unit: MouseScrollUnit::Line,
+                    x: lines.x,
+                    y: lines.y,
+                    window,
+                });
+            }
+            CiTestingEvent::Touch {
+                id,
+                phase,
+                position,
+            } => {
+                let window = primary_window(world);
+                world.send_event(TouchInput {
+                    phase,
+                    position,
+                    window,
+                    force: None,
+                    id,
+                });
+            }
+            CiTestingEvent::GamepadPress(button) => {
+                let gamepad = virtual_gamepad(world, &mut input);
+                let event = RawGamepadButtonChangedEvent::new(gamepad, button, 1.0);
+                world.send_event(RawGamepadEvent::from(event));
+                world.send_event(event);
+            }
+            CiTestingEvent::GamepadRelease(button) => {
+                let gamepad = virtual_gamepad(world, &mut input);
+                let event = RawGamepadButtonChangedEvent::new(gamepad, button, 0.0);
+                world.send_event(RawGamepadEvent::from(event));
+                world.send_event(event);
+            }
+            CiTestingEvent::GamepadAxis(axis, value) => {
+                let gamepad = virtual_gamepad(world, &mut input);
+                let event = RawGamepadAxisChangedEvent::new(gamepad, axis, value);
+                world.send_event(RawGamepadEvent::from(event));
+                world.send_event(event);
+            }
+            CiTestingEvent::AssertResource {
+                resource,
+                path,
+                value,
+            } => {
+                let result = check_resource(world, &resource, &path, &value);
+                report_assertion(world, *current_frame, &resource, result);
+            }
+            CiTestingEvent::AssertComponent {
+                component,
+                name,
+                path,
+                value,
+            } => {
+                let result = check_component(world, &component, name.as_deref(), &path, &value);
+                report_assertion(world, *current_frame, &component, result);
+            }
         }
     }
 
     *current_frame += 1;
 }
+
+/// Returns the primary window, or [`Entity::PLACEHOLDER`] if there is none.
+fn primary_window(world: &mut World) -> Entity {
+    world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(world)
+        .unwrap_or(Entity::PLACEHOLDER)
+}
+
+fn send_keyboard_input(world: &mut World, key_code: KeyCode, state: ButtonState) {
+    let window = primary_window(world);
+    world.send_event(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state,
+        text: None,
+        repeat: false,
+        window,
+    });
+}
+
+fn send_mouse_button_input(world: &mut World, button: MouseButton, state: ButtonState) {
+    let window = primary_window(world);
+    world.send_event(MouseButtonInput {
+        button,
+        state,
+        window,
+    });
+}
+
+/// Returns the virtual gamepad, connecting it first if needed.
+fn virtual_gamepad(world: &mut World, input: &mut VirtualInput) -> Entity {
+    *input.gamepad.get_or_insert_with(|| {
+        let gamepad = world.spawn_empty().id();
+        let event = GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected {
+                name: "CI Testing Gamepad".to_string(),
+                vendor_id: None,
+                product_id: None,
+            },
+        );
+        world.send_event(RawGamepadEvent::from(event.clone()));
+        world.send_event(event);
+        gamepad
+    })
+}
+
+/// Stops the program with an error if an assertion failed.
+fn report_assertion(world: &mut World, frame: u32, type_path: &str, result: Result<(), String>) {
+    match result {
+        Ok(()) => info!("Assertion on `{}` passed at frame {}.", type_path, frame),
+        Err(message) => {
+            error!(
+                "Assertion on `{}` failed at frame {}: {}",
+                type_path, frame, message
+            );
+            world.send_event(AppExit::error());
+        }
+    }
+}
+
+fn check_resource(world: &World, resource: &str, path: &str, value: &str) -> Result<(), String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let reflect_resource = registry
+        .get_with_type_path(resource)
+        .and_then(|registration| registration.data::<ReflectResource>())
+        .ok_or_else(|| "resource is not registered with `#[reflect(Resource)]`".to_string())?;
+    let reflected = reflect_resource
+        .reflect(world)
+        .map_err(|_| "resource does not exist".to_string())?;
+    check_value(&registry, reflected, path, value)
+}
+
+fn check_component(
+    world: &mut World,
+    component: &str,
+    name: Option<&str>,
+    path: &str,
+    value: &str,
+) -> Result<(), String> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let reflect_component = registry
+        .get_with_type_path(component)
+        .and_then(|registration| registration.data::<ReflectComponent>())
+        .ok_or_else(|| "component is not registered with `#[reflect(Component)]`".to_string())?;
+
+    let mut checked = 0;
+    for (entity, entity_name) in world.query::<(EntityRef, Option<&Name>)>().iter(world) {
+        if name.is_some() && entity_name.map(Name::as_str) != name {
+            continue;
+        }
+        let Some(reflected) = reflect_component.reflect(entity) else {
+            continue;
+        };
+        check_value(&registry, reflected, path, value)
+            .map_err(|message| format!("{message} on entity {}", entity.id()))?;
+        checked += 1;
+    }
+
+    if checked == 0 {
+        return Err("no matching entity has the component".to_string());
+    }
+    Ok(())
+}
+
+/// Checks that the field at `path` of `reflected` is equal to `expected`, given in RON.
+fn check_value(
+    registry: &TypeRegistry,
+    reflected: &dyn Reflect,
+    path: &str,
+    expected: &str,
+) -> Result<(), String> {
+    let field = reflected
+        .reflect_path(path)
+        .map_err(|err| err.to_string())?;
+    let registration = field
+        .get_represented_type_info()
+        .and_then(|type_info| registry.get(type_info.type_id()))
+        .ok_or_else(|| format!("the type of `{path}` is not registered"))?;
+
+    let mut deserializer = ron::Deserializer::from_str(expected).map_err(|err| err.to_string())?;
+    let expected_field = TypedReflectDeserializer::new(registration, registry)
+        .deserialize(&mut deserializer)
+        .map_err(|err| format!("`{expected}` is invalid: {err}"))?;
+
+    match field.reflect_partial_eq(&*expected_field) {
+        Some(true) => Ok(()),
+        _ => Err(format!("expected {expected}, found {field:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_reflect::TypePath;
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score {
+        points: u32,
+        lives: u8,
+    }
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Health(f32);
+
+    fn world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Score>();
+            registry.register::<Health>();
+        }
+        world.insert_resource(registry);
+        world.insert_resource(Score {
+            points: 10,
+            lives: 3,
+        });
+        world.spawn((Name::new("Player"), Health(100.0)));
+        world.spawn((Name::new("Enemy"), Health(25.0)));
+        world
+    }
+
+    #[test]
+    fn assert_resource() {
+        let world = world();
+        let score = Score::type_path();
+        assert!(check_resource(&world, score, ".points", "10").is_ok());
+        assert!(check_resource(&world, score, "", "(points: 10, lives: 3)").is_ok());
+        assert!(check_resource(&world, score, ".lives", "2").is_err());
+        assert!(check_resource(&world, score, ".missing", "2").is_err());
+        assert!(check_resource(&world, "my_game::Missing", "", "()").is_err());
+    }
+
+    #[test]
+    fn assert_component() {
+        let mut world = world();
+        let health = Health::type_path();
+        assert!(check_component(&mut world, health, Some("Player"), ".0", "100.0").is_ok());
+        assert!(check_component(&mut world, health, Some("Enemy"), ".0", "100.0").is_err());
+        // Without a name, every entity with the component is checked.
+        assert!(check_component(&mut world, health, None, ".0", "25.0").is_err());
+        assert!(check_component(&mut world, health, Some("Nobody"), ".0", "25.0").is_err());
+    }
+}
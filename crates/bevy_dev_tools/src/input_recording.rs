@@ -0,0 +1,443 @@
+//! Recording and replaying of user input, to reproduce bugs deterministically.
+//!
+//! Add the [`InputRecordingPlugin`] in [recording mode](InputRecordingPlugin::record) while
+//! playing to save every input event to a file, along with the duration of each frame. Adding it
+//! in [replay mode](InputRecordingPlugin::replay) instead feeds the recorded events back into the
+//! app on the same frames, while pinning [`TimeUpdateStrategy::ManualDuration`] to the recorded
+//! frame durations, so that the session plays out the same way again.
+//!
+//! The recording is a text file containing one [`RecordedFrame`] per line, in [`ron`] format.
+//! Frames are written as they end, so a recording survives the app crashing.
+
+use alloc::collections::VecDeque;
+use bevy_app::prelude::*;
+use bevy_ecs::{entity::EntityHashMap, event::Events, prelude::*};
+use bevy_input::{
+    gamepad::{
+        GamepadConnectionEvent, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent,
+        RawGamepadEvent,
+    },
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    InputSystems,
+};
+use bevy_time::{Real, Time, TimeUpdateStrategy, Virtual};
+use bevy_window::{CursorMoved, PrimaryWindow, Window};
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+use tracing::{error, info, warn};
+
+/// A plugin that records user input to a file, or replays a previous recording.
+///
+/// The recorded events are [`KeyboardInput`], [`MouseButtonInput`], [`MouseMotion`],
+/// [`MouseWheel`], [`CursorMoved`], [`TouchInput`] and [`RawGamepadEvent`]. Gamepad events are
+/// recorded before `bevy_input` processes them, so that replaying them updates the [`Gamepad`]
+/// components and sends [`GamepadEvent`]s just like the original session did.
+///
+/// While replaying, input coming from the platform is discarded. Live gamepad input may still
+/// slip through, so gamepads should be left untouched.
+///
+/// [`Gamepad`]: bevy_input::gamepad::Gamepad
+/// [`GamepadEvent`]: bevy_input::gamepad::GamepadEvent
+pub struct InputRecordingPlugin {
+    mode: InputRecordingMode,
+}
+
+/// Whether the [`InputRecordingPlugin`] records or replays input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputRecordingMode {
+    /// Records input to the file at the given path, replacing it if it exists.
+    Record(PathBuf),
+    /// Replays the input recorded in the file at the given path.
+    Replay(PathBuf),
+}
+
+impl InputRecordingPlugin {
+    /// Creates a plugin recording input to the file at `path`.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: InputRecordingMode::Record(path.into()),
+        }
+    }
+
+    /// Creates a plugin replaying the input recorded in the file at `path`.
+    pub fn replay(path: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: InputRecordingMode::Replay(path.into()),
+        }
+    }
+
+    /// Returns whether this plugin records or replays input.
+    pub fn mode(&self) -> &InputRecordingMode {
+        &self.mode
+    }
+}
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<KeyboardInput>()
+            .add_event::<MouseButtonInput>()
+            .add_event::<MouseMotion>()
+            .add_event::<MouseWheel>()
+            .add_event::<CursorMoved>()
+            .add_event::<TouchInput>()
+            .add_event::<RawGamepadEvent>()
+            .add_event::<GamepadConnectionEvent>()
+            .add_event::<RawGamepadButtonChangedEvent>()
+            .add_event::<RawGamepadAxisChangedEvent>();
+
+        match &self.mode {
+            InputRecordingMode::Record(path) => {
+                let file = match File::create(path) {
+                    Ok(file) => file,
+                    Err(err) => {
+                        error!(
+                            "Failed to create input recording {}: {}",
+                            path.display(),
+                            err
+                        );
+                        return;
+                    }
+                };
+                app.insert_resource(InputRecorder {
+                    writer: BufWriter::new(file),
+                    frame: 0,
+                })
+                // Record after the input systems, to catch any events sent just before them.
+                .add_systems(PreUpdate, record_input.after(InputSystems));
+            }
+            InputRecordingMode::Replay(path) => {
+                let recording =
+                    std::fs::read_to_string(path).expect("error reading input recording file");
+                let frames = recording
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| {
+                        ron::from_str::<RecordedFrame>(line)
+                            .expect("error deserializing input recording file")
+                    })
+                    .collect::<VecDeque<_>>();
+
+                if let Some(first) = frames.front() {
+                    app.insert_resource(TimeUpdateStrategy::ManualDuration(first.real_delta));
+                }
+                app.insert_resource(InputReplay {
+                    frames,
+                    ..Default::default()
+                })
+                .add_systems(PreUpdate, replay_input.before(InputSystems));
+            }
+        }
+    }
+}
+
+/// The input captured during a single frame by the [`InputRecordingPlugin`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedFrame {
+    /// The number of frames since recording started.
+    pub frame: u64,
+    /// The delta of [`Time<Real>`] during this frame, which is pinned while replaying.
+    pub real_delta: Duration,
+    /// The delta of [`Time<Virtual>`] during this frame, which is checked while replaying.
+    pub virtual_delta: Duration,
+    /// The input events received during this frame, in order for each type of event.
+    pub events: Vec<RecordedInput>,
+}
+
+/// An input event captured by the [`InputRecordingPlugin`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RecordedInput {
+    /// A [`KeyboardInput`] event.
+    Keyboard(KeyboardInput),
+    /// A [`MouseButtonInput`] event.
+    MouseButton(MouseButtonInput),
+    /// A [`MouseMotion`] event.
+    MouseMotion(MouseMotion),
+    /// A [`MouseWheel`] event.
+    MouseWheel(MouseWheel),
+    /// A [`CursorMoved`] event.
+    CursorMoved(CursorMoved),
+    /// A [`TouchInput`] event.
+    Touch(TouchInput),
+    /// A [`RawGamepadEvent`].
+    Gamepad(RawGamepadEvent),
+}
+
+/// The file input is recorded to.
+#[derive(Resource)]
+struct InputRecorder {
+    writer: BufWriter<File>,
+    frame: u64,
+}
+
+/// The recording being replayed.
+#[derive(Resource, Default)]
+struct InputReplay {
+    /// The frames that have yet to be replayed.
+    frames: VecDeque<RecordedFrame>,
+    /// The number of frames since the replay started.
+    frame: u64,
+    /// Maps the gamepads of the recording to the gamepads spawned for the replay.
+    gamepads: EntityHashMap<Entity>,
+    /// Whether a divergence from the recorded frame durations has been reported.
+    diverged: bool,
+}
+
+fn record_input(
+    mut recorder: ResMut<InputRecorder>,
+    real_time: Res<Time<Real>>,
+    virtual_time: Res<Time<Virtual>>,
+    mut keyboard: EventReader<KeyboardInput>,
+    mut mouse_buttons: EventReader<MouseButtonInput>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut touches: EventReader<TouchInput>,
+    mut gamepads: EventReader<RawGamepadEvent>,
+) {
+    let mut events = Vec::new();
+    events.extend(keyboard.read().cloned().map(RecordedInput::Keyboard));
+    events.extend(
+        mouse_buttons
+            .read()
+            .cloned()
+            .map(RecordedInput::MouseButton),
+    );
+    events.extend(mouse_motion.read().cloned().map(RecordedInput::MouseMotion));
+    events.extend(mouse_wheel.read().cloned().map(RecordedInput::MouseWheel));
+    events.extend(cursor_moved.read().cloned().map(RecordedInput::CursorMoved));
+    events.extend(touches.read().cloned().map(RecordedInput::Touch));
+    events.extend(gamepads.read().cloned().map(RecordedInput::Gamepad));
+
+    let frame = RecordedFrame {
+        frame: recorder.frame,
+        real_delta: real_time.delta(),
+        virtual_delta: virtual_time.delta(),
+        events,
+    };
+    recorder.frame += 1;
+
+    let result = ron::to_string(&frame)
+        .map_err(|err| err.to_string())
+        .and_then(|line| {
+            // Flush every frame, so that the recording is complete if the app crashes.
+            writeln!(recorder.writer, "{line}")
+                .and_then(|()| recorder.writer.flush())
+                .map_err(|err| err.to_string())
+        });
+    if let Err(err) = result {
+        error!("Failed to write input recording: {}", err);
+    }
+}
+
+fn replay_input(world: &mut World) {
+    world.resource_scope(|world, mut replay: Mut<InputReplay>| {
+        let Some(frame) = replay.frames.front() else {
+            return;
+        };
+        if frame.frame != replay.frame {
+            replay.frame += 1;
+            return;
+        }
+        let frame = replay.frames.pop_front().unwrap();
+
+        let virtual_delta = world.resource::<Time<Virtual>>().delta();
+        if virtual_delta != frame.virtual_delta && !replay.diverged {
+            warn!(
+                "Input replay diverged at frame {}: the virtual time delta is {:?} instead of {:?}. \
+                The replay may not reproduce the recording.",
+                frame.frame, virtual_delta, frame.virtual_delta
+            );
+            replay.diverged = true;
+        }
+
+        // Discard live input, so that only the recording drives the app.
+        clear_events::<KeyboardInput>(world);
+        clear_events::<MouseButtonInput>(world);
+        clear_events::<MouseMotion>(world);
+        clear_events::<MouseWheel>(world);
+        clear_events::<CursorMoved>(world);
+        clear_events::<TouchInput>(world);
+        clear_events::<RawGamepadEvent>(world);
+        clear_events::<GamepadConnectionEvent>(world);
+        clear_events::<RawGamepadButtonChangedEvent>(world);
+        clear_events::<RawGamepadAxisChangedEvent>(world);
+
+        let window = world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(world)
+            .ok();
+        for event in frame.events {
+            replay_event(world, &mut replay, window, event);
+        }
+
+        // Pin the duration of the next frame to the recorded one.
+        replay.frame += 1;
+        match replay.frames.front() {
+            Some(next) => {
+                world.insert_resource(TimeUpdateStrategy::ManualDuration(next.real_delta));
+            }
+            None => {
+                world.insert_resource(TimeUpdateStrategy::Automatic);
+                info!("Input replay finished after {} frames.", replay.frame);
+            }
+        }
+    });
+}
+
+fn replay_event(
+    world: &mut World,
+    replay: &mut InputReplay,
+    window: Option<Entity>,
+    event: RecordedInput,
+) {
+    // Events are sent to the primary window, as the recorded windows no longer exist.
+    let window = |recorded: Entity| window.unwrap_or(recorded);
+    match event {
+        RecordedInput::Keyboard(mut event) => {
+            event.window = window(event.window);
+            world.send_event(event);
+        }
+        RecordedInput::MouseButton(mut event) => {
+            event.window = window(event.window);
+            world.send_event(event);
+        }
+        RecordedInput::MouseMotion(event) => {
+            world.send_event(event);
+        }
+        RecordedInput::MouseWheel(mut event) => {
+            event.window = window(event.window);
+            world.send_event(event);
+        }
+        RecordedInput::CursorMoved(mut event) => {
+            event.window = window(event.window);
+            if let Some(mut window) = world.get_mut::<Window>(event.window) {
+                window.set_cursor_position(Some(event.position));
+            }
+            world.send_event(event);
+        }
+        RecordedInput::Touch(mut event) => {
+            event.window = window(event.window);
+            world.send_event(event);
+        }
+        RecordedInput::Gamepad(RawGamepadEvent::Connection(mut event)) => {
+            event.gamepad = *replay
+                .gamepads
+                .entry(event.gamepad)
+                .or_insert_with(|| world.spawn_empty().id());
+            world.send_event(RawGamepadEvent::from(event.clone()));
+            world.send_event(event);
+        }
+        RecordedInput::Gamepad(RawGamepadEvent::Button(mut event)) => {
+            let Some(&gamepad) = replay.gamepads.get(&event.gamepad) else {
+                warn!(
+                    "Skipping replayed input of unknown gamepad {}.",
+                    event.gamepad
+                );
+                return;
+            };
+            event.gamepad = gamepad;
+            world.send_event(RawGamepadEvent::from(event));
+            world.send_event(event);
+        }
+        RecordedInput::Gamepad(RawGamepadEvent::Axis(mut event)) => {
+            let Some(&gamepad) = replay.gamepads.get(&event.gamepad) else {
+                warn!(
+                    "Skipping replayed input of unknown gamepad {}.",
+                    event.gamepad
+                );
+                return;
+            };
+            event.gamepad = gamepad;
+            world.send_event(RawGamepadEvent::from(event));
+            world.send_event(event);
+        }
+    }
+}
+
+fn clear_events<E: Event>(world: &mut World) {
+    if let Some(mut events) = world.get_resource_mut::<Events<E>>() {
+        events.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_input::{
+        keyboard::{Key, KeyCode},
+        ButtonInput, ButtonState, InputPlugin,
+    };
+    use bevy_time::TimePlugin;
+
+    fn key_event(state: ButtonState) -> KeyboardInput {
+        KeyboardInput {
+            key_code: KeyCode::Space,
+            logical_key: Key::Space,
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        }
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let path =
+            std::env::temp_dir().join(format!("bevy_input_recording_{}.ron", std::process::id()));
+
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, InputRecordingPlugin::record(&path)));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )));
+        app.update();
+        app.world_mut().send_event(key_event(ButtonState::Pressed));
+        app.update();
+        app.update();
+        app.world_mut().send_event(key_event(ButtonState::Released));
+        app.update();
+        drop(app);
+
+        let recording = std::fs::read_to_string(&path).unwrap();
+        let frames = recording
+            .lines()
+            .map(|line| ron::from_str::<RecordedFrame>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[1].real_delta, Duration::from_millis(10));
+        assert_eq!(
+            frames[1].events,
+            [RecordedInput::Keyboard(key_event(ButtonState::Pressed))]
+        );
+        assert!(frames[2].events.is_empty());
+
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, InputRecordingPlugin::replay(&path)));
+        let mut pressed = Vec::new();
+        for _ in 0..4 {
+            // Live input is ignored while replaying.
+            app.world_mut().send_event(key_event(ButtonState::Pressed));
+            app.update();
+            pressed.push(
+                app.world()
+                    .resource::<ButtonInput<KeyCode>>()
+                    .pressed(KeyCode::Space),
+            );
+        }
+        assert_eq!(pressed, [false, true, true, false]);
+        assert_eq!(
+            app.world().resource::<Time<Virtual>>().delta(),
+            Duration::from_millis(10)
+        );
+        assert!(!app.world().resource::<InputReplay>().diverged);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
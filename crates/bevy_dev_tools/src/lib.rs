@@ -8,6 +8,8 @@
 //! This crate provides additional utilities for the [Bevy game engine](https://bevyengine.org),
 //! focused on improving developer experience.
 
+extern crate alloc;
+
 use bevy_app::prelude::*;
 
 #[cfg(feature = "bevy_ci_testing")]
@@ -15,6 +17,9 @@ pub mod ci_testing;
 
 pub mod fps_overlay;
 
+#[cfg(feature = "input_recording")]
+pub mod input_recording;
+
 pub mod picking_debug;
 
 pub mod states;
@@ -21,8 +21,11 @@ mod entity_count_diagnostics_plugin;
 mod frame_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+#[cfg(feature = "std")]
+mod system_execution_diagnostics_plugin;
 #[cfg(feature = "sysinfo_plugin")]
 mod system_information_diagnostics_plugin;
+mod world_memory_diagnostics_plugin;
 
 pub use diagnostic::*;
 
@@ -30,8 +33,11 @@ pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_count_diagnostics_plugin::{update_frame_count, FrameCount, FrameCountPlugin};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::{LogDiagnosticsPlugin, LogDiagnosticsState};
+#[cfg(feature = "std")]
+pub use system_execution_diagnostics_plugin::SystemExecutionDiagnosticsPlugin;
 #[cfg(feature = "sysinfo_plugin")]
 pub use system_information_diagnostics_plugin::{SystemInfo, SystemInformationDiagnosticsPlugin};
+pub use world_memory_diagnostics_plugin::WorldMemoryDiagnosticsPlugin;
 
 use bevy_app::prelude::*;
 
@@ -0,0 +1,181 @@
+use alloc::format;
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{ExecutionTraces, ScheduleLabel},
+};
+
+use crate::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+
+/// Adds per-system execution time diagnostics to an App, using the [`ExecutionTraces`] recorded
+/// by the multi-threaded executor.
+///
+/// For every schedule run with the multi-threaded executor this records:
+/// - `system_execution/<schedule>/<system>`: how long each system took, in milliseconds.
+/// - `system_execution/<schedule>`: how long the whole schedule took, in milliseconds.
+/// - `system_execution/<schedule>/blocked_systems`: how many systems had to wait for a running
+///   system with conflicting data access.
+///
+/// Diagnostics are created the first time a system or schedule is traced. The traces themselves
+/// are drained each frame; to inspect which systems blocked each other, read [`ExecutionTraces`]
+/// before this plugin's system runs in [`First`].
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct SystemExecutionDiagnosticsPlugin {
+    /// The total number of values to keep for averaging.
+    pub max_history_length: usize,
+}
+
+impl Default for SystemExecutionDiagnosticsPlugin {
+    fn default() -> Self {
+        Self {
+            max_history_length: crate::DEFAULT_MAX_HISTORY_LENGTH,
+        }
+    }
+}
+
+impl Plugin for SystemExecutionDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let max_history_length = self.max_history_length;
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<ExecutionTraces>()
+            .add_systems(
+                First,
+                move |mut store: ResMut<DiagnosticsStore>, mut traces: ResMut<ExecutionTraces>| {
+                    Self::record_traces(&mut store, &mut traces, max_history_length);
+                },
+            );
+    }
+}
+
+impl SystemExecutionDiagnosticsPlugin {
+    /// The root of all diagnostic paths added by this plugin.
+    pub const ROOT: &'static str = "system_execution";
+
+    /// Returns the path of the diagnostic measuring the duration of the given system.
+    pub fn system_path(schedule: &dyn ScheduleLabel, system: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!("{}/{schedule:?}/{system}", Self::ROOT))
+    }
+
+    /// Returns the path of the diagnostic measuring the duration of the given schedule.
+    pub fn schedule_path(schedule: &dyn ScheduleLabel) -> DiagnosticPath {
+        DiagnosticPath::new(format!("{}/{schedule:?}", Self::ROOT))
+    }
+
+    /// Returns the path of the diagnostic counting blocked systems in the given schedule.
+    pub fn blocked_systems_path(schedule: &dyn ScheduleLabel) -> DiagnosticPath {
+        DiagnosticPath::new(format!("{}/{schedule:?}/blocked_systems", Self::ROOT))
+    }
+
+    /// Drains `traces` into `store`, creating diagnostics that keep `max_history_length` values
+    /// for systems and schedules that don't have one yet.
+    pub fn record_traces(
+        store: &mut DiagnosticsStore,
+        traces: &mut ExecutionTraces,
+        max_history_length: usize,
+    ) {
+        let mut add = |path: DiagnosticPath, suffix: &'static str, measurement| {
+            if store.get(&path).is_none() {
+                store.add(
+                    Diagnostic::new(path.clone())
+                        .with_suffix(suffix)
+                        .with_max_history_length(max_history_length),
+                );
+            }
+            let diagnostic = store.get_mut(&path).unwrap();
+            if diagnostic.is_enabled {
+                diagnostic.add_measurement(measurement);
+            }
+        };
+
+        for trace in traces.drain() {
+            let schedule = &*trace.schedule;
+            for system in &trace.systems {
+                add(
+                    Self::system_path(schedule, &system.name),
+                    "ms",
+                    DiagnosticMeasurement {
+                        time: system.end,
+                        value: system.duration().as_secs_f64() * 1000.0,
+                    },
+                );
+            }
+            add(
+                Self::schedule_path(schedule),
+                "ms",
+                DiagnosticMeasurement {
+                    time: trace.end,
+                    value: trace.duration().as_secs_f64() * 1000.0,
+                },
+            );
+            add(
+                Self::blocked_systems_path(schedule),
+                "",
+                DiagnosticMeasurement {
+                    time: trace.end,
+                    value: trace.blocked_systems().count() as f64,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiagnosticsPlugin;
+    use bevy_ecs::schedule::ExecutorKind;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn first(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    fn second(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    #[test]
+    fn records_system_and_schedule_durations() {
+        let mut app = App::new();
+        app.add_plugins((
+            DiagnosticsPlugin,
+            SystemExecutionDiagnosticsPlugin::default(),
+        ))
+        .init_resource::<Counter>()
+        .add_systems(Update, (first, second));
+        app.edit_schedule(Update, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+        });
+
+        // The traces of one frame are turned into diagnostics at the start of the next.
+        app.update();
+        app.update();
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        for system in ["first", "second"] {
+            let path = SystemExecutionDiagnosticsPlugin::system_path(
+                &Update,
+                &format!("bevy_diagnostic::system_execution_diagnostics_plugin::tests::{system}"),
+            );
+            let diagnostic = store.get(&path).unwrap();
+            assert_eq!(diagnostic.history_len(), 1);
+            assert!(diagnostic.value().unwrap() >= 0.0);
+        }
+        assert!(store
+            .get(&SystemExecutionDiagnosticsPlugin::schedule_path(&Update))
+            .is_some());
+        // Both systems write `Counter`, so one of them waits for the other.
+        let blocked = store
+            .get(&SystemExecutionDiagnosticsPlugin::blocked_systems_path(
+                &Update,
+            ))
+            .unwrap();
+        assert_eq!(blocked.value(), Some(1.0));
+        assert!(!app.world().resource::<ExecutionTraces>().is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{storage::MemoryUsage, world::World};
+
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+
+/// Adds diagnostics of the memory used by the [`World`]'s storages to an App, in KiB.
+///
+/// The totals come from [`World::memory_stats`] and are measured in [`Last`], after the
+/// frame's changes have been applied. Allocated memory that keeps growing over a long session
+/// usually points to a leak, while a large amount of wasted memory points to storages that grew
+/// for a spike of entities and kept their capacity.
+///
+/// Collecting the statistics walks every archetype, table, sparse set and resource, which is
+/// cheap compared to iterating the entities but not free for worlds with many archetypes.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct WorldMemoryDiagnosticsPlugin;
+
+impl Plugin for WorldMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        for path in [
+            Self::ALLOCATED,
+            Self::USED,
+            Self::WASTED,
+            Self::TABLES,
+            Self::SPARSE_SETS,
+            Self::RESOURCES,
+        ] {
+            app.register_diagnostic(Diagnostic::new(path).with_suffix("KiB"));
+        }
+        app.register_diagnostic(Diagnostic::new(Self::ARCHETYPE_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::TABLE_COUNT))
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl WorldMemoryDiagnosticsPlugin {
+    /// The memory allocated by all storages.
+    pub const ALLOCATED: DiagnosticPath = DiagnosticPath::const_new("world_memory/allocated");
+    /// The memory used by stored values in all storages.
+    pub const USED: DiagnosticPath = DiagnosticPath::const_new("world_memory/used");
+    /// The memory allocated but not used by all storages.
+    pub const WASTED: DiagnosticPath = DiagnosticPath::const_new("world_memory/wasted");
+    /// The memory allocated by tables.
+    pub const TABLES: DiagnosticPath = DiagnosticPath::const_new("world_memory/tables");
+    /// The memory allocated by sparse sets.
+    pub const SPARSE_SETS: DiagnosticPath = DiagnosticPath::const_new("world_memory/sparse_sets");
+    /// The memory allocated by resources.
+    pub const RESOURCES: DiagnosticPath = DiagnosticPath::const_new("world_memory/resources");
+    /// The number of archetypes.
+    pub const ARCHETYPE_COUNT: DiagnosticPath =
+        DiagnosticPath::const_new("world_memory/archetype_count");
+    /// The number of tables.
+    pub const TABLE_COUNT: DiagnosticPath = DiagnosticPath::const_new("world_memory/table_count");
+
+    /// Measures the memory used by the world's storages.
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, world: &World) {
+        let stats = world.memory_stats();
+        let total = stats.total();
+        let kib = |bytes: usize| bytes as f64 / 1024.0;
+        let allocated = |usage: MemoryUsage| kib(usage.allocated_bytes);
+
+        diagnostics.add_measurement(&Self::ALLOCATED, || allocated(total));
+        diagnostics.add_measurement(&Self::USED, || kib(total.used_bytes));
+        diagnostics.add_measurement(&Self::WASTED, || kib(total.wasted_bytes()));
+        diagnostics.add_measurement(&Self::TABLES, || allocated(stats.tables_total()));
+        diagnostics.add_measurement(&Self::SPARSE_SETS, || allocated(stats.sparse_sets_total()));
+        diagnostics.add_measurement(&Self::RESOURCES, || allocated(stats.resources_total()));
+        diagnostics.add_measurement(&Self::ARCHETYPE_COUNT, || stats.archetypes.len() as f64);
+        diagnostics.add_measurement(&Self::TABLE_COUNT, || stats.tables.len() as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticsPlugin, DiagnosticsStore};
+    use bevy_ecs::component::Component;
+
+    #[derive(Component)]
+    struct Payload(#[expect(dead_code, reason = "only the size matters")] [u8; 1024]);
+
+    #[test]
+    fn measures_world_memory() {
+        let mut app = App::new();
+        app.add_plugins((DiagnosticsPlugin, WorldMemoryDiagnosticsPlugin));
+        app.update();
+        let allocated = |app: &App| {
+            app.world()
+                .resource::<DiagnosticsStore>()
+                .get(&WorldMemoryDiagnosticsPlugin::ALLOCATED)
+                .unwrap()
+                .value()
+                .unwrap()
+        };
+        let before = allocated(&app);
+
+        app.world_mut()
+            .spawn_batch((0..64).map(|_| Payload([0; 1024])));
+        app.update();
+        assert!(allocated(&app) >= before + 64.0);
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let tables = store
+            .get(&WorldMemoryDiagnosticsPlugin::TABLES)
+            .unwrap()
+            .value()
+            .unwrap();
+        assert!(tables >= 64.0);
+        assert!(
+            store
+                .get(&WorldMemoryDiagnosticsPlugin::ARCHETYPE_COUNT)
+                .unwrap()
+                .value()
+                .unwrap()
+                >= 1.0
+        );
+    }
+}
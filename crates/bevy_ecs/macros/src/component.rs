@@ -113,6 +113,28 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         .on_remove
         .map(|path| path.to_token_stream(&bevy_ecs_path));
 
+    // Many-to-many relationships have their own traits, with the same hooks.
+    let many_relationship_target = attrs
+        .relationship_target
+        .as_ref()
+        .is_some_and(|relationship_target| relationship_target.many);
+    let (relationship_trait, relationship_target_trait) = if many_relationship_target
+        || attrs
+            .relationship
+            .as_ref()
+            .is_some_and(|relationship| relationship.many)
+    {
+        (
+            quote!(#bevy_ecs_path::relationship::MultiRelationship),
+            quote!(#bevy_ecs_path::relationship::MultiRelationshipTarget),
+        )
+    } else {
+        (
+            quote!(#bevy_ecs_path::relationship::Relationship),
+            quote!(#bevy_ecs_path::relationship::RelationshipTarget),
+        )
+    };
+
     let on_insert_path = if relationship.is_some() {
         if attrs.on_insert.is_some() {
             return syn::Error::new(
@@ -123,7 +145,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_insert))
+        Some(quote!(<Self as #relationship_trait>::on_insert))
     } else {
         attrs
             .on_insert
@@ -140,7 +162,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_replace))
+        Some(quote!(<Self as #relationship_trait>::on_replace))
     } else if attrs.relationship_target.is_some() {
         if attrs.on_replace.is_some() {
             return syn::Error::new(
@@ -151,7 +173,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_replace))
+        Some(quote!(<Self as #relationship_target_trait>::on_replace))
     } else {
         attrs
             .on_replace
@@ -171,7 +193,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_despawn))
+        Some(quote!(<Self as #relationship_target_trait>::on_despawn))
     } else {
         attrs
             .on_despawn
@@ -249,7 +271,9 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         .then_some(quote! { #bevy_ecs_path::component::Immutable })
         .unwrap_or(quote! { #bevy_ecs_path::component::Mutable });
 
-    let clone_behavior = if relationship_target.is_some() {
+    let clone_behavior = if many_relationship_target {
+        quote!(#bevy_ecs_path::component::ComponentCloneBehavior::Custom(#bevy_ecs_path::relationship::clone_multi_relationship_target::<Self>))
+    } else if relationship_target.is_some() {
         quote!(#bevy_ecs_path::component::ComponentCloneBehavior::Custom(#bevy_ecs_path::relationship::clone_relationship_target::<Self>))
     } else if let Some(behavior) = attrs.clone_behavior {
         quote!(#bevy_ecs_path::component::ComponentCloneBehavior::#behavior)
@@ -477,11 +501,13 @@ struct Require {
 
 struct Relationship {
     relationship_target: Type,
+    many: bool,
 }
 
 struct RelationshipTarget {
     relationship: Type,
     linked_spawn: bool,
+    many: bool,
 }
 
 // values for `storage` attribute
@@ -669,14 +695,23 @@ mod kw {
     syn::custom_keyword!(relationship_target);
     syn::custom_keyword!(relationship);
     syn::custom_keyword!(linked_spawn);
+    syn::custom_keyword!(many);
 }
 
 impl Parse for Relationship {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         input.parse::<kw::relationship_target>()?;
         input.parse::<Token![=]>()?;
+        let relationship_target = input.parse::<Type>()?;
+        let mut many = false;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            input.parse::<kw::many>()?;
+            many = true;
+        }
         Ok(Relationship {
-            relationship_target: input.parse::<Type>()?,
+            relationship_target,
+            many,
         })
     }
 }
@@ -685,12 +720,16 @@ impl Parse for RelationshipTarget {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         let mut relationship: Option<Type> = None;
         let mut linked_spawn: bool = false;
+        let mut many: bool = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(kw::linked_spawn) {
                 input.parse::<kw::linked_spawn>()?;
                 linked_spawn = true;
+            } else if lookahead.peek(kw::many) {
+                input.parse::<kw::many>()?;
+                many = true;
             } else if lookahead.peek(kw::relationship) {
                 input.parse::<kw::relationship>()?;
                 input.parse::<Token![=]>()?;
@@ -707,6 +746,7 @@ impl Parse for RelationshipTarget {
                 syn::Error::new(input.span(), "Missing `relationship = X` attribute")
             })?,
             linked_spawn,
+            many,
         })
     }
 }
@@ -742,6 +782,40 @@ fn derive_relationship(
 
     let relationship_target = &relationship.relationship_target;
 
+    if relationship.many {
+        let collection = &field.ty;
+        return Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs_path::relationship::MultiRelationship for #struct_name #type_generics #where_clause {
+                type RelationshipTarget = #relationship_target;
+                type Collection = #collection;
+
+                #[inline]
+                fn collection(&self) -> &Self::Collection {
+                    &self.#relationship_member
+                }
+
+                #[inline]
+                fn from_collection_risky(collection: Self::Collection) -> Self {
+                    Self {
+                        #(#members: core::default::Default::default(),)*
+                        #relationship_member: collection
+                    }
+                }
+            }
+
+            impl #impl_generics #bevy_ecs_path::relationship::TargetEntities for #struct_name #type_generics #where_clause {
+                type Targets<'a> = <#collection as #bevy_ecs_path::relationship::RelationshipSourceCollection>::SourceIter<'a>
+                where
+                    Self: 'a;
+
+                #[inline]
+                fn targets(&self) -> Self::Targets<'_> {
+                    #bevy_ecs_path::relationship::RelationshipSourceCollection::iter(&self.#relationship_member)
+                }
+            }
+        }));
+    }
+
     Ok(Some(quote! {
         impl #impl_generics #bevy_ecs_path::relationship::Relationship for #struct_name #type_generics #where_clause {
             type RelationshipTarget = #relationship_target;
@@ -798,6 +872,44 @@ fn derive_relationship_target(
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
     let linked_spawn = relationship_target.linked_spawn;
+    if relationship_target.many {
+        return Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs_path::relationship::MultiRelationshipTarget for #struct_name #type_generics #where_clause {
+                const LINKED_SPAWN: bool = #linked_spawn;
+                type Relationship = #relationship;
+                type Collection = #collection;
+
+                #[inline]
+                fn collection(&self) -> &Self::Collection {
+                    &self.#relationship_member
+                }
+
+                #[inline]
+                fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+                    &mut self.#relationship_member
+                }
+
+                #[inline]
+                fn from_collection_risky(collection: Self::Collection) -> Self {
+                    Self {
+                        #(#members: core::default::Default::default(),)*
+                        #relationship_member: collection
+                    }
+                }
+            }
+
+            impl #impl_generics #bevy_ecs_path::relationship::SourceEntities for #struct_name #type_generics #where_clause {
+                type Sources<'a> = <#collection as #bevy_ecs_path::relationship::RelationshipSourceCollection>::SourceIter<'a>
+                where
+                    Self: 'a;
+
+                #[inline]
+                fn sources(&self) -> Self::Sources<'_> {
+                    #bevy_ecs_path::relationship::RelationshipSourceCollection::iter(&self.#relationship_member)
+                }
+            }
+        }));
+    }
     Ok(Some(quote! {
         impl #impl_generics #bevy_ecs_path::relationship::RelationshipTarget for #struct_name #type_generics #where_clause {
             const LINKED_SPAWN: bool = #linked_spawn;
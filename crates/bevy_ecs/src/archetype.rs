@@ -24,7 +24,10 @@ use crate::{
     component::{ComponentId, Components, RequiredComponentConstructor, StorageType},
     entity::{Entity, EntityLocation},
     observer::Observers,
-    storage::{ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId, TableRow},
+    storage::{
+        ArchetypeMemoryStats, ImmutableSparseSet, MemoryUsage, SparseArray, SparseSet,
+        SparseSetIndex, TableId, TableRow,
+    },
 };
 use alloc::{boxed::Box, vec::Vec};
 use bevy_platform::collections::HashMap;
@@ -372,6 +375,7 @@ bitflags::bitflags! {
 pub struct Archetype {
     id: ArchetypeId,
     table_id: TableId,
+    compactions: u32,
     edges: Edges,
     entities: Vec<ArchetypeEntity>,
     components: ImmutableSparseSet<ComponentId, ArchetypeComponentInfo>,
@@ -386,6 +390,7 @@ impl Archetype {
         observers: &Observers,
         id: ArchetypeId,
         table_id: TableId,
+        compactions: u32,
         table_components: impl Iterator<Item = (ComponentId, ArchetypeComponentId)>,
         sparse_set_components: impl Iterator<Item = (ComponentId, ArchetypeComponentId)>,
     ) -> Self {
@@ -434,6 +439,7 @@ impl Archetype {
         Self {
             id,
             table_id,
+            compactions,
             entities: Vec::new(),
             components: archetype_components.into_immutable(),
             edges: Default::default(),
@@ -447,6 +453,13 @@ impl Archetype {
         self.id
     }
 
+    /// Returns the number of [compactions](crate::world::World::compact) of the world that
+    /// reassigned archetype ids before this archetype got its current id.
+    #[inline]
+    pub(crate) fn compactions(&self) -> u32 {
+        self.compactions
+    }
+
     /// Fetches the flags for the archetype.
     #[inline]
     pub(crate) fn flags(&self) -> ArchetypeFlags {
@@ -507,6 +520,17 @@ impl Archetype {
         self.components.len()
     }
 
+    /// Returns the memory used by the bookkeeping of this archetype.
+    pub(crate) fn memory_stats(&self) -> ArchetypeMemoryStats {
+        ArchetypeMemoryStats {
+            id: self.id,
+            table: self.table_id,
+            entity_count: self.len(),
+            component_count: self.component_count(),
+            entities: MemoryUsage::of_vec(&self.entities),
+        }
+    }
+
     /// Gets an iterator of all of the components in the archetype, along with
     /// their archetype component ID.
     pub(crate) fn components_with_archetype_component_id(
@@ -650,6 +674,11 @@ impl Archetype {
         self.entities.clear();
     }
 
+    /// Shrinks the capacity of the archetype's list of entities to its length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+    }
+
     /// Returns true if any of the components in this archetype have `on_add` hooks
     #[inline]
     pub fn has_add_hook(&self) -> bool {
@@ -725,14 +754,23 @@ impl Archetype {
 ///
 /// This is used in archetype update methods to limit archetype updates to the
 /// ones added since the last time the method ran.
+///
+/// A generation taken before a [compaction](crate::world::World::compact) removed archetypes
+/// is outdated: indexing [`Archetypes`] with it returns every archetype, as their ids changed.
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct ArchetypeGeneration(pub(crate) ArchetypeId);
+pub struct ArchetypeGeneration {
+    pub(crate) id: ArchetypeId,
+    pub(crate) compactions: u32,
+}
 
 impl ArchetypeGeneration {
     /// The first archetype.
     #[inline]
     pub const fn initial() -> Self {
-        ArchetypeGeneration(ArchetypeId::EMPTY)
+        ArchetypeGeneration {
+            id: ArchetypeId::EMPTY,
+            compactions: 0,
+        }
     }
 }
 
@@ -795,6 +833,8 @@ pub type ComponentIndex = HashMap<ComponentId, HashMap<ArchetypeId, ArchetypeRec
 pub struct Archetypes {
     pub(crate) archetypes: Vec<Archetype>,
     archetype_component_count: usize,
+    /// the number of compactions that reassigned archetype ids
+    compactions: u32,
     /// find the archetype id by the archetype's components
     by_components: HashMap<ArchetypeComponents, ArchetypeId>,
     /// find all the archetypes that contain a component
@@ -819,6 +859,7 @@ impl Archetypes {
             by_components: Default::default(),
             by_component: Default::default(),
             archetype_component_count: 0,
+            compactions: 0,
         };
         // SAFETY: Empty archetype has no components
         unsafe {
@@ -840,8 +881,10 @@ impl Archetypes {
     /// function was called.
     #[inline]
     pub fn generation(&self) -> ArchetypeGeneration {
-        let id = ArchetypeId::new(self.archetypes.len());
-        ArchetypeGeneration(id)
+        ArchetypeGeneration {
+            id: ArchetypeId::new(self.archetypes.len()),
+            compactions: self.compactions,
+        }
     }
 
     /// Fetches the total number of [`Archetype`]s within the world.
@@ -941,6 +984,7 @@ impl Archetypes {
 
         let archetypes = &mut self.archetypes;
         let archetype_component_count = &mut self.archetype_component_count;
+        let compactions = self.compactions;
         let component_index = &mut self.by_component;
         *self
             .by_components
@@ -965,6 +1009,7 @@ impl Archetypes {
                     observers,
                     id,
                     table_id,
+                    compactions,
                     table_components
                         .iter()
                         .copied()
@@ -992,6 +1037,75 @@ impl Archetypes {
         }
     }
 
+    /// Shrinks the capacity of the entity lists of all archetypes to their length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for archetype in &mut self.archetypes {
+            archetype.shrink_to_fit();
+        }
+    }
+
+    /// Removes the archetypes without entities, except for the [empty archetype](Self::empty),
+    /// and gives the remaining archetypes consecutive ids, keeping their order.
+    ///
+    /// Returns `false` if no archetype was removed, in which case nothing changed.
+    /// Otherwise, the locations of the entities must be updated to the new ids, and
+    /// [`ArchetypeGeneration`]s taken before this call become outdated.
+    pub(crate) fn remove_empty(&mut self) -> bool {
+        let is_kept =
+            |archetype: &Archetype| archetype.id == ArchetypeId::EMPTY || !archetype.is_empty();
+        let mut new_ids = Vec::with_capacity(self.archetypes.len());
+        let mut kept = 0;
+        for archetype in &self.archetypes {
+            if is_kept(archetype) {
+                new_ids.push(Some(ArchetypeId::new(kept)));
+                kept += 1;
+            } else {
+                new_ids.push(None);
+            }
+        }
+        if kept == self.archetypes.len() {
+            return false;
+        }
+
+        self.compactions = self.compactions.wrapping_add(1);
+        self.archetypes.retain(is_kept);
+        for archetype in &mut self.archetypes {
+            archetype.id = new_ids[archetype.id.index()].unwrap();
+            archetype.compactions = self.compactions;
+            // The edges may point to removed archetypes, and are rebuilt as entities move.
+            archetype.edges = Edges::default();
+        }
+        self.by_components
+            .retain(|_, id| match new_ids[id.index()] {
+                Some(new_id) => {
+                    *id = new_id;
+                    true
+                }
+                None => false,
+            });
+        self.by_component.retain(|_, archetypes| {
+            *archetypes = archetypes
+                .drain()
+                .filter_map(|(id, record)| Some((new_ids[id.index()]?, record)))
+                .collect();
+            !archetypes.is_empty()
+        });
+        true
+    }
+
+    /// Replaces the table id of every archetype by `new_table_ids[table_id]`.
+    ///
+    /// # Panics
+    /// Panics if `new_table_ids` does not contain the new id of the table of an archetype.
+    ///
+    /// # Safety
+    /// The new table ids must exist in the tables.
+    pub(crate) unsafe fn remap_tables(&mut self, new_table_ids: &[Option<TableId>]) {
+        for archetype in &mut self.archetypes {
+            archetype.table_id = new_table_ids[archetype.table_id.as_usize()].unwrap();
+        }
+    }
+
     /// Get the component index
     pub(crate) fn component_index(&self) -> &ComponentIndex {
         &self.by_component
@@ -1021,7 +1135,11 @@ impl Index<RangeFrom<ArchetypeGeneration>> for Archetypes {
 
     #[inline]
     fn index(&self, index: RangeFrom<ArchetypeGeneration>) -> &Self::Output {
-        &self.archetypes[index.start.0.index()..]
+        if index.start.compactions != self.compactions {
+            // The generation is outdated, so all archetypes are new to it.
+            return &self.archetypes;
+        }
+        &self.archetypes[index.start.id.index()..]
     }
 }
 impl Index<ArchetypeId> for Archetypes {
@@ -1,6 +1,6 @@
 use alloc::{borrow::ToOwned, boxed::Box, collections::VecDeque, vec::Vec};
 use bevy_platform::collections::{HashMap, HashSet};
-use bevy_ptr::{Ptr, PtrMut};
+use bevy_ptr::{OwningPtr, Ptr, PtrMut};
 use bumpalo::Bump;
 use core::any::TypeId;
 
@@ -437,6 +437,61 @@ impl EntityCloner {
         }
     }
 
+    /// Clones a single component value of `entity` with the [`ComponentCloneBehavior`] of its component, allocating
+    /// the clone in `allocator` instead of writing it to another entity. Entities are not mapped, and deferred
+    /// operations queued by the clone function are discarded.
+    ///
+    /// Returns `None` if the clone function did not write a value, for example because the component uses
+    /// [`ComponentCloneBehavior::Ignore`].
+    ///
+    /// # Safety
+    /// - `info` must come from `world`.
+    /// - `source` must point to a valid value of the component described by `info`.
+    pub(crate) unsafe fn clone_component_value<'b>(
+        world: &World,
+        info: &ComponentInfo,
+        entity: Entity,
+        source: Ptr<'_>,
+        allocator: &'b Bump,
+    ) -> Option<OwningPtr<'b>> {
+        let handler = info
+            .clone_behavior()
+            .resolve(ComponentCloneBehavior::global_default_fn());
+        let mut bundle_scratch = BundleScratch::with_capacity(1);
+        let mut entity_cloner = EntityCloner::default();
+        let mut mapper = EntityHashMap::<Entity>::new();
+
+        #[cfg(feature = "bevy_reflect")]
+        let app_registry = world.get_resource::<crate::reflect::AppTypeRegistry>();
+        #[cfg(not(feature = "bevy_reflect"))]
+        let app_registry = Option::<&()>::None;
+
+        // SAFETY:
+        // - `info` comes from `world`
+        // - `source` points to a valid value of the component described by `info`
+        let mut ctx = unsafe {
+            ComponentCloneCtx::new(
+                info.id(),
+                entity,
+                entity,
+                allocator,
+                &mut bundle_scratch,
+                world.entities(),
+                info,
+                &mut entity_cloner,
+                &mut mapper,
+                app_registry,
+            )
+        };
+        (handler)(&SourceComponent { ptr: source, info }, &mut ctx);
+
+        // SAFETY: values written by clone functions are initialized and uniquely owned by the scratch
+        bundle_scratch
+            .component_ptrs
+            .pop()
+            .map(|ptr| unsafe { ptr.promote() })
+    }
+
     /// Returns `true` if this cloner is configured to clone entities referenced in cloned components via [`RelationshipTarget::LINKED_SPAWN`](crate::relationship::RelationshipTarget::LINKED_SPAWN).
     /// This will produce "deep" / recursive clones of relationship trees that have "linked spawn".
     #[inline]
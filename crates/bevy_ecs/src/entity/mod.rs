@@ -80,7 +80,7 @@ use crate::{
     storage::{SparseSetIndex, TableId, TableRow},
 };
 use alloc::vec::Vec;
-use bevy_platform::sync::atomic::Ordering;
+use bevy_platform::{collections::HashMap, sync::atomic::Ordering};
 use core::{
     fmt,
     hash::Hash,
@@ -707,6 +707,9 @@ pub struct Entities {
     /// [`flush`]: Entities::flush
     pending: Vec<EntityRow>,
     free_cursor: AtomicIdCursor,
+    /// Generations that rows allocated with [`Entities::alloc_at`] must not fall behind once they
+    /// are freed again, so that handles to entities that used the row in the meantime stay invalid.
+    generation_floors: HashMap<EntityRow, EntityGeneration>,
 }
 
 impl Entities {
@@ -715,6 +718,7 @@ impl Entities {
             meta: Vec::new(),
             pending: Vec::new(),
             free_cursor: AtomicIdCursor::new(0),
+            generation_floors: HashMap::new(),
         }
     }
 
@@ -824,6 +828,40 @@ impl Entities {
         }
     }
 
+    /// Allocate a specific entity ID, including its generation.
+    ///
+    /// Returns `false` and does nothing if the row of `entity` is currently in use.
+    /// Rows past the end of the allocated range are added to the freelist.
+    ///
+    /// If the row has been used by later generations in the meantime, freeing `entity` again
+    /// continues from the newest generation instead of the one after `entity`.
+    pub(crate) fn alloc_at(&mut self, entity: Entity) -> bool {
+        self.verify_flushed();
+        let index = entity.index() as usize;
+        if index >= self.meta.len() {
+            let old_len = self.meta.len();
+            self.pending.extend((old_len..index).map(|index| {
+                // SAFETY: `index` is smaller than the index of `entity`, which is a valid row
+                EntityRow::new(unsafe { NonMaxU32::new_unchecked(index as u32) })
+            }));
+            self.meta.resize(index + 1, EntityMeta::EMPTY);
+        } else if let Some(position) = self.pending.iter().position(|row| *row == entity.row()) {
+            self.pending.swap_remove(position);
+        } else {
+            return false;
+        }
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+        let generation = self.meta[index].generation;
+        if generation > entity.generation.after_versions(1) {
+            self.generation_floors
+                .entry(entity.row())
+                .and_modify(|floor| *floor = (*floor).max(generation))
+                .or_insert(generation);
+        }
+        self.meta[index].generation = entity.generation;
+        true
+    }
+
     /// Destroy an entity, allowing it to be reused.
     ///
     /// Must not be called while reserved entities are awaiting `flush()`.
@@ -843,6 +881,11 @@ impl Entities {
                 entity.row()
             );
         }
+        if !self.generation_floors.is_empty() {
+            if let Some(floor) = self.generation_floors.remove(&entity.row()) {
+                meta.generation = meta.generation.max(floor);
+            }
+        }
 
         let loc = mem::replace(&mut meta.location, EntityMeta::EMPTY.location);
 
@@ -882,11 +925,25 @@ impl Entities {
             .is_some_and(|e| e.generation() == entity.generation())
     }
 
+    /// Shrinks the capacity of the entity metadata and of the list of freed entities to fit.
+    ///
+    /// The metadata of freed entities is kept, so their ids are never reused with a stale generation.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        debug_assert!(
+            !self.needs_flush(),
+            "reserved entities must be flushed before shrinking"
+        );
+        self.meta.shrink_to_fit();
+        self.pending.shrink_to_fit();
+        self.generation_floors.shrink_to_fit();
+    }
+
     /// Clears all [`Entity`] from the World.
     pub fn clear(&mut self) {
         self.meta.clear();
         self.pending.clear();
         *self.free_cursor.get_mut() = 0;
+        self.generation_floors.clear();
     }
 
     /// Returns the location of an [`Entity`].
@@ -1,13 +1,19 @@
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, collections::VecDeque, vec::Vec};
 use bevy_ecs::{
     change_detection::MaybeLocation,
     event::{Event, EventCursor, EventId, EventInstance},
     resource::Resource,
 };
+use bevy_platform::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 use core::{
     marker::PhantomData,
     ops::{Deref, DerefMut},
 };
+
+use super::event_cursor::CursorTracker;
 #[cfg(feature = "bevy_reflect")]
 use {
     bevy_ecs::reflect::ReflectResource,
@@ -35,7 +41,7 @@ use {
 /// Events will persist across a single frame boundary and so ordering of event producers and
 /// consumers is not critical (although poorly-planned ordering may cause accumulating lag).
 /// If events are not handled by the end of the frame after they are updated, they will be
-/// dropped silently.
+/// dropped, unless another [`EventRetention`] is used.
 ///
 /// # Example
 /// ```
@@ -77,6 +83,9 @@ use {
 ///
 /// The buffers in [`Events`] will grow indefinitely if [`update`](Events::update) is never called.
 ///
+/// With another [`EventRetention`] than the default, the oldest buffer is not cleared on
+/// [`update`](Events::update), but only rid of the events that are no longer retained.
+///
 /// An alternative call pattern would be to call [`update`](Events::update)
 /// manually across frames to control when events are cleared.
 /// This complicates consumption and risks ever-expanding memory usage if not cleaned up,
@@ -98,6 +107,13 @@ pub struct Events<E: Event> {
     /// Holds the newer events.
     pub(crate) events_b: EventSequence<E>,
     pub(crate) event_count: usize,
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    retention: EventRetention,
+    /// The event count at each of the last updates, oldest first.
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    update_event_counts: VecDeque<usize>,
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    cursors: Vec<Arc<CursorTracker>>,
 }
 
 // Derived Default impl would incorrectly require E: Default
@@ -107,11 +123,88 @@ impl<E: Event> Default for Events<E> {
             events_a: Default::default(),
             events_b: Default::default(),
             event_count: Default::default(),
+            retention: Default::default(),
+            update_event_counts: Default::default(),
+            cursors: Default::default(),
         }
     }
 }
 
+/// How long [`Events`] keep the events sent to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventRetention {
+    /// Events are dropped on the given number of [`Events::update`] after being sent.
+    ///
+    /// With the default of `Updates(2)`, events are kept until the end of the frame after the one
+    /// they were sent in. `Updates(0)` behaves like `Updates(1)`, dropping events on the next update.
+    Updates(usize),
+    /// Events are kept until every [registered](EventCursor#registered-cursors) cursor has read them,
+    /// and at least as long as with the default retention.
+    ///
+    /// As a registered cursor that never reads keeps every event, this is best used
+    /// for events that every reader eventually reads, like the ones read in `FixedUpdate`.
+    UntilRead,
+}
+
+impl Default for EventRetention {
+    fn default() -> Self {
+        Self::Updates(2)
+    }
+}
+
 impl<E: Event> Events<E> {
+    /// Creates empty events with the given [`EventRetention`].
+    pub fn with_retention(retention: EventRetention) -> Self {
+        Self {
+            retention,
+            ..Self::default()
+        }
+    }
+
+    /// Returns how long the events are kept.
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// Sets how long the events are kept, from the next [`update`](Events::update).
+    pub fn set_retention(&mut self, retention: EventRetention) {
+        self.retention = retention;
+    }
+
+    /// Returns `true` if the [`EventReader`](super::EventReader)s and
+    /// [`EventMutator`](super::EventMutator)s of these events register their cursors when their
+    /// system is initialized, which is the case when the [`EventRetention`] is not the default.
+    pub fn tracks_readers(&self) -> bool {
+        self.retention != EventRetention::default()
+    }
+
+    /// Gets a new [registered](EventCursor#registered-cursors) [`EventCursor`] with the given `name`,
+    /// used to report the events it missed. This will include all events already in the event buffers.
+    pub fn register_cursor(&mut self, name: impl Into<Cow<'static, str>>) -> EventCursor<E> {
+        let tracker = Arc::new(CursorTracker {
+            name: name.into(),
+            position: AtomicUsize::new(self.oldest_event_count()),
+            missed: AtomicUsize::new(0),
+        });
+        self.cursors.push(tracker.clone());
+        EventCursor {
+            tracker: Some(tracker),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the name of each [registered](EventCursor#registered-cursors) cursor that missed events
+    /// since the last call, and the number of events it missed.
+    ///
+    /// Events are missed by a registered cursor when they are dropped by an [`update`](Events::update)
+    /// before it read them.
+    pub fn take_missed_events(&mut self) -> impl Iterator<Item = (&str, usize)> {
+        self.cursors.iter().filter_map(|tracker| {
+            let missed = tracker.missed.swap(0, Ordering::Relaxed);
+            (missed > 0).then_some((&*tracker.name, missed))
+        })
+    }
+
     /// Returns the index of the oldest event stored in the event buffer.
     pub fn oldest_event_count(&self) -> usize {
         self.events_a.start_event_count
@@ -187,7 +280,7 @@ impl<E: Event> Events<E> {
     ///
     /// If you need access to the events that were removed, consider using [`Events::update_drain`].
     pub fn update(&mut self) {
-        core::mem::swap(&mut self.events_a, &mut self.events_b);
+        self.swap_buffers();
         self.events_b.clear();
         self.events_b.start_event_count = self.event_count;
         debug_assert_eq!(
@@ -202,7 +295,7 @@ impl<E: Event> Events<E> {
     /// If you do not need to take ownership of the removed events, use [`Events::update`] instead.
     #[must_use = "If you do not need the returned events, call .update() instead."]
     pub fn update_drain(&mut self) -> impl Iterator<Item = E> + '_ {
-        core::mem::swap(&mut self.events_a, &mut self.events_b);
+        self.swap_buffers();
         let iter = self.events_b.events.drain(..);
         self.events_b.start_event_count = self.event_count;
         debug_assert_eq!(
@@ -213,6 +306,66 @@ impl<E: Event> Events<E> {
         iter.map(|e| e.event)
     }
 
+    /// Moves the retained events to `events_a`, and the events to drop to `events_b`.
+    fn swap_buffers(&mut self) {
+        let keep_from = self
+            .retained_event_count()
+            .max(self.events_a.start_event_count);
+
+        for tracker in &self.cursors {
+            let position = tracker.position.load(Ordering::Relaxed);
+            let missed = keep_from.saturating_sub(position.max(self.events_a.start_event_count));
+            if missed > 0 {
+                tracker.missed.fetch_add(missed, Ordering::Relaxed);
+            }
+        }
+
+        if keep_from < self.events_b.start_event_count {
+            // Some of the oldest events are retained: the newer events are moved after them.
+            let a = &mut self.events_a;
+            let retained = a.events.split_off(keep_from - a.start_event_count);
+            let dropped = core::mem::replace(&mut a.events, retained);
+            a.events.append(&mut self.events_b.events);
+            a.start_event_count = keep_from;
+            self.events_b.events = dropped;
+        } else {
+            core::mem::swap(&mut self.events_a, &mut self.events_b);
+            let a = &mut self.events_a;
+            let dropped = (keep_from - a.start_event_count).min(a.len());
+            if dropped > 0 {
+                self.events_b.extend(a.events.drain(..dropped));
+                a.start_event_count += dropped;
+            }
+        }
+    }
+
+    /// Returns the count of the oldest event to keep on this update.
+    fn retained_event_count(&mut self) -> usize {
+        let updates = match self.retention {
+            EventRetention::Updates(updates) => updates.max(1),
+            EventRetention::UntilRead => 2,
+        };
+        self.update_event_counts.push_back(self.event_count);
+        while self.update_event_counts.len() > updates {
+            self.update_event_counts.pop_front();
+        }
+        let mut keep_from = if self.update_event_counts.len() < updates {
+            0
+        } else {
+            self.update_event_counts[0]
+        };
+
+        // The trackers of the cursors that were dropped are only referenced here.
+        self.cursors
+            .retain(|tracker| Arc::strong_count(tracker) > 1);
+        if self.retention == EventRetention::UntilRead {
+            for tracker in &self.cursors {
+                keep_from = keep_from.min(tracker.position.load(Ordering::Relaxed));
+            }
+        }
+        keep_from
+    }
+
     #[inline]
     fn reset_start_event_count(&mut self) {
         self.events_a.start_event_count = self.event_count;
@@ -1,9 +1,23 @@
+use alloc::borrow::Cow;
 use bevy_ecs::event::{
     Event, EventIterator, EventIteratorWithId, EventMutIterator, EventMutIteratorWithId, Events,
 };
 #[cfg(feature = "multi_threaded")]
 use bevy_ecs::event::{EventMutParIter, EventParIter};
-use core::marker::PhantomData;
+use bevy_ecs::{
+    component::Tick,
+    system::{ReadOnlySystemParam, SystemMeta, SystemParam},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+use bevy_platform::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use bevy_utils::synccell::SyncCell;
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 /// Stores the state for an [`EventReader`] or [`EventMutator`].
 ///
@@ -47,27 +61,48 @@ use core::marker::PhantomData;
 /// # bevy_ecs::system::assert_is_system(send_and_receive_events);
 /// ```
 ///
+/// # Registered cursors
+///
+/// A cursor can be registered with [`Events::register_cursor`] or
+/// [`EventRegistry::register_cursor`](super::EventRegistry::register_cursor).
+/// The events keep track of how far registered cursors have read, so that they can be retained
+/// until read with [`EventRetention::UntilRead`](super::EventRetention::UntilRead), and so that
+/// the events dropped before being read by a registered cursor are reported by the
+/// [`EventRegistry`](super::EventRegistry).
+///
 /// [`EventReader`]: super::EventReader
 /// [`EventMutator`]: super::EventMutator
 #[derive(Debug)]
 pub struct EventCursor<E: Event> {
     pub(super) last_event_count: usize,
+    pub(super) tracker: Option<Arc<CursorTracker>>,
     pub(super) _marker: PhantomData<E>,
 }
 
+/// How far a registered [`EventCursor`] has read, shared with the [`Events`] it reads.
+#[derive(Debug)]
+pub(super) struct CursorTracker {
+    pub(super) name: Cow<'static, str>,
+    pub(super) position: AtomicUsize,
+    pub(super) missed: AtomicUsize,
+}
+
 impl<E: Event> Default for EventCursor<E> {
     fn default() -> Self {
         EventCursor {
             last_event_count: 0,
+            tracker: None,
             _marker: Default::default(),
         }
     }
 }
 
+/// Cloning a registered cursor returns a cursor that is not registered.
 impl<E: Event> Clone for EventCursor<E> {
     fn clone(&self) -> Self {
         EventCursor {
             last_event_count: self.last_event_count,
+            tracker: None,
             _marker: PhantomData,
         }
     }
@@ -136,5 +171,72 @@ impl<E: Event> EventCursor<E> {
     /// See [`EventReader::clear()`](super::EventReader::clear)
     pub fn clear(&mut self, events: &Events<E>) {
         self.last_event_count = events.event_count;
+        self.publish();
+    }
+
+    /// Returns `true` if this cursor was registered with the [`Events`] it reads.
+    pub fn is_registered(&self) -> bool {
+        self.tracker.is_some()
+    }
+
+    /// Lets the [`Events`] know how far this cursor has read, if it is registered.
+    #[inline]
+    pub(super) fn publish(&self) {
+        if let Some(tracker) = &self.tracker {
+            tracker
+                .position
+                .store(self.last_event_count, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The [`EventCursor`] of an [`EventReader`](super::EventReader) or
+/// [`EventMutator`](super::EventMutator), local to its system.
+///
+/// The cursor is [registered](EventCursor#registered-cursors) under the name of the system if the
+/// events [track their readers](Events::tracks_readers) when the system is initialized.
+#[derive(Debug)]
+pub struct SystemEventCursor<'s, E: Event>(&'s mut EventCursor<E>);
+
+impl<E: Event> Deref for SystemEventCursor<'_, E> {
+    type Target = EventCursor<E>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<E: Event> DerefMut for SystemEventCursor<'_, E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+// SAFETY: only local state is accessed
+unsafe impl<'a, E: Event> ReadOnlySystemParam for SystemEventCursor<'a, E> {}
+
+// SAFETY: only local state is accessed
+unsafe impl<'a, E: Event> SystemParam for SystemEventCursor<'a, E> {
+    type State = SyncCell<EventCursor<E>>;
+    type Item<'w, 's> = SystemEventCursor<'s, E>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let cursor = match world.get_resource_mut::<Events<E>>() {
+            Some(mut events) if events.tracks_readers() => {
+                events.register_cursor(system_meta.name.clone())
+            }
+            _ => EventCursor::default(),
+        };
+        SyncCell::new(cursor)
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        _world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        SystemEventCursor(state.get())
     }
 }
@@ -65,6 +65,7 @@ impl<'a, E: Event> EventIteratorWithId<'a, E> {
         // Ensure `len` is implemented correctly
         debug_assert_eq!(unread_count, reader.len(events));
         reader.last_event_count = events.event_count - unread_count;
+        reader.publish();
         // Iterate the oldest first, then the newer events
         let chain = a.iter().chain(b.iter());
 
@@ -94,9 +95,14 @@ impl<'a, E: Event> Iterator for EventIteratorWithId<'a, E> {
                 tracing::trace!("EventReader::iter() -> {}", item.1);
                 self.reader.last_event_count += 1;
                 self.unread -= 1;
+                // Publish as we go, so that readers that stop early don't hold events back.
+                self.reader.publish();
                 Some(item)
             }
-            None => None,
+            None => {
+                self.reader.publish();
+                None
+            }
         }
     }
 
@@ -106,6 +112,7 @@ impl<'a, E: Event> Iterator for EventIteratorWithId<'a, E> {
 
     fn count(self) -> usize {
         self.reader.last_event_count += self.unread;
+        self.reader.publish();
         self.unread
     }
 
@@ -115,6 +122,7 @@ impl<'a, E: Event> Iterator for EventIteratorWithId<'a, E> {
     {
         let EventInstance { event_id, event } = self.chain.last()?;
         self.reader.last_event_count += self.unread;
+        self.reader.publish();
         Some((event, *event_id))
     }
 
@@ -122,10 +130,12 @@ impl<'a, E: Event> Iterator for EventIteratorWithId<'a, E> {
         if let Some(EventInstance { event_id, event }) = self.chain.nth(n) {
             self.reader.last_event_count += n + 1;
             self.unread -= n + 1;
+            self.reader.publish();
             Some((event, *event_id))
         } else {
             self.reader.last_event_count += self.unread;
             self.unread = 0;
+            self.reader.publish();
             None
         }
     }
@@ -165,6 +175,7 @@ impl<'a, E: Event> EventParIter<'a, E> {
         // Ensure `len` is implemented correctly
         debug_assert_eq!(unread_count, reader.len(events));
         reader.last_event_count = events.event_count - unread_count;
+        reader.publish();
 
         Self {
             reader,
@@ -245,6 +256,7 @@ impl<'a, E: Event> EventParIter<'a, E> {
             // Events are guaranteed to be read at this point.
             self.reader.last_event_count += self.unread;
             self.unread = 0;
+            self.reader.publish();
         }
     }
 
@@ -13,8 +13,8 @@ mod writer;
 pub(crate) use base::EventInstance;
 pub use base::{Event, EventId};
 pub use bevy_ecs_macros::Event;
-pub use collections::{Events, SendBatchIds};
-pub use event_cursor::EventCursor;
+pub use collections::{EventRetention, Events, SendBatchIds};
+pub use event_cursor::{EventCursor, SystemEventCursor};
 #[cfg(feature = "multi_threaded")]
 pub use iterators::EventParIter;
 pub use iterators::{EventIterator, EventIteratorWithId};
@@ -23,7 +23,7 @@ pub use mut_iterators::EventMutParIter;
 pub use mut_iterators::{EventMutIterator, EventMutIteratorWithId};
 pub use mutator::EventMutator;
 pub use reader::EventReader;
-pub use registry::{EventRegistry, ShouldUpdateEvents};
+pub use registry::{EventRegistry, MissedEvents, ShouldUpdateEvents};
 #[expect(
     deprecated,
     reason = "`EventUpdates` was renamed to `EventUpdateSystems`."
@@ -623,4 +623,164 @@ mod tests {
         });
         schedule.run(&mut world);
     }
+
+    #[test]
+    fn test_events_retention_updates() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::Updates(3));
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.send(TestEvent { i: 1 });
+        events.update();
+        assert_eq!(
+            get_events(&events, &mut events.get_cursor()),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+
+        events.update();
+        assert_eq!(
+            get_events(&events, &mut events.get_cursor()),
+            vec![TestEvent { i: 1 }],
+            "events are dropped on the third update after being sent"
+        );
+        assert_eq!(
+            events.update_drain().collect::<Vec<_>>(),
+            vec![TestEvent { i: 1 }]
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_events_retention_until_read() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::UntilRead);
+        let mut cursor = events.register_cursor("cursor");
+        let mut unregistered = events.get_cursor();
+        events.send(TestEvent { i: 0 });
+        for _ in 0..5 {
+            events.update();
+        }
+        events.send(TestEvent { i: 1 });
+        assert_eq!(
+            get_events(&events, &mut unregistered),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+        assert_eq!(
+            get_events(&events, &mut cursor),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+
+        // Read events are kept as long as with the default retention.
+        events.update();
+        assert_eq!(
+            get_events(&events, &mut events.get_cursor()),
+            vec![TestEvent { i: 1 }]
+        );
+
+        // Dropped cursors no longer retain events.
+        drop(cursor);
+        events.update();
+        assert!(events.is_empty());
+        assert_eq!(events.take_missed_events().count(), 0);
+    }
+
+    #[test]
+    fn test_events_retention_until_partially_read() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::UntilRead);
+        let mut cursor = events.register_cursor("cursor");
+        let mut mutator = events.register_cursor("mutator");
+        events.send_batch((0..4).map(|i| TestEvent { i }));
+
+        // Iterators that are not read to the end still publish how far they got.
+        assert_eq!(cursor.read(&events).next(), Some(&TestEvent { i: 0 }));
+        assert_eq!(cursor.read(&events).nth(1), Some(&TestEvent { i: 2 }));
+        assert_eq!(mutator.read_mut(&mut events).take(3).count(), 3);
+        for _ in 0..5 {
+            events.update();
+        }
+        assert_eq!(
+            get_events(&events, &mut events.get_cursor()),
+            vec![TestEvent { i: 3 }]
+        );
+        assert_eq!(events.take_missed_events().count(), 0);
+    }
+
+    #[test]
+    fn test_events_missed_by_registered_cursor() {
+        let mut events = Events::<TestEvent>::default();
+        let mut cursor = events.register_cursor("cursor");
+        events.send(TestEvent { i: 0 });
+        events.send(TestEvent { i: 1 });
+        events.update();
+        events.send(TestEvent { i: 2 });
+        events.update();
+        events.update();
+        assert_eq!(
+            events.take_missed_events().collect::<Vec<_>>(),
+            vec![("cursor", 3)]
+        );
+        assert_eq!(events.take_missed_events().count(), 0);
+
+        events.send(TestEvent { i: 3 });
+        events.update();
+        assert_eq!(get_events(&events, &mut cursor), vec![TestEvent { i: 3 }]);
+        events.update();
+        assert!(cursor.is_registered() && !cursor.clone().is_registered());
+        assert_eq!(events.take_missed_events().count(), 0);
+    }
+
+    #[test]
+    fn test_event_registry_reports_missed_events() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Frame(usize);
+
+        #[derive(Resource, Default)]
+        struct Received(Vec<usize>);
+
+        fn every_third_frame(frame: Res<Frame>) -> bool {
+            frame.0 % 3 == 0
+        }
+
+        fn read(mut events: EventReader<TestEvent>, mut received: ResMut<Received>) {
+            received.0.extend(events.read().map(|event| event.i));
+        }
+
+        fn run(retention: EventRetention) -> (Vec<usize>, Vec<MissedEvents>) {
+            let mut world = World::new();
+            world.init_resource::<Frame>();
+            world.init_resource::<Received>();
+            EventRegistry::set_retention::<TestEvent>(&mut world, retention);
+            let mut schedule = Schedule::default();
+            schedule.add_systems(
+                (
+                    event_update_system,
+                    read.run_if(every_third_frame),
+                    |mut frame: ResMut<Frame>, mut events: EventWriter<TestEvent>| {
+                        events.write(TestEvent { i: frame.0 });
+                        frame.0 += 1;
+                    },
+                )
+                    .chain(),
+            );
+            let mut missed = Vec::new();
+            for _ in 0..7 {
+                schedule.run(&mut world);
+                missed.extend_from_slice(world.resource::<EventRegistry>().missed_events());
+            }
+            (world.resource_mut::<Received>().0.clone(), missed)
+        }
+
+        let (received, missed) = run(EventRetention::UntilRead);
+        assert_eq!(received, vec![0, 1, 2, 3, 4, 5]);
+        assert!(missed.is_empty());
+
+        // The events sent right after the reader ran are dropped on the third update, before it
+        // runs again.
+        let (received, missed) = run(EventRetention::Updates(3));
+        assert_eq!(received, vec![1, 2, 4, 5]);
+        assert_eq!(missed.len(), 2);
+        assert!(missed
+            .iter()
+            .all(|missed| missed.count == 1 && missed.cursor.ends_with("read")));
+    }
 }
@@ -68,6 +68,7 @@ impl<'a, E: Event> EventMutIteratorWithId<'a, E> {
         let unread_count = a.len() + b.len();
 
         mutator.last_event_count = events.event_count - unread_count;
+        mutator.publish();
         // Iterate the oldest first, then the newer events
         let chain = a.iter_mut().chain(b.iter_mut());
 
@@ -97,9 +98,14 @@ impl<'a, E: Event> Iterator for EventMutIteratorWithId<'a, E> {
                 tracing::trace!("EventMutator::iter() -> {}", item.1);
                 self.mutator.last_event_count += 1;
                 self.unread -= 1;
+                // Publish as we go, so that readers that stop early don't hold events back.
+                self.mutator.publish();
                 Some(item)
             }
-            None => None,
+            None => {
+                self.mutator.publish();
+                None
+            }
         }
     }
 
@@ -109,6 +115,7 @@ impl<'a, E: Event> Iterator for EventMutIteratorWithId<'a, E> {
 
     fn count(self) -> usize {
         self.mutator.last_event_count += self.unread;
+        self.mutator.publish();
         self.unread
     }
 
@@ -118,6 +125,7 @@ impl<'a, E: Event> Iterator for EventMutIteratorWithId<'a, E> {
     {
         let EventInstance { event_id, event } = self.chain.last()?;
         self.mutator.last_event_count += self.unread;
+        self.mutator.publish();
         Some((event, *event_id))
     }
 
@@ -125,10 +133,12 @@ impl<'a, E: Event> Iterator for EventMutIteratorWithId<'a, E> {
         if let Some(EventInstance { event_id, event }) = self.chain.nth(n) {
             self.mutator.last_event_count += n + 1;
             self.unread -= n + 1;
+            self.mutator.publish();
             Some((event, *event_id))
         } else {
             self.mutator.last_event_count += self.unread;
             self.unread = 0;
+            self.mutator.publish();
             None
         }
     }
@@ -166,6 +176,7 @@ impl<'a, E: Event> EventMutParIter<'a, E> {
 
         let unread_count = a.len() + b.len();
         mutator.last_event_count = events.event_count - unread_count;
+        mutator.publish();
 
         Self {
             mutator,
@@ -248,6 +259,7 @@ impl<'a, E: Event> EventMutParIter<'a, E> {
             // Events are guaranteed to be read at this point.
             self.mutator.last_event_count += self.unread;
             self.unread = 0;
+            self.mutator.publish();
         }
     }
 
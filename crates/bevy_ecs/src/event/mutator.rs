@@ -1,8 +1,8 @@
 #[cfg(feature = "multi_threaded")]
 use bevy_ecs::event::EventMutParIter;
 use bevy_ecs::{
-    event::{Event, EventCursor, EventMutIterator, EventMutIteratorWithId, Events},
-    system::{Local, ResMut, SystemParam},
+    event::{Event, EventMutIterator, EventMutIteratorWithId, Events, SystemEventCursor},
+    system::{ResMut, SystemParam},
 };
 
 /// Mutably reads events of type `T` keeping track of which events have already been read
@@ -43,7 +43,7 @@ use bevy_ecs::{
 /// [`EventWriter`]: super::EventWriter
 #[derive(SystemParam, Debug)]
 pub struct EventMutator<'w, 's, E: Event> {
-    pub(super) reader: Local<'s, EventCursor<E>>,
+    pub(super) reader: SystemEventCursor<'s, E>,
     #[system_param(validation_message = "Event not initialized")]
     events: ResMut<'w, Events<E>>,
 }
@@ -1,8 +1,8 @@
 #[cfg(feature = "multi_threaded")]
 use bevy_ecs::event::EventParIter;
 use bevy_ecs::{
-    event::{Event, EventCursor, EventIterator, EventIteratorWithId, Events},
-    system::{Local, Res, SystemParam},
+    event::{Event, EventIterator, EventIteratorWithId, Events, SystemEventCursor},
+    system::{Res, SystemParam},
 };
 
 /// Reads events of type `T` in order and tracks which events have already been read.
@@ -15,7 +15,7 @@ use bevy_ecs::{
 /// [`EventWriter<T>`]: super::EventWriter
 #[derive(SystemParam, Debug)]
 pub struct EventReader<'w, 's, E: Event> {
-    pub(super) reader: Local<'s, EventCursor<E>>,
+    pub(super) reader: SystemEventCursor<'s, E>,
     #[system_param(validation_message = "Event not initialized")]
     events: Res<'w, Events<E>>,
 }
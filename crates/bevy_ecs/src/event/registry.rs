@@ -1,20 +1,37 @@
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
 use bevy_ecs::{
     change_detection::{DetectChangesMut, MutUntyped},
     component::{ComponentId, Tick},
-    event::{Event, Events},
+    event::{Event, EventCursor, EventRetention, Events},
     resource::Resource,
     world::World,
 };
+use log::warn;
 
 #[doc(hidden)]
 struct RegisteredEvent {
     component_id: ComponentId,
-    // Required to flush the secondary buffer and drop events even if left unchanged.
-    previously_updated: bool,
+    // Required to flush the retained events and drop them even if left unchanged.
+    retains_events: bool,
     // SAFETY: The component ID and the function must be used to fetch the Events<T> resource
     // of the same type initialized in `register_event`, or improper type casts will occur.
-    update: unsafe fn(MutUntyped),
+    // Returns whether events are still retained after the update.
+    update: unsafe fn(MutUntyped, &mut Vec<MissedEvents>) -> bool,
+}
+
+/// Events of one type that a [registered](EventCursor#registered-cursors) cursor missed, as they
+/// were dropped before it read them.
+///
+/// This is reported by [`EventRegistry::missed_events`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissedEvents {
+    /// The type name of the events.
+    pub event: &'static str,
+    /// The name of the cursor, which is the name of the system for the cursor of an
+    /// [`EventReader`](super::EventReader) or [`EventMutator`](super::EventMutator).
+    pub cursor: Cow<'static, str>,
+    /// The number of events missed.
+    pub count: usize,
 }
 
 /// A registry of all of the [`Events`] in the [`World`], used by [`event_update_system`](crate::event::update::event_update_system)
@@ -26,6 +43,7 @@ pub struct EventRegistry {
     /// This field is generally automatically updated by the [`signal_event_update_system`](crate::event::update::signal_event_update_system).
     pub should_update: ShouldUpdateEvents,
     event_updates: Vec<RegisteredEvent>,
+    missed_events: Vec<MissedEvents>,
 }
 
 /// Controls whether or not the events in an [`EventRegistry`] should be updated.
@@ -52,33 +70,79 @@ impl EventRegistry {
         let mut registry = world.get_resource_or_init::<Self>();
         registry.event_updates.push(RegisteredEvent {
             component_id,
-            previously_updated: false,
-            update: |ptr| {
+            retains_events: false,
+            update: |ptr, missed_events| {
                 // SAFETY: The resource was initialized with the type Events<T>.
-                unsafe { ptr.with_type::<Events<T>>() }
-                    .bypass_change_detection()
-                    .update();
+                let mut events = unsafe { ptr.with_type::<Events<T>>() };
+                let events = events.bypass_change_detection();
+                events.update();
+                missed_events.extend(events.take_missed_events().map(|(cursor, count)| {
+                    MissedEvents {
+                        event: core::any::type_name::<T>(),
+                        cursor: cursor.to_string().into(),
+                        count,
+                    }
+                }));
+                !events.is_empty()
             },
         });
     }
 
+    /// Sets how long the events of type `T` are kept, registering them if needed.
+    ///
+    /// This should be done before initializing the systems reading them, so that their cursors
+    /// are [registered](EventCursor#registered-cursors).
+    pub fn set_retention<T: Event>(world: &mut World, retention: EventRetention) {
+        if !world.contains_resource::<Events<T>>() {
+            Self::register_event::<T>(world);
+        }
+        world.resource_mut::<Events<T>>().set_retention(retention);
+    }
+
+    /// Gets a new [registered](EventCursor#registered-cursors) [`EventCursor`] reading the events of
+    /// type `T`, registering them if needed.
+    ///
+    /// The events dropped before the cursor read them are reported by [`EventRegistry::missed_events`]
+    /// under the given `name`.
+    pub fn register_cursor<T: Event>(
+        world: &mut World,
+        name: impl Into<Cow<'static, str>>,
+    ) -> EventCursor<T> {
+        if !world.contains_resource::<Events<T>>() {
+            Self::register_event::<T>(world);
+        }
+        world.resource_mut::<Events<T>>().register_cursor(name)
+    }
+
+    /// Returns the events that registered cursors missed during the last [`run_updates`](Self::run_updates).
+    ///
+    /// A warning is also logged for each of them.
+    pub fn missed_events(&self) -> &[MissedEvents] {
+        &self.missed_events
+    }
+
     /// Updates all of the registered events in the World.
     pub fn run_updates(&mut self, world: &mut World, last_change_tick: Tick) {
+        self.missed_events.clear();
         for registered_event in &mut self.event_updates {
             // Bypass the type ID -> Component ID lookup with the cached component ID.
             if let Some(events) = world.get_resource_mut_by_id(registered_event.component_id) {
                 let has_changed = events.has_changed_since(last_change_tick);
-                if registered_event.previously_updated || has_changed {
+                // Keep updating until no events are retained, then wait for more changes.
+                if registered_event.retains_events || has_changed {
                     // SAFETY: The update function pointer is called with the resource
                     // fetched from the same component ID.
-                    unsafe { (registered_event.update)(events) };
-                    // Always set to true if the events have changed, otherwise disable running on the second invocation
-                    // to wait for more changes.
-                    registered_event.previously_updated =
-                        has_changed || !registered_event.previously_updated;
+                    registered_event.retains_events =
+                        unsafe { (registered_event.update)(events, &mut self.missed_events) };
                 }
             }
         }
+        for missed in &self.missed_events {
+            warn!(
+                "{} missed {} `{}` events, which were dropped before it read them",
+                missed.cursor, missed.count, missed.event
+            );
+        }
     }
 
     /// Removes an event from the world and it's associated [`EventRegistry`].
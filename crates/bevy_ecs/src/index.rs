@@ -0,0 +1,354 @@
+//! Indexes finding the entities that hold a given value of a component, without scanning a query.
+//!
+//! Indexes are opt-in, and are only available for [immutable](Immutable) components
+//! that are [`Eq`], [`Hash`] and [`Clone`]: as the value of such components can only change
+//! when they are inserted or removed, the index is kept up to date by their
+//! [`on_insert`](crate::component::ComponentHooks::on_insert) and
+//! [`on_replace`](crate::component::ComponentHooks::on_replace) hooks.
+//!
+//! ```
+//! use bevy_ecs::{index::QueryByIndex, prelude::*};
+//!
+//! #[derive(Component, PartialEq, Eq, Hash, Clone)]
+//! #[component(immutable)]
+//! struct PlayerId(u32);
+//!
+//! #[derive(Component)]
+//! struct Score(u32);
+//!
+//! let mut world = World::new();
+//! world.register_index::<PlayerId>();
+//! world.spawn((PlayerId(7), Score(10)));
+//! world.spawn((PlayerId(8), Score(20)));
+//!
+//! fn score_of_player_7(scores: QueryByIndex<PlayerId, &Score>) {
+//!     let score = scores.single(&PlayerId(7)).unwrap();
+//!     assert_eq!(score.0, 10);
+//! }
+//! # world.run_system_once(score_of_player_7).unwrap();
+//! # use bevy_ecs::system::RunSystemOnce;
+//! ```
+//!
+//! # Warning
+//!
+//! The index must be registered before the component is first added to an entity,
+//! as the hooks of a component cannot change once it is in use.
+
+use crate::{
+    component::{Component, ComponentHook, HookContext, Immutable},
+    entity::{hash_set, Entity, EntityHashSet},
+    query::{QueryData, QueryFilter, QueryManyUniqueIter, QuerySingleError, ROQueryItem},
+    resource::Resource,
+    system::{Query, Res, SystemParam},
+    world::{DeferredWorld, World},
+};
+use bevy_platform::collections::HashMap;
+use core::{hash::Hash, marker::PhantomData};
+
+/// A component that can be indexed with [`World::register_index`].
+///
+/// This is implemented for every [immutable](Immutable) component that is [`Eq`], [`Hash`] and
+/// [`Clone`].
+pub trait IndexableComponent: Component<Mutability = Immutable> + Eq + Hash + Clone {}
+
+impl<C: Component<Mutability = Immutable> + Eq + Hash + Clone> IndexableComponent for C {}
+
+/// The entities with an empty set of entities, returned for values that are not in an index.
+static NO_ENTITIES: EntityHashSet = EntityHashSet::new();
+
+/// A [`Resource`] mapping each value of the component `C` to the entities that hold it.
+///
+/// This is added by [`World::register_index`], and kept up to date by the hooks of `C`.
+/// Use a [`QueryByIndex`] to query the entities holding a value.
+///
+/// Removing this resource stops the index from being updated, but the hooks of `C` keep running.
+/// Registering the index again rebuilds it from the entities holding `C`.
+#[derive(Resource)]
+pub struct ComponentIndex<C: IndexableComponent> {
+    entities: HashMap<C, EntityHashSet>,
+}
+
+/// The hooks of `C` that were registered before its index, run after updating the index.
+///
+/// These are kept apart from the [`ComponentIndex`] so that they still run if it is removed.
+#[derive(Resource)]
+struct IndexedComponentHooks<C: IndexableComponent> {
+    on_insert: Option<ComponentHook>,
+    on_replace: Option<ComponentHook>,
+    marker: PhantomData<C>,
+}
+
+impl<C: IndexableComponent> ComponentIndex<C> {
+    /// Returns the entities holding the given value of `C`.
+    pub fn get(&self, value: &C) -> &EntityHashSet {
+        self.entities.get(value).unwrap_or(&NO_ENTITIES)
+    }
+
+    /// Returns `true` if any entity holds the given value of `C`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns an iterator over the values of `C` held by at least one entity, and these entities.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &EntityHashSet)> {
+        self.entities.iter()
+    }
+
+    /// Returns the number of distinct values of `C` held by entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity holds `C`.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+impl World {
+    /// Registers an index of the values of the component `C`, stored in the
+    /// [`ComponentIndex<C>`] resource and queried with a [`QueryByIndex`].
+    ///
+    /// Registering the same index more than once has no effect. If the [`ComponentIndex<C>`]
+    /// was removed since, it is rebuilt from the entities holding `C`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `C` exists in any archetypes when its index is first registered, as the hooks
+    /// maintaining the index could not account for the existing entities.
+    pub fn register_index<C: IndexableComponent>(&mut self) {
+        if self.contains_resource::<IndexedComponentHooks<C>>() {
+            if !self.contains_resource::<ComponentIndex<C>>() {
+                let mut entities = HashMap::<C, EntityHashSet>::default();
+                for (entity, value) in self.query::<(Entity, &C)>().iter(self) {
+                    entities.entry(value.clone()).or_default().insert(entity);
+                }
+                self.insert_resource(ComponentIndex { entities });
+            }
+            return;
+        }
+        let hooks = self.register_component_hooks::<C>();
+        let chained = IndexedComponentHooks::<C> {
+            on_insert: hooks.on_insert.replace(index_on_insert::<C>),
+            on_replace: hooks.on_replace.replace(index_on_replace::<C>),
+            marker: PhantomData,
+        };
+        self.insert_resource(chained);
+        self.insert_resource(ComponentIndex::<C> {
+            entities: HashMap::default(),
+        });
+    }
+}
+
+fn index_on_insert<C: IndexableComponent>(mut world: DeferredWorld, context: HookContext) {
+    if let Some(value) = world.get::<C>(context.entity).cloned() {
+        if let Some(mut index) = world.get_resource_mut::<ComponentIndex<C>>() {
+            index
+                .entities
+                .entry(value)
+                .or_default()
+                .insert(context.entity);
+        }
+    }
+    let hook = world
+        .get_resource::<IndexedComponentHooks<C>>()
+        .and_then(|hooks| hooks.on_insert);
+    if let Some(hook) = hook {
+        hook(world, context);
+    }
+}
+
+fn index_on_replace<C: IndexableComponent>(mut world: DeferredWorld, context: HookContext) {
+    if let Some(value) = world.get::<C>(context.entity).cloned() {
+        if let Some(mut index) = world.get_resource_mut::<ComponentIndex<C>>() {
+            if let Some(entities) = index.entities.get_mut(&value) {
+                entities.remove(&context.entity);
+                if entities.is_empty() {
+                    index.entities.remove(&value);
+                }
+            }
+        }
+    }
+    let hook = world
+        .get_resource::<IndexedComponentHooks<C>>()
+        .and_then(|hooks| hooks.on_replace);
+    if let Some(hook) = hook {
+        hook(world, context);
+    }
+}
+
+/// A [`SystemParam`] querying the entities that hold a given value of the indexed component `C`.
+///
+/// Only the entities holding the value are fetched, instead of iterating over every entity
+/// matching the query. The index must have been registered with [`World::register_index`].
+///
+/// ```
+/// # use bevy_ecs::{index::QueryByIndex, prelude::*};
+/// #[derive(Component, PartialEq, Eq, Hash, Clone)]
+/// #[component(immutable)]
+/// struct Chunk(i32, i32);
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// fn heal_chunk(mut query: QueryByIndex<Chunk, &mut Health>) {
+///     for mut health in query.iter_mut(&Chunk(3, 4)) {
+///         health.0 += 10;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(heal_chunk);
+/// ```
+#[derive(SystemParam)]
+pub struct QueryByIndex<
+    'w,
+    's,
+    C: IndexableComponent,
+    D: QueryData + 'static = Entity,
+    F: QueryFilter + 'static = (),
+> {
+    index: Res<'w, ComponentIndex<C>>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, C: IndexableComponent, D: QueryData, F: QueryFilter> QueryByIndex<'w, 's, C, D, F> {
+    /// Returns the index of `C`.
+    pub fn index(&self) -> &ComponentIndex<C> {
+        &self.index
+    }
+
+    /// Returns the underlying query, matching every entity.
+    pub fn query(&self) -> &Query<'w, 's, D, F> {
+        &self.query
+    }
+
+    /// Returns an iterator over the read-only query items of the entities holding `value`.
+    pub fn iter(
+        &self,
+        value: &C,
+    ) -> QueryManyUniqueIter<'_, 's, D::ReadOnly, F, hash_set::Iter<'_>> {
+        self.query.iter_many_unique(self.index.get(value))
+    }
+
+    /// Returns an iterator over the query items of the entities holding `value`.
+    pub fn iter_mut(&mut self, value: &C) -> QueryManyUniqueIter<'_, 's, D, F, hash_set::Iter<'_>> {
+        self.query.iter_many_unique_mut(self.index.get(value))
+    }
+
+    /// Returns the read-only query item of the single entity holding `value`.
+    ///
+    /// Returns an error if there is no such entity matching the query, or more than one.
+    pub fn single(&self, value: &C) -> Result<ROQueryItem<'_, D>, QuerySingleError> {
+        let mut items = self.iter(value);
+        let item = items
+            .next()
+            .ok_or(QuerySingleError::NoEntities(core::any::type_name::<Self>()))?;
+        if items.next().is_some() {
+            return Err(QuerySingleError::MultipleEntities(core::any::type_name::<
+                Self,
+            >()));
+        }
+        Ok(item)
+    }
+
+    /// Returns `true` if an entity holding `value` matches the query.
+    pub fn contains(&self, value: &C) -> bool {
+        self.index
+            .get(value)
+            .iter()
+            .any(|entity| self.query.contains(*entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::RunSystemOnce;
+    use alloc::vec::Vec;
+
+    #[derive(Component, PartialEq, Eq, Hash, Clone, Debug)]
+    #[component(immutable, on_replace = count_replaced)]
+    struct Chunk(i32, i32);
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    fn count_replaced(mut world: DeferredWorld, _: HookContext) {
+        world.resource_mut::<Replaced>().0 += 1;
+    }
+
+    #[derive(Resource, Default)]
+    struct Replaced(usize);
+
+    #[test]
+    fn component_index() {
+        let mut world = World::new();
+        world.init_resource::<Replaced>();
+        world.register_index::<Chunk>();
+
+        let a = world.spawn((Chunk(0, 0), Health(1))).id();
+        let b = world.spawn((Chunk(0, 0), Health(2))).id();
+        let c = world.spawn(Chunk(1, 0)).id();
+        let index = world.resource::<ComponentIndex<Chunk>>();
+        assert_eq!(index.len(), 2);
+        assert!(index.get(&Chunk(0, 0)).contains(&a) && index.get(&Chunk(0, 0)).contains(&b));
+
+        // Replacing, removing and despawning keep the index up to date.
+        world.entity_mut(a).insert(Chunk(1, 0));
+        world.entity_mut(b).remove::<Chunk>();
+        world.despawn(c);
+        let index = world.resource::<ComponentIndex<Chunk>>();
+        assert!(!index.contains(&Chunk(0, 0)));
+        assert_eq!(index.get(&Chunk(1, 0)).iter().collect::<Vec<_>>(), [&a]);
+        // The hook of the component still runs.
+        assert_eq!(world.resource::<Replaced>().0, 3);
+    }
+
+    #[test]
+    fn query_by_index() {
+        let mut world = World::new();
+        world.register_index::<Chunk>();
+        world.spawn((Chunk(3, 4), Health(10)));
+        world.spawn((Chunk(3, 4), Health(20)));
+        world.spawn((Chunk(5, 5), Health(30)));
+        world.spawn(Chunk(6, 6));
+
+        world
+            .run_system_once(|mut query: QueryByIndex<Chunk, &mut Health>| {
+                for mut health in query.iter_mut(&Chunk(3, 4)) {
+                    health.0 += 1;
+                }
+                let mut healths = query.iter(&Chunk(3, 4)).map(|h| h.0).collect::<Vec<_>>();
+                healths.sort();
+                assert_eq!(healths, [11, 21]);
+                assert_eq!(query.single(&Chunk(5, 5)).unwrap().0, 30);
+                assert!(query.single(&Chunk(3, 4)).is_err());
+                assert!(query.single(&Chunk(6, 6)).is_err());
+                assert!(!query.contains(&Chunk(6, 6)));
+                assert!(query.iter(&Chunk(7, 7)).next().is_none());
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn removed_index_still_runs_hooks() {
+        let mut world = World::new();
+        world.init_resource::<Replaced>();
+        world.register_index::<Chunk>();
+        let a = world.spawn(Chunk(0, 0)).id();
+
+        world.remove_resource::<ComponentIndex<Chunk>>();
+        world.entity_mut(a).insert(Chunk(1, 0));
+        let b = world.spawn(Chunk(1, 0)).id();
+        world.despawn(b);
+        assert_eq!(world.resource::<Replaced>().0, 2);
+
+        // Registering the index again rebuilds it.
+        world.register_index::<Chunk>();
+        let index = world.resource::<ComponentIndex<Chunk>>();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get(&Chunk(1, 0)).iter().collect::<Vec<_>>(), [&a]);
+        world.despawn(a);
+        assert_eq!(world.resource::<Replaced>().0, 3);
+        assert!(world.resource::<ComponentIndex<Chunk>>().is_empty());
+    }
+}
@@ -928,6 +928,9 @@ pub struct FilteredAccess<T: SparseSetIndex> {
     // An array of filter sets to express `With` or `Without` clauses in disjunctive normal form, for example: `Or<(With<A>, With<B>)>`.
     // Filters like `(With<A>, Or<(With<B>, Without<C>)>` are expanded into `Or<((With<A>, With<B>), (With<A>, Without<C>))>`.
     pub(crate) filter_sets: Vec<AccessFilters<T>>,
+    // The part of `access` made on other entities than the filtered one, for example through a relationship.
+    // The filters cannot make it disjoint from another access.
+    pub(crate) related: Access<T>,
 }
 
 // This is needed since `#[derive(Clone)]` does not generate optimized `clone_from`.
@@ -937,6 +940,7 @@ impl<T: SparseSetIndex> Clone for FilteredAccess<T> {
             access: self.access.clone(),
             required: self.required.clone(),
             filter_sets: self.filter_sets.clone(),
+            related: self.related.clone(),
         }
     }
 
@@ -944,6 +948,7 @@ impl<T: SparseSetIndex> Clone for FilteredAccess<T> {
         self.access.clone_from(&source.access);
         self.required.clone_from(&source.required);
         self.filter_sets.clone_from(&source.filter_sets);
+        self.related.clone_from(&source.related);
     }
 }
 
@@ -971,7 +976,7 @@ pub enum AccessConflicts {
 }
 
 impl AccessConflicts {
-    fn add(&mut self, other: &Self) {
+    pub(crate) fn add(&mut self, other: &Self) {
         match (self, other) {
             (s, AccessConflicts::All) => {
                 *s = AccessConflicts::All;
@@ -1032,6 +1037,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
             access: Access::default(),
             required: FixedBitSet::default(),
             filter_sets: vec![AccessFilters::default()],
+            related: Access::default(),
         }
     }
 
@@ -1042,6 +1048,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
             access: Access::default(),
             required: FixedBitSet::default(),
             filter_sets: Vec::new(),
+            related: Access::default(),
         }
     }
 
@@ -1076,6 +1083,24 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.access.add_resource_read(index.clone());
     }
 
+    /// Adds access to the components of entities other than the filtered one, for example the
+    /// target of a [`Relationship`](crate::relationship::Relationship).
+    ///
+    /// As the filters say nothing about these entities, this access is never considered
+    /// disjoint from another one thanks to the filters.
+    pub fn add_related_access(&mut self, related: &Access<T>) {
+        self.access.extend(related);
+        self.related.extend(related);
+    }
+
+    /// Returns the part of the access made on entities other than the filtered one.
+    ///
+    /// See [`FilteredAccess::add_related_access`].
+    #[inline]
+    pub fn related_access(&self) -> &Access<T> {
+        &self.related
+    }
+
     /// Adds exclusive access to the resource given by `index`.
     pub fn add_resource_write(&mut self, index: T) {
         self.access.add_resource_write(index.clone());
@@ -1117,6 +1142,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     /// Adds all of the accesses from `other` to `self`.
     pub fn extend_access(&mut self, other: &FilteredAccess<T>) {
         self.access.extend(&other.access);
+        self.related.extend(&other.related);
     }
 
     /// Returns `true` if this and `other` can be active at the same time.
@@ -1127,6 +1153,13 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
             return false;
         }
 
+        // Likewise, the filters say nothing about the related entities.
+        if !self.related.is_components_compatible(&other.access)
+            || !other.related.is_components_compatible(&self.access)
+        {
+            return false;
+        }
+
         if self.access.is_components_compatible(&other.access) {
             return true;
         }
@@ -1164,6 +1197,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     pub fn extend(&mut self, other: &FilteredAccess<T>) {
         self.access.extend(&other.access);
         self.required.union_with(&other.required);
+        self.related.extend(&other.related);
 
         // We can avoid allocating a new array of bitsets if `other` contains just a single set of filters:
         // in this case we can short-circuit by performing an in-place union for each bitset.
@@ -1213,6 +1247,19 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.required.is_subset(&other.required) && self.access().is_subset(other.access())
     }
 
+    /// Returns `true` if an archetype with the set of components matches the filters.
+    pub(crate) fn matches_component_set(&self, set_contains_id: &impl Fn(T) -> bool) -> bool {
+        self.filter_sets.iter().any(|set| {
+            set.with
+                .ones()
+                .all(|index| set_contains_id(T::get_sparse_set_index(index)))
+                && set
+                    .without
+                    .ones()
+                    .all(|index| !set_contains_id(T::get_sparse_set_index(index)))
+        })
+    }
+
     /// Returns the indices of the elements that this access filters for.
     pub fn with_filters(&self) -> impl Iterator<Item = T> + '_ {
         self.filter_sets
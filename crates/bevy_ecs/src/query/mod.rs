@@ -7,6 +7,7 @@ mod fetch;
 mod filter;
 mod iter;
 mod par_iter;
+mod related;
 mod state;
 mod world_query;
 
@@ -18,6 +19,7 @@ pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
 pub use par_iter::*;
+pub use related::*;
 pub use state::*;
 pub use world_query::*;
 
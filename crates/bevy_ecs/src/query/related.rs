@@ -0,0 +1,502 @@
+use crate::{
+    archetype::Archetype,
+    component::{ComponentId, Components, Tick},
+    entity::Entity,
+    query::{FilteredAccess, QueryData, QueryFilter, ReadOnlyQueryData, WorldQuery},
+    relationship::Relationship,
+    storage::{Table, TableRow},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+use bevy_platform::sync::Arc;
+use core::marker::PhantomData;
+
+/// Fetches the query data `D` of the entity targeted by the [`Relationship`] `R`, if it also
+/// matches the query filter `F`.
+///
+/// This only matches entities with `R`, and returns `None` if the target of `R` does not
+/// match `D` and `F`. Use [`RelatedMatches`] to only keep the entities whose target matches.
+///
+/// As many entities can target the same one, the data of the target can only be read.
+/// This access is not made disjoint from the access of other queries by the filters of this query,
+/// which are about the entities with `R` rather than their targets.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, query::Related};
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// fn print_parent_positions(query: Query<(&Position, Related<ChildOf, &Position>)>) {
+///     for (position, parent_position) in &query {
+///         if let Some(parent_position) = parent_position {
+///             println!("{} is {} from its parent", position.0, position.0 - parent_position.0);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_parent_positions);
+/// ```
+pub struct Related<R: Relationship, D: ReadOnlyQueryData, F: QueryFilter = ()>(
+    PhantomData<(R, D, F)>,
+);
+
+/// Filter that selects entities whose target through the [`Relationship`] `R` matches the
+/// query filter `F`.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, query::RelatedMatches};
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// #[derive(Component)]
+/// struct Boss;
+///
+/// fn boss_minions(query: Query<Entity, (With<Enemy>, RelatedMatches<ChildOf, With<Boss>>)>) {
+///     for minion in &query {
+///         println!("{minion} protects a boss");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(boss_minions);
+/// ```
+pub struct RelatedMatches<R: Relationship, F: QueryFilter>(PhantomData<(R, F)>);
+
+/// The [`WorldQuery::State`] of [`Related`] and [`RelatedMatches`].
+pub struct RelatedState<R: Relationship, D: WorldQuery, F: WorldQuery> {
+    relationship: ComponentId,
+    inner: Arc<InnerState<D, F>>,
+    _marker: PhantomData<fn() -> R>,
+}
+
+struct InnerState<D: WorldQuery, F: WorldQuery> {
+    data: D::State,
+    filter: F::State,
+    /// The access of the inner query, also used to match the archetype of the target.
+    access: FilteredAccess<ComponentId>,
+}
+
+impl<R: Relationship, D: WorldQuery, F: WorldQuery> RelatedState<R, D, F> {
+    fn new(relationship: ComponentId, data: D::State, filter: F::State) -> Self {
+        let mut access = FilteredAccess::default();
+        D::update_component_access(&data, &mut access);
+        F::update_component_access(&filter, &mut access);
+        Self {
+            relationship,
+            inner: Arc::new(InnerState {
+                data,
+                filter,
+                access,
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    fn init(world: &mut World) -> Self {
+        let relationship = world.register_component::<R>();
+        Self::new(relationship, D::init_state(world), F::init_state(world))
+    }
+
+    fn get(components: &Components) -> Option<Self> {
+        Some(Self::new(
+            components.component_id::<R>()?,
+            D::get_state(components)?,
+            F::get_state(components)?,
+        ))
+    }
+
+    fn update_component_access(&self, access: &mut FilteredAccess<ComponentId>) {
+        <&R as WorldQuery>::update_component_access(&self.relationship, access);
+        assert!(
+            access
+                .access()
+                .is_components_compatible(self.inner.access.access()),
+            "The query data of the target of {} conflicts with a previous access in this query.",
+            core::any::type_name::<R>(),
+        );
+        access.add_related_access(self.inner.access.access());
+    }
+}
+
+/// The [`WorldQuery::Fetch`] of [`Related`] and [`RelatedMatches`].
+pub struct RelatedFetch<'w, R: Relationship, D: WorldQuery, F: WorldQuery> {
+    world: UnsafeWorldCell<'w>,
+    relationship: <&'static R as WorldQuery>::Fetch<'w>,
+    data: D::Fetch<'w>,
+    filter: F::Fetch<'w>,
+    inner: Arc<InnerState<D, F>>,
+}
+
+impl<R: Relationship, D: WorldQuery, F: WorldQuery> Clone for RelatedFetch<'_, R, D, F> {
+    fn clone(&self) -> Self {
+        Self {
+            world: self.world,
+            relationship: self.relationship,
+            data: self.data.clone(),
+            filter: self.filter.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'w, R: Relationship, D: WorldQuery, F: QueryFilter> RelatedFetch<'w, R, D, F> {
+    /// # Safety
+    /// See [`WorldQuery::init_fetch`].
+    unsafe fn new(
+        world: UnsafeWorldCell<'w>,
+        state: &RelatedState<R, D, F>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self {
+        // SAFETY: The caller ensures that `world` can access the relationship, and the inner
+        // query data and filter of any entity, as declared in `update_component_access`.
+        unsafe {
+            Self {
+                world,
+                relationship: <&R as WorldQuery>::init_fetch(
+                    world,
+                    &state.relationship,
+                    last_run,
+                    this_run,
+                ),
+                data: D::init_fetch(world, &state.inner.data, last_run, this_run),
+                filter: F::init_fetch(world, &state.inner.filter, last_run, this_run),
+                inner: state.inner.clone(),
+            }
+        }
+    }
+
+    fn shrink<'wshort>(self) -> RelatedFetch<'wshort, R, D, F>
+    where
+        'w: 'wshort,
+    {
+        RelatedFetch {
+            world: self.world,
+            relationship: <&R as WorldQuery>::shrink_fetch(self.relationship),
+            data: D::shrink_fetch(self.data),
+            filter: F::shrink_fetch(self.filter),
+            inner: self.inner,
+        }
+    }
+
+    /// Prepares the inner fetches for the target of the relationship of `entity`.
+    ///
+    /// Returns the target and its table row if it matches the inner query data and filter.
+    ///
+    /// # Safety
+    /// `entity` and `table_row` must be in the range of the archetype or table the fetch was last
+    /// set to, as in [`QueryData::fetch`].
+    unsafe fn set_target(
+        &mut self,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Option<(Entity, TableRow)> {
+        // SAFETY: The caller ensures that `entity` and `table_row` are valid for the current
+        // archetype or table, which contains the relationship.
+        let relationship =
+            unsafe { <&R as QueryData>::fetch(&mut self.relationship, entity, table_row) };
+        let target = relationship.get();
+        let location = self.world.entities().get(target)?;
+        let archetype = self.world.archetypes().get(location.archetype_id)?;
+        let inner = &*self.inner;
+        let contains = |id| archetype.contains(id);
+        if !inner.access.matches_component_set(&contains)
+            || !D::matches_component_set(&inner.data, &contains)
+            || !F::matches_component_set(&inner.filter, &contains)
+        {
+            return None;
+        }
+        // SAFETY: The tables are not accessed mutably by this fetch.
+        let table = unsafe { self.world.storages() }
+            .tables
+            .get(location.table_id)?;
+        // SAFETY: `archetype` and `table` are those of the target, which matches the inner query
+        // data and filter, and their access was declared in `update_component_access`.
+        unsafe {
+            D::set_archetype(&mut self.data, &inner.data, archetype, table);
+            F::set_archetype(&mut self.filter, &inner.filter, archetype, table);
+            F::filter_fetch(&mut self.filter, target, location.table_row)
+        }
+        .then_some((target, location.table_row))
+    }
+}
+
+/// SAFETY:
+/// `update_component_access` adds a read access to `R` and the access of the inner query data and
+/// filter as related access, and panics if the latter conflicts with a previous access.
+/// `fetch` only reads `R` from the archetype or table it was set to, and the inner query data
+/// and filter from the target entity.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData, F: QueryFilter> WorldQuery for Related<R, D, F> {
+    type Fetch<'w> = RelatedFetch<'w, R, D, F>;
+    type State = RelatedState<R, D, F>;
+
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
+        fetch.shrink()
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe { RelatedFetch::new(world, state, last_run, this_run) }
+    }
+
+    const IS_DENSE: bool = <&R as WorldQuery>::IS_DENSE;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe {
+            <&R as WorldQuery>::set_archetype(
+                &mut fetch.relationship,
+                &state.relationship,
+                archetype,
+                table,
+            );
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(fetch: &mut Self::Fetch<'w>, state: &Self::State, table: &'w Table) {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe {
+            <&R as WorldQuery>::set_table(&mut fetch.relationship, &state.relationship, table);
+        }
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        state.update_component_access(access);
+    }
+
+    fn init_state(world: &mut World) -> Self::State {
+        RelatedState::init(world)
+    }
+
+    fn get_state(components: &Components) -> Option<Self::State> {
+        RelatedState::get(components)
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        set_contains_id(state.relationship)
+    }
+}
+
+/// SAFETY: `Self` is the same as `Self::ReadOnly`
+unsafe impl<R: Relationship, D: ReadOnlyQueryData, F: QueryFilter> QueryData for Related<R, D, F> {
+    const IS_READ_ONLY: bool = true;
+    type ReadOnly = Self;
+    type Item<'w> = Option<D::Item<'w>>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item.map(D::shrink)
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        // SAFETY: The invariants are upheld by the caller.
+        let (target, target_row) = unsafe { fetch.set_target(entity, table_row) }?;
+        // SAFETY: `set_target` set the inner query data to the archetype and table of the target.
+        Some(unsafe { D::fetch(&mut fetch.data, target, target_row) })
+    }
+}
+
+/// SAFETY: [`Related`] only reads `R` and the read-only query data of the target.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData, F: QueryFilter> ReadOnlyQueryData
+    for Related<R, D, F>
+{
+}
+
+/// SAFETY:
+/// `update_component_access` adds a read access to `R` and the access of the inner filter
+/// as related access, and panics if the latter conflicts with a previous access.
+/// `filter_fetch` only reads `R` from the archetype or table it was set to, and the inner filter
+/// from the target entity.
+unsafe impl<R: Relationship, F: QueryFilter> WorldQuery for RelatedMatches<R, F> {
+    type Fetch<'w> = RelatedFetch<'w, R, (), F>;
+    type State = RelatedState<R, (), F>;
+
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
+        fetch.shrink()
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe { RelatedFetch::new(world, state, last_run, this_run) }
+    }
+
+    const IS_DENSE: bool = <&R as WorldQuery>::IS_DENSE;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe {
+            <&R as WorldQuery>::set_archetype(
+                &mut fetch.relationship,
+                &state.relationship,
+                archetype,
+                table,
+            );
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(fetch: &mut Self::Fetch<'w>, state: &Self::State, table: &'w Table) {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe {
+            <&R as WorldQuery>::set_table(&mut fetch.relationship, &state.relationship, table);
+        }
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        state.update_component_access(access);
+    }
+
+    fn init_state(world: &mut World) -> Self::State {
+        RelatedState::init(world)
+    }
+
+    fn get_state(components: &Components) -> Option<Self::State> {
+        RelatedState::get(components)
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        set_contains_id(state.relationship)
+    }
+}
+
+/// SAFETY: The filter only reads `R` and the filtered components of the target.
+unsafe impl<R: Relationship, F: QueryFilter> QueryFilter for RelatedMatches<R, F> {
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe { fetch.set_target(entity, table_row) }.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        hierarchy::ChildOf,
+        prelude::*,
+        query::{Related, RelatedMatches},
+        system::RunSystemOnce,
+    };
+    use alloc::vec::Vec;
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[derive(Component)]
+    struct Boss;
+
+    #[derive(Component, PartialEq, Debug)]
+    #[component(storage = "SparseSet")]
+    struct Health(u32);
+
+    #[test]
+    fn related_query_data() {
+        let mut world = World::new();
+        let boss = world.spawn((Boss, Health(100))).id();
+        let crowd = world.spawn_empty().id();
+        let a = world.spawn((Enemy, Health(1), ChildOf(boss))).id();
+        let b = world.spawn((Enemy, ChildOf(crowd))).id();
+        world.spawn(Enemy);
+
+        let mut query = world.query::<(Entity, Related<ChildOf, (&Health, Has<Boss>)>)>();
+        let mut items = query.iter(&world).collect::<Vec<_>>();
+        items.sort_by_key(|(entity, _)| *entity == b);
+        assert_eq!(
+            items,
+            [(a, Some((&Health(100), true))), (b, None)],
+            "only the entities with a relationship match, with the data of their target"
+        );
+
+        let mut query = world.query::<Related<ChildOf, &Health, Without<Boss>>>();
+        assert!(query.iter(&world).all(|health| health.is_none()));
+    }
+
+    #[test]
+    fn related_query_filter() {
+        let mut world = World::new();
+        let boss = world.spawn(Boss).id();
+        let crowd = world.spawn_empty().id();
+        let minion = world.spawn((Enemy, ChildOf(boss))).id();
+        world.spawn((Enemy, ChildOf(crowd)));
+        world.spawn(Enemy);
+
+        let mut query =
+            world.query_filtered::<Entity, (With<Enemy>, RelatedMatches<ChildOf, With<Boss>>)>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [minion]);
+
+        // The change ticks of the target are checked.
+        let mut query = world.query_filtered::<Entity, RelatedMatches<ChildOf, Changed<Health>>>();
+        assert_eq!(query.iter(&world).count(), 0);
+        world.entity_mut(boss).insert(Health(10));
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [minion]);
+        world.clear_trackers();
+        assert_eq!(query.iter(&world).count(), 0);
+    }
+
+    #[test]
+    #[should_panic = "error[B0001]"]
+    fn related_access_ignores_filters() {
+        // The target of the first query may be matched by the second one.
+        fn sys(
+            _: Query<Related<ChildOf, &Health>, With<Enemy>>,
+            _: Query<&mut Health, Without<Enemy>>,
+        ) {
+        }
+        let mut world = World::new();
+        world.run_system_once(sys).ok();
+    }
+
+    #[test]
+    fn related_access_is_disjoint() {
+        fn disjoint(
+            _: Query<&mut Health, RelatedMatches<ChildOf, With<Boss>>>,
+            _: Query<Related<ChildOf, &Enemy>>,
+        ) {
+        }
+        let mut world = World::new();
+        world.run_system_once(disjoint).unwrap();
+    }
+
+    #[test]
+    #[should_panic = "conflicts with a previous access in this query"]
+    fn related_conflicts_with_same_query() {
+        let mut world = World::new();
+        world.query::<(&mut Health, Related<ChildOf, &Health>)>();
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
+    archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId, Archetypes},
     component::{ComponentId, Tick},
     entity::{Entity, EntityEquivalent, EntitySet, UniqueEntityArray},
     entity_disabling::DefaultQueryFilters,
@@ -187,6 +187,8 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             unsafe {
                 if state.new_archetype_internal(archetype) {
                     state.update_archetype_component_access(archetype, access);
+                } else {
+                    state.update_related_archetype_component_access(archetype, access);
                 }
             }
         }
@@ -437,6 +439,10 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     /// The cache is also updated in [`QueryState::new`], [`QueryState::get`], or any method with mutable
     /// access to `self`.
     ///
+    /// # Panics
+    ///
+    /// If the world was [compacted](World::compact) since the cache was last updated.
+    ///
     /// # Safety
     ///
     /// This does not check for mutable query correctness. To be safe, make sure mutable queries
@@ -449,9 +455,11 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         last_run: Tick,
         this_run: Tick,
     ) -> Query<'w, 's, D, F> {
+        self.validate_archetypes(world.archetypes());
         // SAFETY:
         // - The caller ensured we have the correct access to the world.
         // - The caller ensured that the world matches.
+        // - We checked that the matched archetypes and tables still exist.
         unsafe { Query::new(world, self, last_run, this_run) }
     }
 
@@ -537,6 +545,10 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     /// If `world` does not match the one used to call `QueryState::new` for this instance.
     pub fn update_archetypes_unsafe_world_cell(&mut self, world: UnsafeWorldCell) {
         self.validate_world(world.id());
+        let compactions = world.archetypes().generation().compactions;
+        if self.archetype_generation.compactions != compactions {
+            self.reset_archetypes(compactions);
+        }
         if self.component_access.required.is_empty() {
             let archetypes = world.archetypes();
             let old_generation =
@@ -573,7 +585,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             if let Some(archetypes) = potential_archetypes {
                 for archetype_id in archetypes {
                     // exclude archetypes that have already been processed
-                    if archetype_id < &self.archetype_generation.0 {
+                    if archetype_id < &self.archetype_generation.id {
                         continue;
                     }
                     // SAFETY: get_potential_archetypes only returns archetype ids that are valid for the world
@@ -610,6 +622,35 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// Panics if the world was [compacted](World::compact) since the matched archetypes were last
+    /// updated, as their ids are no longer valid.
+    #[inline]
+    #[track_caller]
+    fn validate_archetypes(&self, archetypes: &Archetypes) {
+        #[inline(never)]
+        #[track_caller]
+        #[cold]
+        fn panic_compacted() -> ! {
+            panic!("The World was compacted since this QueryState was last updated. Call `QueryState::update_archetypes` before using its `manual` methods.");
+        }
+
+        if self.archetype_generation.compactions != archetypes.generation().compactions {
+            panic_compacted();
+        }
+    }
+
+    /// Forgets the matched archetypes and tables, whose ids were reassigned by a
+    /// [compaction](World::compact) of the world, so that they are all processed again.
+    fn reset_archetypes(&mut self, compactions: u32) {
+        self.matched_tables = FixedBitSet::new();
+        self.matched_archetypes = FixedBitSet::new();
+        self.matched_storage_ids = Vec::new();
+        self.archetype_generation = ArchetypeGeneration {
+            id: ArchetypeId::EMPTY,
+            compactions,
+        };
+    }
+
     /// Update the current [`QueryState`] with information from the provided [`Archetype`]
     /// (if applicable, i.e. if the archetype has any intersecting [`ComponentId`] with the current [`QueryState`]).
     ///
@@ -627,6 +668,9 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         if matches {
             // SAFETY: The caller ensures that `archetype` is from the World the state was initialized from.
             unsafe { self.update_archetype_component_access(archetype, access) };
+        } else {
+            // SAFETY: The caller ensures that `archetype` is from the World the state was initialized from.
+            unsafe { self.update_related_archetype_component_access(archetype, access) };
         }
     }
 
@@ -639,6 +683,10 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     /// # Safety
     /// `archetype` must be from the `World` this state was initialized from.
     unsafe fn new_archetype_internal(&mut self, archetype: &Archetype) -> bool {
+        if archetype.compactions() != self.archetype_generation.compactions {
+            // Systems process every archetype again after a compaction, starting with this one.
+            self.reset_archetypes(archetype.compactions());
+        }
         if D::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
             && self.matches_component_set(&|id| archetype.contains(id))
@@ -669,15 +717,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
 
     /// Returns `true` if this query matches a set of components. Otherwise, returns `false`.
     pub fn matches_component_set(&self, set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
-        self.component_access.filter_sets.iter().any(|set| {
-            set.with
-                .ones()
-                .all(|index| set_contains_id(ComponentId::get_sparse_set_index(index)))
-                && set
-                    .without
-                    .ones()
-                    .all(|index| !set_contains_id(ComponentId::get_sparse_set_index(index)))
-        })
+        self.component_access.matches_component_set(set_contains_id)
     }
 
     /// For the given `archetype`, adds any component accessed used by this query's underlying [`FilteredAccess`] to `access`.
@@ -731,6 +771,32 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// For an [`Archetype`] not matched by this query, adds the access made on the entities related
+    /// to the matched ones, as they may belong to any archetype.
+    ///
+    /// # Safety
+    /// `archetype` must be from the `World` this state was initialized from.
+    unsafe fn update_related_archetype_component_access(
+        &mut self,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        let related = &self.component_access.related;
+        if !related.has_any_component_read() {
+            return;
+        }
+        for (component_id, archetype_component_id) in
+            archetype.components_with_archetype_component_id()
+        {
+            if related.has_component_read(component_id) {
+                access.add_component_read(archetype_component_id);
+            }
+            if related.has_component_write(component_id) {
+                access.add_component_write(archetype_component_id);
+            }
+        }
+    }
+
     /// Use this to transform a [`QueryState`] into a more generic [`QueryState`].
     /// This can be useful for passing to another function that might take the more general form.
     /// See [`Query::transmute_lens`](crate::system::Query::transmute_lens) for more details.
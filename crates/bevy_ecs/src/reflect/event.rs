@@ -0,0 +1,128 @@
+//! Definitions for [`Event`] reflection.
+//! This allows sending and triggering events whose types are known only at runtime,
+//! for example from scripts or remote tools.
+//!
+//! This module exports two types: [`ReflectEventFns`] and [`ReflectEvent`].
+//!
+//! Same as [`super::component`], but for [`Event`]s.
+
+use crate::{entity::Entity, event::Event, world::World};
+use bevy_reflect::{FromReflect, FromType, PartialReflect, TypePath, TypeRegistry};
+
+use super::from_reflect_with_fallback;
+
+/// A struct used to operate on the reflected [`Event`] trait of a type.
+///
+/// A [`ReflectEvent`] for type `T` can be obtained via
+/// [`bevy_reflect::TypeRegistration::data`].
+#[derive(Clone)]
+pub struct ReflectEvent(ReflectEventFns);
+
+/// The raw function pointers needed to make up a [`ReflectEvent`].
+///
+/// This is used when creating custom implementations of [`ReflectEvent`] with
+/// [`ReflectEvent::new()`].
+///
+/// > **Note:**
+/// > Creating custom implementations of [`ReflectEvent`] is an advanced feature that most users
+/// > will not need.
+/// > Usually a [`ReflectEvent`] is created for a type by deriving [`Reflect`](bevy_reflect::Reflect)
+/// > and adding the `#[reflect(Event)]` attribute.
+/// > After adding the event to the [`TypeRegistry`],
+/// > its [`ReflectEvent`] can then be retrieved when needed.
+#[derive(Clone)]
+pub struct ReflectEventFns {
+    /// Function pointer implementing [`ReflectEvent::send()`].
+    pub send: fn(&mut World, &dyn PartialReflect, &TypeRegistry) -> bool,
+    /// Function pointer implementing [`ReflectEvent::trigger()`].
+    pub trigger: fn(&mut World, &dyn PartialReflect, &[Entity], &TypeRegistry),
+}
+
+impl ReflectEventFns {
+    /// Get the default set of [`ReflectEventFns`] for a specific event type using its
+    /// [`FromType`] implementation.
+    ///
+    /// This is useful if you want to start with the default implementation before overriding some
+    /// of the functions to create a custom implementation.
+    pub fn new<T: Event + FromReflect + TypePath>() -> Self {
+        <ReflectEvent as FromType<T>>::from_type().0
+    }
+}
+
+impl ReflectEvent {
+    /// Sends a reflected [`Event`] like [`send_event()`](World::send_event).
+    ///
+    /// Returns `false` if the event could not be sent, because the [`Events`](crate::event::Events)
+    /// resource for this type doesn't exist.
+    pub fn send(
+        &self,
+        world: &mut World,
+        event: &dyn PartialReflect,
+        registry: &TypeRegistry,
+    ) -> bool {
+        (self.0.send)(world, event, registry)
+    }
+
+    /// Triggers a reflected [`Event`] for the given `targets` like
+    /// [`trigger_targets()`](World::trigger_targets), running any observers watching for it.
+    ///
+    /// If `targets` is empty, only global observers are run, like [`trigger()`](World::trigger).
+    pub fn trigger(
+        &self,
+        world: &mut World,
+        event: &dyn PartialReflect,
+        targets: &[Entity],
+        registry: &TypeRegistry,
+    ) {
+        (self.0.trigger)(world, event, targets, registry);
+    }
+
+    /// Create a custom implementation of [`ReflectEvent`].
+    ///
+    /// This is an advanced feature,
+    /// useful for scripting implementations,
+    /// that should not be used by most users
+    /// unless you know what you are doing.
+    ///
+    /// Usually you should derive [`Reflect`](bevy_reflect::Reflect) and add the `#[reflect(Event)]`
+    /// attribute to generate a [`ReflectEvent`] implementation automatically.
+    ///
+    /// See [`ReflectEventFns`] for more information.
+    pub fn new(fns: ReflectEventFns) -> Self {
+        Self(fns)
+    }
+
+    /// The underlying function pointers implementing methods on `ReflectEvent`.
+    ///
+    /// This is useful when you want to keep track locally of an individual
+    /// function pointer.
+    ///
+    /// Calling [`TypeRegistry::get`] followed by
+    /// [`TypeRegistration::data::<ReflectEvent>`] can be costly if done several
+    /// times per frame. Consider cloning [`ReflectEvent`] and keeping it
+    /// between frames, cloning a `ReflectEvent` is very cheap.
+    ///
+    /// If you only need a subset of the methods on `ReflectEvent`,
+    /// use `fn_pointers` to get the underlying [`ReflectEventFns`]
+    /// and copy the subset of function pointers you care about.
+    ///
+    /// [`TypeRegistration::data::<ReflectEvent>`]: bevy_reflect::TypeRegistration::data
+    pub fn fn_pointers(&self) -> &ReflectEventFns {
+        &self.0
+    }
+}
+
+impl<E: Event + FromReflect + TypePath> FromType<E> for ReflectEvent {
+    fn from_type() -> Self {
+        ReflectEvent(ReflectEventFns {
+            send: |world, reflected_event, registry| {
+                let event = from_reflect_with_fallback::<E>(reflected_event, world, registry);
+                world.send_event(event).is_some()
+            },
+            trigger: |world, reflected_event, targets, registry| {
+                let event = from_reflect_with_fallback::<E>(reflected_event, world, registry);
+                world.trigger_targets(event, targets);
+            },
+        })
+    }
+}
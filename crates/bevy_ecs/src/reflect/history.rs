@@ -0,0 +1,1161 @@
+use crate::{
+    bundle::Bundle,
+    component::{Component, ComponentCloneBehavior, ComponentInfo, Mutable},
+    entity::{Entity, EntityHashMap, EntityMapper},
+    hierarchy::Children,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    relationship::RelationshipHookMode,
+    resource::Resource,
+    system::{Commands, EntityCommands},
+    world::World,
+};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use bevy_reflect::{GetPath, PartialReflect, Reflect, TypePath, TypeRegistry};
+use core::any::TypeId;
+use log::warn;
+use thiserror::Error;
+
+/// A [`Resource`] recording reversible edits to the [`World`], for undo and redo in editors.
+///
+/// Edits are recorded by the commands of [`HistoryCommandsExt`] and [`HistoryEntityCommandsExt`],
+/// which spawn, despawn, insert, remove and modify components like their unrecorded counterparts,
+/// but also capture the previous values of the affected components through [`ReflectComponent`].
+/// Components must therefore be registered in the [`AppTypeRegistry`] with `#[reflect(Component)]`.
+///
+/// Every recorded command becomes its own undo step, unless it is recorded between
+/// [`begin_group`](Self::begin_group) and [`end_group`](Self::end_group), in which case all the
+/// edits of the group are undone and redone together.
+///
+/// Despawned entities are respawned with new [`Entity`] ids when their despawn is undone.
+/// The history keeps track of this and updates the entities referenced by its edits, including
+/// the entities stored in recorded components, so later undo and redo steps still apply to the
+/// right entities. Entities spawned as [`Children`] of a despawned entity are restored along with it.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::{AppTypeRegistry, CommandHistory, HistoryCommandsExt, HistoryEntityCommandsExt};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, PartialEq, Debug)]
+/// #[reflect(Component)]
+/// struct Position(f32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Position>();
+///
+/// let entity = world.commands().spawn_recorded(Position(0.0)).id();
+/// world.commands().entity(entity).insert_recorded(Position(1.0));
+/// world.flush();
+///
+/// CommandHistory::undo(&mut world).unwrap();
+/// assert_eq!(world.get::<Position>(entity), Some(&Position(0.0)));
+/// CommandHistory::redo(&mut world).unwrap();
+/// assert_eq!(world.get::<Position>(entity), Some(&Position(1.0)));
+/// ```
+#[derive(Resource, Default)]
+pub struct CommandHistory {
+    undo: Vec<HistoryStep>,
+    redo: Vec<HistoryStep>,
+    /// The group being recorded, and how many times it has been begun.
+    group: Option<(HistoryStep, usize)>,
+    limit: Option<usize>,
+}
+
+/// A single undo step of the [`CommandHistory`], made of one or more edits.
+pub struct HistoryStep {
+    label: Option<Cow<'static, str>>,
+    edits: Vec<Edit>,
+}
+
+impl HistoryStep {
+    /// Returns the label given to the group this step was recorded in, if any.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Returns the number of edits in this step.
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Returns `true` if this step contains no edits.
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    fn remap(&mut self, map: &EntityHashMap<Entity>) {
+        for edit in &mut self.edits {
+            edit.remap(map);
+        }
+    }
+}
+
+/// A reversible change to the [`World`].
+enum Edit {
+    /// The entity was spawned or despawned, along with its descendants.
+    Existence {
+        entity: Entity,
+        /// Whether the edit spawned the entity.
+        spawned: bool,
+        /// The entity and its descendants, captured when they were last despawned.
+        snapshot: Vec<EntitySnapshot>,
+    },
+    /// A component was inserted, replaced, modified or removed.
+    Set {
+        entity: Entity,
+        previous: Option<Box<ComponentValue>>,
+        value: Option<Box<ComponentValue>>,
+    },
+}
+
+struct EntitySnapshot {
+    entity: Entity,
+    components: Vec<ComponentValue>,
+}
+
+struct ComponentValue {
+    reflect_component: ReflectComponent,
+    type_id: TypeId,
+    value: Box<dyn Reflect>,
+}
+
+/// An error that occurs when recording, undoing or redoing an edit in the [`CommandHistory`].
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    /// The [`AppTypeRegistry`] resource is missing from the [`World`].
+    #[error("The `AppTypeRegistry` resource is required to record edits")]
+    MissingTypeRegistry,
+    /// The component type is not registered with [`ReflectComponent`] in the [`AppTypeRegistry`].
+    #[error("The component `{0}` is not registered with `#[reflect(Component)]` in the `AppTypeRegistry`")]
+    UnregisteredComponent(Cow<'static, str>),
+    /// The entity does not exist.
+    #[error("The entity {0} does not exist")]
+    EntityDoesNotExist(Entity),
+    /// The entity does not have the component.
+    #[error("The entity {entity} does not have a `{component}` component")]
+    MissingComponent {
+        /// The entity that was edited.
+        entity: Entity,
+        /// The type path of the missing component.
+        component: Cow<'static, str>,
+    },
+    /// A value could not be cloned through reflection.
+    #[error("The value of `{0}` could not be cloned through reflection")]
+    CloneFailed(Cow<'static, str>),
+    /// A field path could not be applied to a component.
+    #[error("Could not set `{path}` on `{component}`: {message}")]
+    InvalidField {
+        /// The type path of the component.
+        component: Cow<'static, str>,
+        /// The path of the field.
+        path: String,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl CommandHistory {
+    /// Creates an empty history that keeps an unlimited number of undo steps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty history that keeps at most `limit` undo steps.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the maximum number of undo steps kept, if any.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Sets the maximum number of undo steps kept, discarding the oldest steps if needed.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        self.enforce_limit();
+    }
+
+    /// Returns `true` if there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+            || self
+                .group
+                .as_ref()
+                .is_some_and(|(step, _)| !step.is_empty())
+    }
+
+    /// Returns `true` if there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Iterates over the steps that can be undone, from the most recent one.
+    pub fn undo_steps(&self) -> impl Iterator<Item = &HistoryStep> {
+        self.undo.iter().rev()
+    }
+
+    /// Iterates over the steps that can be redone, from the next one.
+    pub fn redo_steps(&self) -> impl Iterator<Item = &HistoryStep> {
+        self.redo.iter().rev()
+    }
+
+    /// Starts grouping the edits recorded from now on into a single undo step.
+    ///
+    /// Groups can be nested: the step is finished once [`end_group`](Self::end_group) has been
+    /// called as many times as `begin_group`. The label of the outermost group is kept.
+    pub fn begin_group(&mut self, label: impl Into<Cow<'static, str>>) {
+        match &mut self.group {
+            Some((_, depth)) => *depth += 1,
+            None => {
+                self.group = Some((
+                    HistoryStep {
+                        label: Some(label.into()),
+                        edits: Vec::new(),
+                    },
+                    1,
+                ));
+            }
+        }
+    }
+
+    /// Ends the group started by [`begin_group`](Self::begin_group).
+    ///
+    /// Does nothing if no group is being recorded.
+    pub fn end_group(&mut self) {
+        let Some((_, depth)) = &mut self.group else {
+            return;
+        };
+        *depth -= 1;
+        if *depth == 0 {
+            self.finish_group();
+        }
+    }
+
+    /// Returns `true` if edits are currently grouped into a single step.
+    pub fn is_grouping(&self) -> bool {
+        self.group.is_some()
+    }
+
+    /// Removes all undo and redo steps.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.group = None;
+    }
+
+    /// Undoes the most recent step, returning `false` if there was nothing to undo.
+    ///
+    /// A group that is still being recorded is finished first.
+    ///
+    /// If an edit of the step fails, the edits undone before it can be redone, while the failed edit
+    /// and the rest of the step stay in the undo stack.
+    pub fn undo(world: &mut World) -> Result<bool, HistoryError> {
+        Self::step(world, true)
+    }
+
+    /// Redoes the most recently undone step, returning `false` if there was nothing to redo.
+    ///
+    /// Failed edits are handled like in [`CommandHistory::undo`].
+    pub fn redo(world: &mut World) -> Result<bool, HistoryError> {
+        Self::step(world, false)
+    }
+
+    fn step(world: &mut World, undo: bool) -> Result<bool, HistoryError> {
+        if !world.contains_resource::<Self>() {
+            return Ok(false);
+        }
+        // Fetch the registry before taking the step, so that it isn't lost if the registry is missing.
+        let registry = type_registry(world)?;
+        let registry = registry.read();
+
+        let mut history = world.resource_mut::<Self>();
+        history.finish_group();
+        let step = if undo {
+            history.undo.pop()
+        } else {
+            history.redo.pop()
+        };
+        let Some(mut step) = step else {
+            return Ok(false);
+        };
+
+        let mut failure = None;
+        for index in 0..step.edits.len() {
+            let index = if undo {
+                step.edits.len() - 1 - index
+            } else {
+                index
+            };
+            match step.edits[index].apply(world, &registry, undo) {
+                Ok(Some(map)) => {
+                    step.remap(&map);
+                    world.resource_mut::<Self>().remap(&map);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    failure = Some((index, error));
+                    break;
+                }
+            }
+        }
+        world.flush();
+
+        let mut history = world.resource_mut::<Self>();
+        let history = &mut *history;
+        let Some((index, error)) = failure else {
+            if undo {
+                history.redo.push(step);
+            } else {
+                history.undo.push(step);
+            }
+            return Ok(true);
+        };
+
+        // Only the edits that were applied move to the opposite stack. The failed edit and the ones
+        // that weren't reached go back where the step came from, so they can be retried.
+        let (applied, remaining) = if undo {
+            let applied = step.edits.split_off(index + 1);
+            (applied, step.edits)
+        } else {
+            let remaining = step.edits.split_off(index);
+            (step.edits, remaining)
+        };
+        let (origin, opposite) = if undo {
+            (&mut history.undo, &mut history.redo)
+        } else {
+            (&mut history.redo, &mut history.undo)
+        };
+        origin.push(HistoryStep {
+            label: step.label.clone(),
+            edits: remaining,
+        });
+        if !applied.is_empty() {
+            opposite.push(HistoryStep {
+                label: step.label,
+                edits: applied,
+            });
+        }
+        Err(error)
+    }
+
+    fn record(world: &mut World, edit: Edit) {
+        let mut history = world.get_resource_or_init::<Self>();
+        history.redo.clear();
+        if let Some((group, _)) = &mut history.group {
+            group.edits.push(edit);
+        } else {
+            history.undo.push(HistoryStep {
+                label: None,
+                edits: vec![edit],
+            });
+            history.enforce_limit();
+        }
+    }
+
+    fn finish_group(&mut self) {
+        if let Some((step, _)) = self.group.take() {
+            if !step.is_empty() {
+                self.undo.push(step);
+                self.enforce_limit();
+            }
+        }
+    }
+
+    fn enforce_limit(&mut self) {
+        if let Some(limit) = self.limit {
+            let excess = self.undo.len().saturating_sub(limit);
+            self.undo.drain(..excess);
+        }
+    }
+
+    fn remap(&mut self, map: &EntityHashMap<Entity>) {
+        let group = self.group.iter_mut().map(|(step, _)| step);
+        for step in self.undo.iter_mut().chain(&mut self.redo).chain(group) {
+            step.remap(map);
+        }
+    }
+}
+
+impl Edit {
+    /// Reverts (`undo`) or reapplies the edit.
+    ///
+    /// Returns the new ids of the entities that were respawned, if any.
+    fn apply(
+        &mut self,
+        world: &mut World,
+        registry: &TypeRegistry,
+        undo: bool,
+    ) -> Result<Option<EntityHashMap<Entity>>, HistoryError> {
+        match self {
+            Edit::Existence {
+                entity,
+                spawned,
+                snapshot,
+            } => {
+                if undo == *spawned {
+                    *snapshot = despawn(world, registry, *entity)?;
+                    Ok(None)
+                } else {
+                    respawn(world, registry, snapshot).map(Some)
+                }
+            }
+            Edit::Set {
+                entity,
+                previous,
+                value,
+            } => {
+                let (target, other) = if undo {
+                    (&*previous, &*value)
+                } else {
+                    (&*value, &*previous)
+                };
+                write_component(
+                    world,
+                    registry,
+                    *entity,
+                    target.as_deref(),
+                    other.as_deref(),
+                )?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn remap(&mut self, map: &EntityHashMap<Entity>) {
+        let map_entity = |entity: &mut Entity| {
+            if let Some(&mapped) = map.get(entity) {
+                *entity = mapped;
+            }
+        };
+        match self {
+            Edit::Existence {
+                entity, snapshot, ..
+            } => {
+                map_entity(entity);
+                for entity_snapshot in snapshot {
+                    map_entity(&mut entity_snapshot.entity);
+                    for component in &mut entity_snapshot.components {
+                        component.remap(map);
+                    }
+                }
+            }
+            Edit::Set {
+                entity,
+                previous,
+                value,
+            } => {
+                map_entity(entity);
+                for component in previous.iter_mut().chain(value) {
+                    component.remap(map);
+                }
+            }
+        }
+    }
+}
+
+impl ComponentValue {
+    fn capture(
+        world: &World,
+        registry: &TypeRegistry,
+        entity: Entity,
+        type_id: TypeId,
+    ) -> Result<Option<Self>, HistoryError> {
+        let entity_ref = world
+            .get_entity(entity)
+            .map_err(|_| HistoryError::EntityDoesNotExist(entity))?;
+        let reflect_component = reflect_component(registry, type_id)?;
+        let Some(value) = reflect_component.reflect(entity_ref) else {
+            return Ok(None);
+        };
+        let value = value
+            .reflect_clone()
+            .map_err(|_| HistoryError::CloneFailed(value.reflect_type_path().to_string().into()))?;
+        Ok(Some(Self {
+            reflect_component: reflect_component.clone(),
+            type_id,
+            value,
+        }))
+    }
+
+    fn type_path(&self) -> Cow<'static, str> {
+        self.value.reflect_type_path().to_string().into()
+    }
+
+    fn remap(&mut self, map: &EntityHashMap<Entity>) {
+        let mut mapper = HistoryMapper {
+            map,
+            unmapped: false,
+        };
+        self.reflect_component
+            .map_entities(&mut *self.value, &mut mapper);
+    }
+}
+
+/// Maps the entities of respawned snapshots, noting whether a value refers to other entities.
+struct HistoryMapper<'a> {
+    map: &'a EntityHashMap<Entity>,
+    unmapped: bool,
+}
+
+impl EntityMapper for HistoryMapper<'_> {
+    fn get_mapped(&mut self, source: Entity) -> Entity {
+        self.map.get(&source).copied().unwrap_or_else(|| {
+            self.unmapped = true;
+            source
+        })
+    }
+
+    fn set_mapped(&mut self, _source: Entity, _target: Entity) {}
+}
+
+fn type_registry(world: &World) -> Result<AppTypeRegistry, HistoryError> {
+    world
+        .get_resource::<AppTypeRegistry>()
+        .cloned()
+        .ok_or(HistoryError::MissingTypeRegistry)
+}
+
+fn reflect_component(
+    registry: &TypeRegistry,
+    type_id: TypeId,
+) -> Result<&ReflectComponent, HistoryError> {
+    let registration = registry
+        .get(type_id)
+        .ok_or_else(|| HistoryError::UnregisteredComponent(format_type_id(registry, type_id)))?;
+    registration.data::<ReflectComponent>().ok_or_else(|| {
+        HistoryError::UnregisteredComponent(registration.type_info().type_path().into())
+    })
+}
+
+fn format_type_id(registry: &TypeRegistry, type_id: TypeId) -> Cow<'static, str> {
+    registry
+        .get_type_info(type_id)
+        .map(|info| Cow::Borrowed(info.type_path()))
+        .unwrap_or_else(|| alloc::format!("{type_id:?}").into())
+}
+
+/// Replaces the component of `entity` by `value`, or removes the component of type `other` if
+/// `value` is `None`.
+fn write_component(
+    world: &mut World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    value: Option<&ComponentValue>,
+    other: Option<&ComponentValue>,
+) -> Result<(), HistoryError> {
+    let mut entity_mut = world
+        .get_entity_mut(entity)
+        .map_err(|_| HistoryError::EntityDoesNotExist(entity))?;
+    match (value, other) {
+        (Some(value), _) => {
+            let mutable = entity_mut
+                .world()
+                .components()
+                .get_id(value.type_id)
+                .is_some_and(|id| {
+                    entity_mut
+                        .world()
+                        .components()
+                        .get_info(id)
+                        .is_some_and(ComponentInfo::mutable)
+                });
+            if mutable {
+                value.reflect_component.apply_or_insert_mapped(
+                    &mut entity_mut,
+                    value.value.as_partial_reflect(),
+                    registry,
+                    &mut (),
+                    RelationshipHookMode::Run,
+                );
+            } else {
+                value.reflect_component.insert(
+                    &mut entity_mut,
+                    value.value.as_partial_reflect(),
+                    registry,
+                );
+            }
+        }
+        (None, Some(other)) => other.reflect_component.remove(&mut entity_mut),
+        (None, None) => {}
+    }
+    Ok(())
+}
+
+/// Captures `entity` and its descendants, then despawns them.
+fn despawn(
+    world: &mut World,
+    registry: &TypeRegistry,
+    entity: Entity,
+) -> Result<Vec<EntitySnapshot>, HistoryError> {
+    let mut snapshot = Vec::new();
+    let mut pending = vec![entity];
+    while let Some(entity) = pending.pop() {
+        let entity_ref = world
+            .get_entity(entity)
+            .map_err(|_| HistoryError::EntityDoesNotExist(entity))?;
+        if let Some(children) = entity_ref.get::<Children>() {
+            pending.extend(children.iter());
+        }
+
+        let mut components = Vec::new();
+        for component_id in entity_ref.archetype().components() {
+            let Some(info) = world.components().get_info(component_id) else {
+                continue;
+            };
+            if *info.clone_behavior() == ComponentCloneBehavior::Ignore {
+                continue;
+            }
+            let captured = info
+                .type_id()
+                .ok_or_else(|| HistoryError::UnregisteredComponent(info.name().to_string().into()))
+                .and_then(|type_id| ComponentValue::capture(world, registry, entity, type_id));
+            match captured {
+                Ok(Some(value)) => components.push(value),
+                Ok(None) => {}
+                Err(error) => warn!(
+                    "The component `{}` of {entity} will not be restored when undoing its despawn: {error}",
+                    info.name()
+                ),
+            }
+        }
+        snapshot.push(EntitySnapshot { entity, components });
+    }
+
+    world.despawn(entity);
+    Ok(snapshot)
+}
+
+/// Spawns the entities of `snapshot` again, returning the map from their old ids to their new ids.
+fn respawn(
+    world: &mut World,
+    registry: &TypeRegistry,
+    snapshot: &[EntitySnapshot],
+) -> Result<EntityHashMap<Entity>, HistoryError> {
+    let map: EntityHashMap<Entity> = snapshot
+        .iter()
+        .map(|entity_snapshot| (entity_snapshot.entity, world.spawn_empty().id()))
+        .collect();
+
+    for entity_snapshot in snapshot {
+        for component in &entity_snapshot.components {
+            let mut value = component
+                .value
+                .reflect_clone()
+                .map_err(|_| HistoryError::CloneFailed(component.type_path()))?;
+            let mut mapper = HistoryMapper {
+                map: &map,
+                unmapped: false,
+            };
+            component
+                .reflect_component
+                .map_entities(&mut *value, &mut mapper);
+            // Relationships between the respawned entities are restored as they were, like scenes do.
+            // Relationships to other entities need their hooks to be added back to their targets.
+            let relationship_hook_mode = if mapper.unmapped {
+                RelationshipHookMode::Run
+            } else {
+                RelationshipHookMode::Skip
+            };
+            component.reflect_component.apply_or_insert_mapped(
+                &mut world.entity_mut(map[&entity_snapshot.entity]),
+                value.as_partial_reflect(),
+                registry,
+                &mut (),
+                relationship_hook_mode,
+            );
+        }
+    }
+    world.flush();
+    Ok(map)
+}
+
+/// Records a component edit made by `edit` on `entity`, capturing the component before and after.
+fn record_component_edit<C: Component>(
+    world: &mut World,
+    entity: Entity,
+    edit: impl FnOnce(&mut World) -> Result<(), HistoryError>,
+) -> Result<(), HistoryError> {
+    let registry = type_registry(world)?;
+    let registry = registry.read();
+    let type_id = TypeId::of::<C>();
+    let previous = ComponentValue::capture(world, &registry, entity, type_id)?;
+    edit(world)?;
+    world.flush();
+    let value = ComponentValue::capture(world, &registry, entity, type_id)?;
+    if previous.is_some() || value.is_some() {
+        CommandHistory::record(
+            world,
+            Edit::Set {
+                entity,
+                previous: previous.map(Box::new),
+                value: value.map(Box::new),
+            },
+        );
+    }
+    Ok(())
+}
+
+/// An extension trait for [`Commands`] to record reversible edits in the [`CommandHistory`].
+pub trait HistoryCommandsExt {
+    /// Spawns an entity with the given bundle, recording the spawn in the [`CommandHistory`].
+    ///
+    /// Undoing the spawn despawns the entity and its descendants.
+    fn spawn_recorded<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_>;
+
+    /// Undoes the most recent step of the [`CommandHistory`]. See [`CommandHistory::undo`].
+    fn undo(&mut self);
+
+    /// Redoes the most recently undone step of the [`CommandHistory`]. See [`CommandHistory::redo`].
+    fn redo(&mut self);
+
+    /// Groups the edits recorded by the following commands into a single undo step.
+    /// See [`CommandHistory::begin_group`].
+    fn begin_history_group(&mut self, label: impl Into<Cow<'static, str>>);
+
+    /// Ends the group started by [`begin_history_group`](Self::begin_history_group).
+    fn end_history_group(&mut self);
+}
+
+impl HistoryCommandsExt for Commands<'_, '_> {
+    fn spawn_recorded<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_> {
+        let entity = self.spawn(bundle).id();
+        self.queue(move |world: &mut World| {
+            CommandHistory::record(
+                world,
+                Edit::Existence {
+                    entity,
+                    spawned: true,
+                    snapshot: Vec::new(),
+                },
+            );
+        });
+        self.entity(entity)
+    }
+
+    fn undo(&mut self) {
+        self.queue(|world: &mut World| CommandHistory::undo(world).map(|_| ()));
+    }
+
+    fn redo(&mut self) {
+        self.queue(|world: &mut World| CommandHistory::redo(world).map(|_| ()));
+    }
+
+    fn begin_history_group(&mut self, label: impl Into<Cow<'static, str>>) {
+        let label = label.into();
+        self.queue(move |world: &mut World| {
+            world
+                .get_resource_or_init::<CommandHistory>()
+                .begin_group(label);
+        });
+    }
+
+    fn end_history_group(&mut self) {
+        self.queue(|world: &mut World| {
+            world.get_resource_or_init::<CommandHistory>().end_group();
+        });
+    }
+}
+
+/// An extension trait for [`EntityCommands`] to record reversible edits in the [`CommandHistory`].
+///
+/// The component types must be registered in the [`AppTypeRegistry`] with `#[reflect(Component)]`.
+pub trait HistoryEntityCommandsExt {
+    /// Inserts a component, recording the previous value (if any) in the [`CommandHistory`].
+    fn insert_recorded<C: Component + Reflect + TypePath>(&mut self, component: C) -> &mut Self;
+
+    /// Removes a component, recording its value in the [`CommandHistory`].
+    fn remove_recorded<C: Component + Reflect + TypePath>(&mut self) -> &mut Self;
+
+    /// Modifies a component with the given function, recording its value before and after
+    /// in the [`CommandHistory`].
+    fn modify_recorded<C: Component<Mutability = Mutable> + Reflect + TypePath>(
+        &mut self,
+        modify: impl FnOnce(&mut C) + Send + 'static,
+    ) -> &mut Self;
+
+    /// Sets the field of a component at the given [reflection path](bevy_reflect::GetPath),
+    /// recording the component before and after in the [`CommandHistory`].
+    fn set_field_recorded<C: Component + Reflect + TypePath>(
+        &mut self,
+        path: impl Into<String>,
+        value: impl PartialReflect,
+    ) -> &mut Self;
+
+    /// Despawns the entity and its descendants, recording their components in the
+    /// [`CommandHistory`] so they can be respawned.
+    fn despawn_recorded(&mut self);
+}
+
+impl HistoryEntityCommandsExt for EntityCommands<'_> {
+    fn insert_recorded<C: Component + Reflect + TypePath>(&mut self, component: C) -> &mut Self {
+        let entity = self.id();
+        self.commands().queue(move |world: &mut World| {
+            record_component_edit::<C>(world, entity, |world| {
+                world
+                    .get_entity_mut(entity)
+                    .map_err(|_| HistoryError::EntityDoesNotExist(entity))?
+                    .insert(component);
+                Ok(())
+            })
+        });
+        self
+    }
+
+    fn remove_recorded<C: Component + Reflect + TypePath>(&mut self) -> &mut Self {
+        let entity = self.id();
+        self.commands().queue(move |world: &mut World| {
+            record_component_edit::<C>(world, entity, |world| {
+                world
+                    .get_entity_mut(entity)
+                    .map_err(|_| HistoryError::EntityDoesNotExist(entity))?
+                    .remove::<C>();
+                Ok(())
+            })
+        });
+        self
+    }
+
+    fn modify_recorded<C: Component<Mutability = Mutable> + Reflect + TypePath>(
+        &mut self,
+        modify: impl FnOnce(&mut C) + Send + 'static,
+    ) -> &mut Self {
+        let entity = self.id();
+        self.commands().queue(move |world: &mut World| {
+            record_component_edit::<C>(world, entity, |world| {
+                let mut component = world
+                    .get_entity_mut(entity)
+                    .map_err(|_| HistoryError::EntityDoesNotExist(entity))?
+                    .into_mut::<C>()
+                    .ok_or_else(|| HistoryError::MissingComponent {
+                        entity,
+                        component: C::type_path().into(),
+                    })?;
+                modify(&mut component);
+                Ok(())
+            })
+        });
+        self
+    }
+
+    fn set_field_recorded<C: Component + Reflect + TypePath>(
+        &mut self,
+        path: impl Into<String>,
+        value: impl PartialReflect,
+    ) -> &mut Self {
+        let entity = self.id();
+        let path = path.into();
+        self.commands().queue(move |world: &mut World| {
+            let registry = type_registry(world)?;
+            record_component_edit::<C>(world, entity, |world| {
+                let registry = registry.read();
+                let mut component =
+                    ComponentValue::capture(world, &registry, entity, TypeId::of::<C>())?
+                        .ok_or_else(|| HistoryError::MissingComponent {
+                            entity,
+                            component: C::type_path().into(),
+                        })?;
+                let invalid_field = |message: String| HistoryError::InvalidField {
+                    component: C::type_path().into(),
+                    path: path.clone(),
+                    message,
+                };
+                component
+                    .value
+                    .reflect_path_mut(path.as_str())
+                    .map_err(|error| invalid_field(error.to_string()))?
+                    .try_apply(&value)
+                    .map_err(|error| invalid_field(error.to_string()))?;
+                write_component(world, &registry, entity, Some(&component), None)
+            })
+        });
+        self
+    }
+
+    fn despawn_recorded(&mut self) {
+        let entity = self.id();
+        self.commands().queue(move |world: &mut World| {
+            let registry = type_registry(world)?;
+            let snapshot = despawn(world, &registry.read(), entity)?;
+            CommandHistory::record(
+                world,
+                Edit::Existence {
+                    entity,
+                    spawned: false,
+                    snapshot,
+                },
+            );
+            Ok::<(), HistoryError>(())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hierarchy::ChildOf,
+        prelude::{Component, ReflectComponent},
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, PartialEq, Debug, Clone)]
+    #[reflect(Component)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Position>();
+            registry.register::<Name>();
+            registry.register::<ChildOf>();
+            registry.register::<Children>();
+        }
+        world
+    }
+
+    #[test]
+    fn undo_redo_component_edits() {
+        let mut world = setup();
+        let entity = world.spawn(Position { x: 0.0, y: 0.0 }).id();
+
+        world
+            .commands()
+            .entity(entity)
+            .insert_recorded(Name("a".into()))
+            .modify_recorded::<Position>(|position| position.x = 1.0)
+            .set_field_recorded::<Position>("y", 2.0f32)
+            .remove_recorded::<Name>();
+        world.flush();
+        assert_eq!(world.resource::<CommandHistory>().undo_steps().count(), 4);
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert!(world.get::<Name>(entity).is_none());
+
+        assert!(CommandHistory::undo(&mut world).unwrap());
+        assert_eq!(world.get::<Name>(entity), Some(&Name("a".into())));
+        assert!(CommandHistory::undo(&mut world).unwrap());
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(&Position { x: 1.0, y: 0.0 })
+        );
+        assert!(CommandHistory::undo(&mut world).unwrap());
+        assert!(CommandHistory::undo(&mut world).unwrap());
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(&Position { x: 0.0, y: 0.0 })
+        );
+        assert!(world.get::<Name>(entity).is_none());
+        assert!(!CommandHistory::undo(&mut world).unwrap());
+
+        for _ in 0..4 {
+            assert!(CommandHistory::redo(&mut world).unwrap());
+        }
+        assert!(!CommandHistory::redo(&mut world).unwrap());
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert!(world.get::<Name>(entity).is_none());
+
+        // Recording a new edit discards the steps that could be redone.
+        CommandHistory::undo(&mut world).unwrap();
+        world
+            .commands()
+            .entity(entity)
+            .insert_recorded(Name("b".into()));
+        world.flush();
+        assert!(!world.resource::<CommandHistory>().can_redo());
+    }
+
+    #[test]
+    fn failed_steps_are_kept() {
+        let mut world = setup();
+        let gone = world.spawn(Position { x: 0.0, y: 0.0 }).id();
+        let kept = world.spawn(Position { x: 0.0, y: 0.0 }).id();
+
+        let mut commands = world.commands();
+        commands.begin_history_group("move");
+        commands
+            .entity(gone)
+            .set_field_recorded::<Position>("x", 1.0f32);
+        commands
+            .entity(kept)
+            .set_field_recorded::<Position>("x", 1.0f32);
+        commands.end_history_group();
+        world.flush();
+
+        // A missing registry leaves the step in place.
+        let registry = world.remove_resource::<AppTypeRegistry>().unwrap();
+        assert!(matches!(
+            CommandHistory::undo(&mut world),
+            Err(HistoryError::MissingTypeRegistry)
+        ));
+        assert_eq!(world.resource::<CommandHistory>().undo_steps().count(), 1);
+        world.insert_resource(registry);
+
+        // Only the edits that were undone before the failure can be redone.
+        world.despawn(gone);
+        assert!(CommandHistory::undo(&mut world).is_err());
+        assert_eq!(
+            world.get::<Position>(kept),
+            Some(&Position { x: 0.0, y: 0.0 })
+        );
+        let history = world.resource::<CommandHistory>();
+        let undo: Vec<_> = history.undo_steps().collect();
+        let redo: Vec<_> = history.redo_steps().collect();
+        assert_eq!(
+            (undo.len(), undo[0].len(), undo[0].label()),
+            (1, 1, Some("move"))
+        );
+        assert_eq!(
+            (redo.len(), redo[0].len(), redo[0].label()),
+            (1, 1, Some("move"))
+        );
+
+        assert!(CommandHistory::redo(&mut world).unwrap());
+        assert_eq!(
+            world.get::<Position>(kept),
+            Some(&Position { x: 1.0, y: 0.0 })
+        );
+    }
+
+    #[test]
+    fn groups_are_undone_together() {
+        let mut world = setup();
+        let entity = world.spawn(Position { x: 0.0, y: 0.0 }).id();
+
+        let mut commands = world.commands();
+        commands.begin_history_group("move");
+        commands
+            .entity(entity)
+            .set_field_recorded::<Position>("x", 1.0f32)
+            .set_field_recorded::<Position>("y", 1.0f32);
+        commands.end_history_group();
+        world.flush();
+
+        let history = world.resource::<CommandHistory>();
+        let steps: Vec<_> = history.undo_steps().collect();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].label(), Some("move"));
+        assert_eq!(steps[0].len(), 2);
+
+        world.commands().undo();
+        world.flush();
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(&Position { x: 0.0, y: 0.0 })
+        );
+        world.commands().redo();
+        world.flush();
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(&Position { x: 1.0, y: 1.0 })
+        );
+    }
+
+    #[test]
+    fn spawn_and_despawn_track_respawned_entities() {
+        let mut world = setup();
+        let parent = world.spawn(Name("parent".into())).id();
+
+        let child = world
+            .commands()
+            .spawn_recorded((Name("child".into()), ChildOf(parent)))
+            .id();
+        world.flush();
+        let grandchild = world
+            .spawn((Name("grandchild".into()), ChildOf(child)))
+            .id();
+        world
+            .commands()
+            .entity(child)
+            .insert_recorded(Position { x: 1.0, y: 1.0 });
+        world.commands().entity(child).despawn_recorded();
+        world.flush();
+        assert!(world.get_entity(child).is_err());
+        assert!(world.get_entity(grandchild).is_err());
+        assert!(world.get::<Children>(parent).is_none());
+
+        // Undoing the despawn restores the child and its descendants with new ids.
+        CommandHistory::undo(&mut world).unwrap();
+        let children = world.get::<Children>(parent).unwrap();
+        assert_eq!(children.len(), 1);
+        let child = children[0];
+        assert_eq!(world.get::<Name>(child), Some(&Name("child".into())));
+        assert_eq!(
+            world.get::<Position>(child),
+            Some(&Position { x: 1.0, y: 1.0 })
+        );
+        let grandchildren = world.get::<Children>(child).unwrap();
+        assert_eq!(grandchildren.len(), 1);
+        let grandchild = grandchildren[0];
+        assert_eq!(world.get::<ChildOf>(grandchild), Some(&ChildOf(child)));
+
+        // Earlier edits apply to the respawned entity.
+        CommandHistory::undo(&mut world).unwrap();
+        assert!(world.get::<Position>(child).is_none());
+        CommandHistory::undo(&mut world).unwrap();
+        assert!(world.get_entity(child).is_err());
+        assert!(world.get_entity(grandchild).is_err());
+        assert!(world.get::<Children>(parent).is_none());
+        assert!(!world.resource::<CommandHistory>().can_undo());
+
+        // Redoing everything spawns, edits and despawns the respawned entity again.
+        CommandHistory::redo(&mut world).unwrap();
+        let child = world.get::<Children>(parent).unwrap()[0];
+        assert_eq!(world.get::<Name>(child), Some(&Name("child".into())));
+        assert_eq!(world.get::<Children>(child).unwrap().len(), 1);
+        CommandHistory::redo(&mut world).unwrap();
+        assert_eq!(
+            world.get::<Position>(child),
+            Some(&Position { x: 1.0, y: 1.0 })
+        );
+        CommandHistory::redo(&mut world).unwrap();
+        assert!(world.get_entity(child).is_err());
+        assert!(world.get::<Children>(parent).is_none());
+    }
+
+    #[test]
+    fn history_limit_and_errors() {
+        let mut world = setup();
+        world.insert_resource(CommandHistory::with_limit(2));
+        let entity = world.spawn(Position { x: 0.0, y: 0.0 }).id();
+        for x in 1..=3 {
+            world
+                .commands()
+                .entity(entity)
+                .set_field_recorded::<Position>("x", x as f32);
+        }
+        world.flush();
+        assert_eq!(world.resource::<CommandHistory>().undo_steps().count(), 2);
+
+        world.entity_mut(entity).despawn();
+        assert!(matches!(
+            CommandHistory::undo(&mut world),
+            Err(HistoryError::EntityDoesNotExist(e)) if e == entity
+        ));
+    }
+}
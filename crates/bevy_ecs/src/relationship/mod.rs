@@ -1,11 +1,13 @@
 //! This module provides functionality to link entities to each other using specialized components called "relationships". See the [`Relationship`] trait for more info.
 
+mod multi_relationship;
 mod related_methods;
 mod relationship_query;
 mod relationship_source_collection;
 
 use alloc::format;
 
+pub use multi_relationship::*;
 pub use related_methods::*;
 pub use relationship_query::*;
 pub use relationship_source_collection::*;
@@ -70,6 +72,9 @@ use log::warn;
 /// #[relationship_target(relationship = ChildOf, linked_spawn)]
 /// pub struct Children(Vec<Entity>);
 /// ```
+///
+/// Each source entity relates to a single target of a given [`Relationship`]. To relate it to many targets,
+/// use a [`MultiRelationship`] instead.
 pub trait Relationship: Component + Sized {
     /// The [`Component`] added to the "target" entities of this [`Relationship`], which contains the list of all "source"
     /// entities that relate to the "target".
@@ -294,6 +299,52 @@ pub trait RelationshipTarget: Component<Mutability = Mutable> + Sized {
     }
 }
 
+/// A [`Component`] on a "source" [`Entity`] that references one or more "target" entities:
+/// a [`Relationship`] or a [`MultiRelationship`].
+///
+/// This is used to walk relationships with [`Query::iter_ancestors`](crate::system::Query::iter_ancestors).
+pub trait TargetEntities: Component {
+    /// The iterator type for the target entities.
+    type Targets<'a>: Iterator<Item = Entity>
+    where
+        Self: 'a;
+
+    /// Iterates the target entities of this component.
+    fn targets(&self) -> Self::Targets<'_>;
+}
+
+impl<R: Relationship> TargetEntities for R {
+    type Targets<'a> = core::iter::Once<Entity>;
+
+    #[inline]
+    fn targets(&self) -> Self::Targets<'_> {
+        core::iter::once(self.get())
+    }
+}
+
+/// A [`Component`] on a "target" [`Entity`] that contains the "source" entities relating to it:
+/// a [`RelationshipTarget`] or a [`MultiRelationshipTarget`].
+///
+/// This is used to walk relationships with [`Query::iter_descendants`](crate::system::Query::iter_descendants).
+pub trait SourceEntities: Component {
+    /// The iterator type for the source entities.
+    type Sources<'a>: Iterator<Item = Entity>
+    where
+        Self: 'a;
+
+    /// Iterates the source entities of this component.
+    fn sources(&self) -> Self::Sources<'_>;
+}
+
+impl<S: RelationshipTarget> SourceEntities for S {
+    type Sources<'a> = SourceIter<'a, S>;
+
+    #[inline]
+    fn sources(&self) -> Self::Sources<'_> {
+        self.iter()
+    }
+}
+
 /// The "clone behavior" for [`RelationshipTarget`]. This actually creates an empty
 /// [`RelationshipTarget`] instance with space reserved for the number of targets in the
 /// original instance. The [`RelationshipTarget`] will then be populated with the proper components
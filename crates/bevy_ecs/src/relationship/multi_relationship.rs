@@ -0,0 +1,582 @@
+use alloc::format;
+
+use crate::{
+    component::{Component, HookContext, Mutable},
+    entity::{ComponentCloneCtx, Entity, SourceComponent},
+    error::{ignore, CommandWithEntity, HandleError},
+    relationship::{
+        RelationshipHookMode, RelationshipSourceCollection, SourceEntities, TargetEntities,
+    },
+    system::{entity_command, EntityCommands},
+    world::{DeferredWorld, EntityWorldMut},
+};
+use bevy_platform::prelude::Vec;
+use log::warn;
+
+/// A [`Component`] on a "source" [`Entity`] that references many "target" entities, creating a
+/// many-to-many relationship between them. This is the many-to-many counterpart of a
+/// [`Relationship`](crate::relationship::Relationship), for relations such as `Likes` or `Targeting`,
+/// where the same entity can relate to many targets of the same kind.
+///
+/// Every [`MultiRelationship`] has a corresponding [`MultiRelationshipTarget`], which exists on each "target" entity and
+/// contains the list of all "source" entities that relate to it. Both collections are kept consistent by component
+/// hooks: when a source or a target is despawned, it is removed from the collections of the entities it is related to.
+///
+/// As [`MultiRelationship`] components are immutable, targets are added and removed with
+/// [`EntityWorldMut::add_targets`] and [`EntityWorldMut::remove_targets`], or their [`EntityCommands`] equivalents.
+///
+/// ## Derive
+///
+/// [`MultiRelationship`] and [`MultiRelationshipTarget`] are derived like their one-to-many counterparts,
+/// with the `many` attribute. Both collections can use any [`RelationshipSourceCollection`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::MultiRelationshipTarget;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = LikedBy, many)]
+/// pub struct Likes(Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = Likes, many)]
+/// pub struct LikedBy(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// let pizza = world.spawn_empty().id();
+/// let pasta = world.spawn_empty().id();
+/// let alice = world.spawn(Likes(vec![pizza, pasta])).id();
+/// let bob = world.spawn(Likes(vec![pizza])).id();
+///
+/// assert_eq!(world.get::<LikedBy>(pizza).unwrap().collection(), &[alice, bob]);
+/// world.entity_mut(alice).remove_targets::<Likes>(&[pizza]);
+/// assert_eq!(world.get::<LikedBy>(pizza).unwrap().collection(), &[bob]);
+/// ```
+pub trait MultiRelationship: TargetEntities + Sized {
+    /// The [`Component`] added to the "target" entities of this [`MultiRelationship`], which contains the list of all
+    /// "source" entities that relate to the "target".
+    type RelationshipTarget: MultiRelationshipTarget<Relationship = Self>;
+    /// The collection type that stores the "target" entities of this [`MultiRelationship`].
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`MultiRelationship::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Creates a new [`MultiRelationship`] from the given [`MultiRelationship::Collection`].
+    ///
+    /// # Warning
+    /// The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// The `on_insert` component hook that maintains the [`MultiRelationship`] / [`MultiRelationshipTarget`] connection.
+    fn on_insert(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            caller,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip => return,
+            RelationshipHookMode::RunIfNotLinked => {
+                if <Self::RelationshipTarget as MultiRelationshipTarget>::LINKED_SPAWN {
+                    return;
+                }
+            }
+        }
+        let targets = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .collection()
+            .iter()
+            .collect::<Vec<_>>();
+        let mut invalid_targets = Vec::new();
+        for target_entity in targets {
+            if target_entity == entity {
+                warn!(
+                    "{}The {} relationship on entity {entity:?} points to itself. The invalid target has been removed.",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    core::any::type_name::<Self>(),
+                );
+                invalid_targets.push(target_entity);
+            } else if let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) {
+                if let Some(mut relationship_target) =
+                    target_entity_mut.get_mut::<Self::RelationshipTarget>()
+                {
+                    relationship_target.collection_mut_risky().add(entity);
+                } else {
+                    let mut target =
+                        <Self::RelationshipTarget as MultiRelationshipTarget>::with_capacity(1);
+                    target.collection_mut_risky().add(entity);
+                    world.commands().entity(target_entity).insert(target);
+                }
+            } else {
+                warn!(
+                    "{}The {} relationship on entity {entity:?} relates to {target_entity:?}, which does not exist. The invalid target has been removed.",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    core::any::type_name::<Self>(),
+                );
+                invalid_targets.push(target_entity);
+            }
+        }
+        if !invalid_targets.is_empty() {
+            world.commands().entity(entity).queue_handled(
+                move |mut entity: EntityWorldMut| {
+                    remove_targets::<Self>(&mut entity, &invalid_targets);
+                },
+                ignore,
+            );
+        }
+    }
+
+    /// The `on_replace` component hook that maintains the [`MultiRelationship`] / [`MultiRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_replace(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip => return,
+            RelationshipHookMode::RunIfNotLinked => {
+                if <Self::RelationshipTarget as MultiRelationshipTarget>::LINKED_SPAWN {
+                    return;
+                }
+            }
+        }
+        let targets = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .collection()
+            .iter()
+            .collect::<Vec<_>>();
+        for target_entity in targets {
+            let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) else {
+                continue;
+            };
+            let Some(mut relationship_target) =
+                target_entity_mut.get_mut::<Self::RelationshipTarget>()
+            else {
+                continue;
+            };
+            relationship_target.collection_mut_risky().remove(entity);
+            if relationship_target.is_empty() {
+                if let Ok(mut entity) = world.commands().get_entity(target_entity) {
+                    // This must check emptiness again, as the source could have been related again in the meantime.
+                    entity.queue(|mut entity: EntityWorldMut| {
+                        if entity
+                            .get::<Self::RelationshipTarget>()
+                            .is_some_and(MultiRelationshipTarget::is_empty)
+                        {
+                            entity.remove::<Self::RelationshipTarget>();
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    /// Iterates the target entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of target entities.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if there are no target entities.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated
+/// [`MultiRelationship`] type. See the [`MultiRelationship`] documentation for more information.
+pub trait MultiRelationshipTarget:
+    Component<Mutability = Mutable> + SourceEntities + Sized
+{
+    /// If this is true, when despawning or cloning (when [linked cloning is enabled](crate::entity::EntityClonerBuilder::linked_cloning)),
+    /// the related entities targeting this entity will also be despawned or cloned.
+    ///
+    /// This defaults to false when derived.
+    const LINKED_SPAWN: bool;
+    /// The [`MultiRelationship`] that populates this [`MultiRelationshipTarget`] collection.
+    type Relationship: MultiRelationship<RelationshipTarget = Self>;
+    /// The collection type that stores the "source" entities for this [`MultiRelationshipTarget`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`MultiRelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+    /// Returns a mutable reference to the stored [`MultiRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`MultiRelationshipTarget`] from the given [`MultiRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// The `on_replace` component hook that maintains the [`MultiRelationship`] / [`MultiRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_replace(mut world: DeferredWorld, HookContext { entity, caller, .. }: HookContext) {
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source_entity in relationship_target.iter() {
+            if entities.get(source_entity).is_ok() {
+                commands.queue(
+                    (move |mut source: EntityWorldMut| {
+                        remove_targets::<Self::Relationship>(&mut source, &[entity]);
+                    })
+                    .with_entity(source_entity)
+                    .handle_error_with(ignore),
+                );
+            } else {
+                warn!(
+                    "{}Tried to remove a relationship from non-existent entity {}",
+                    caller
+                        .map(|location| format!("{location}: "))
+                        .unwrap_or_default(),
+                    source_entity
+                );
+            }
+        }
+    }
+
+    /// The `on_despawn` component hook that despawns entities stored in an entity's [`MultiRelationshipTarget`] when
+    /// that entity is despawned.
+    // note: think of this as "on_drop"
+    fn on_despawn(mut world: DeferredWorld, HookContext { entity, caller, .. }: HookContext) {
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source_entity in relationship_target.iter() {
+            if entities.get(source_entity).is_ok() {
+                commands.queue(
+                    entity_command::despawn()
+                        .with_entity(source_entity)
+                        .handle_error_with(ignore),
+                );
+            } else {
+                warn!(
+                    "{}Tried to despawn non-existent entity {}",
+                    caller
+                        .map(|location| format!("{location}: "))
+                        .unwrap_or_default(),
+                    source_entity
+                );
+            }
+        }
+    }
+
+    /// Creates this [`MultiRelationshipTarget`] with the given pre-allocated entity capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        let collection =
+            <Self::Collection as RelationshipSourceCollection>::with_capacity(capacity);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of entities in this collection.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this entity collection is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// The "clone behavior" for [`MultiRelationshipTarget`], which works like [`clone_relationship_target`](super::clone_relationship_target).
+pub fn clone_multi_relationship_target<T: MultiRelationshipTarget>(
+    source: &SourceComponent,
+    context: &mut ComponentCloneCtx,
+) {
+    if let Some(component) = source.read::<T>() {
+        let mut cloned = T::with_capacity(component.len());
+        if context.linked_cloning() && T::LINKED_SPAWN {
+            let collection = cloned.collection_mut_risky();
+            for entity in component.iter() {
+                collection.add(entity);
+                context.queue_entity_clone(entity);
+            }
+        }
+        context.write_target_component(cloned);
+    }
+}
+
+/// Re-inserts the `R` relationship of an entity with the collection of targets changed by `update`,
+/// or removes it if there are no targets left.
+fn update_targets<R: MultiRelationship>(
+    entity: &mut EntityWorldMut,
+    update: impl FnOnce(&mut R::Collection),
+) {
+    let mut collection = <R::Collection as RelationshipSourceCollection>::new();
+    if let Some(relationship) = entity.get::<R>() {
+        collection.extend_from_iter(relationship.iter());
+    }
+    update(&mut collection);
+    if collection.is_empty() {
+        entity.remove::<R>();
+    } else {
+        entity.insert(R::from_collection_risky(collection));
+    }
+}
+
+fn add_targets<R: MultiRelationship>(entity: &mut EntityWorldMut, targets: &[Entity]) {
+    update_targets::<R>(entity, |collection| {
+        for target in targets {
+            if !collection.iter().any(|entity| entity == *target) {
+                collection.add(*target);
+            }
+        }
+    });
+}
+
+fn remove_targets<R: MultiRelationship>(entity: &mut EntityWorldMut, targets: &[Entity]) {
+    if entity.contains::<R>() {
+        update_targets::<R>(entity, |collection| {
+            for target in targets {
+                collection.remove(*target);
+            }
+        });
+    }
+}
+
+impl<'w> EntityWorldMut<'w> {
+    /// Relates this entity to the given `targets` with the many-to-many relationship `R`,
+    /// in addition to its current targets.
+    pub fn add_targets<R: MultiRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        add_targets::<R>(self, targets);
+        self
+    }
+
+    /// Removes the given `targets` from the many-to-many relationship `R` of this entity.
+    ///
+    /// The relationship is removed once it has no targets left.
+    pub fn remove_targets<R: MultiRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        remove_targets::<R>(self, targets);
+        self
+    }
+
+    /// Relates the given `sources` to this entity with the many-to-many relationship `R`,
+    /// in addition to their current targets.
+    pub fn add_sources<R: MultiRelationship>(&mut self, sources: &[Entity]) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for source in sources {
+                world.entity_mut(*source).add_targets::<R>(&[id]);
+            }
+        });
+        self
+    }
+
+    /// Removes this entity from the many-to-many relationship `R` of the given `sources`.
+    pub fn remove_sources<R: MultiRelationship>(&mut self, sources: &[Entity]) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for source in sources {
+                if let Ok(mut source) = world.get_entity_mut(*source) {
+                    source.remove_targets::<R>(&[id]);
+                }
+            }
+        });
+        self
+    }
+}
+
+impl<'a> EntityCommands<'a> {
+    /// Relates this entity to the given `targets` with the many-to-many relationship `R`,
+    /// in addition to its current targets.
+    pub fn add_targets<R: MultiRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        let targets: Vec<_> = targets.to_vec();
+        self.queue(move |mut entity: EntityWorldMut| {
+            add_targets::<R>(&mut entity, &targets);
+        })
+    }
+
+    /// Removes the given `targets` from the many-to-many relationship `R` of this entity.
+    ///
+    /// The relationship is removed once it has no targets left.
+    pub fn remove_targets<R: MultiRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        let targets: Vec<_> = targets.to_vec();
+        self.queue(move |mut entity: EntityWorldMut| {
+            remove_targets::<R>(&mut entity, &targets);
+        })
+    }
+
+    /// Relates the given `sources` to this entity with the many-to-many relationship `R`,
+    /// in addition to their current targets.
+    pub fn add_sources<R: MultiRelationship>(&mut self, sources: &[Entity]) -> &mut Self {
+        let sources: Vec<_> = sources.to_vec();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_sources::<R>(&sources);
+        })
+    }
+
+    /// Removes this entity from the many-to-many relationship `R` of the given `sources`.
+    pub fn remove_sources<R: MultiRelationship>(&mut self, sources: &[Entity]) -> &mut Self {
+        let sources: Vec<_> = sources.to_vec();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_sources::<R>(&sources);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::Component,
+        entity::{Entity, EntityHashSet},
+        hierarchy::ChildOf,
+        relationship::MultiRelationshipTarget,
+        system::{Query, RunSystemOnce},
+        world::World,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component)]
+    #[relationship(relationship_target = LikedBy, many)]
+    struct Likes(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = Likes, many)]
+    struct LikedBy(EntityHashSet);
+
+    fn liked_by(world: &World, entity: Entity) -> EntityHashSet {
+        world
+            .get::<LikedBy>(entity)
+            .map(|liked_by| liked_by.iter().collect())
+            .unwrap_or_default()
+    }
+
+    fn set(entities: &[Entity]) -> EntityHashSet {
+        entities.iter().copied().collect()
+    }
+
+    #[test]
+    fn multi_relationship() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn(Likes(vec![a, b])).id();
+        let d = world.spawn(Likes(vec![a])).id();
+        assert_eq!(liked_by(&world, a), set(&[c, d]));
+        assert_eq!(liked_by(&world, b), set(&[c]));
+
+        world.entity_mut(d).add_targets::<Likes>(&[b, a]);
+        assert_eq!(world.get::<Likes>(d).unwrap().0, [a, b]);
+        assert_eq!(liked_by(&world, b), set(&[c, d]));
+
+        world.entity_mut(c).remove_targets::<Likes>(&[a, b]);
+        assert!(!world.entity(c).contains::<Likes>());
+        assert_eq!(liked_by(&world, a), set(&[d]));
+
+        world.entity_mut(a).add_sources::<Likes>(&[c]);
+        assert_eq!(liked_by(&world, a), set(&[c, d]));
+        world.entity_mut(a).remove_sources::<Likes>(&[d]);
+        assert_eq!(world.get::<Likes>(d).unwrap().0, [b]);
+    }
+
+    #[test]
+    fn multi_relationship_despawn() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn(Likes(vec![a, b])).id();
+        let d = world.spawn(Likes(vec![a])).id();
+
+        // Despawning a target removes it from its sources.
+        world.despawn(a);
+        assert_eq!(world.get::<Likes>(c).unwrap().0, [b]);
+        assert!(!world.entity(d).contains::<Likes>());
+
+        // Despawning a source removes it from its targets.
+        world.despawn(c);
+        assert!(!world.entity(b).contains::<LikedBy>());
+    }
+
+    #[test]
+    fn multi_relationship_invalid_targets() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+        let b = world.spawn_empty().id();
+        world.entity_mut(b).insert(Likes(vec![a, b, missing]));
+        world.flush();
+        assert_eq!(world.get::<Likes>(b).unwrap().0, [a]);
+        assert_eq!(liked_by(&world, a), set(&[b]));
+    }
+
+    #[test]
+    fn multi_relationship_traversal() {
+        #[derive(Component)]
+        #[relationship(relationship_target = Dependents, many)]
+        struct DependsOn(Vec<Entity>);
+
+        #[derive(Component)]
+        #[relationship_target(relationship = DependsOn, many)]
+        struct Dependents(Vec<Entity>);
+
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn(DependsOn(vec![a, b])).id();
+        let d = world.spawn(DependsOn(vec![c])).id();
+
+        world
+            .run_system_once(move |depends_on: Query<&DependsOn>| {
+                assert_eq!(depends_on.iter_ancestors(d).collect::<Vec<_>>(), [c, a, b]);
+            })
+            .unwrap();
+        world
+            .run_system_once(move |dependents: Query<&Dependents>| {
+                assert_eq!(dependents.iter_descendants(a).collect::<Vec<_>>(), [c, d]);
+                assert_eq!(
+                    dependents
+                        .iter_descendants_depth_first(b)
+                        .collect::<Vec<_>>(),
+                    [c, d]
+                );
+            })
+            .unwrap();
+
+        // One-to-many relationships are still traversed the same way.
+        let root = world.spawn_empty().id();
+        let child = world.spawn(ChildOf(root)).id();
+        let grandchild = world.spawn(ChildOf(child)).id();
+        world
+            .run_system_once(move |parents: Query<&ChildOf>| {
+                assert_eq!(
+                    parents.iter_ancestors(grandchild).collect::<Vec<_>>(),
+                    [child, root]
+                );
+            })
+            .unwrap();
+    }
+}
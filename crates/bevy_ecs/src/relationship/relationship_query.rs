@@ -1,14 +1,12 @@
 use crate::{
     entity::Entity,
     query::{QueryData, QueryFilter},
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{Relationship, RelationshipTarget, SourceEntities, TargetEntities},
     system::Query,
 };
 use alloc::collections::VecDeque;
 use smallvec::SmallVec;
 
-use super::SourceIter;
-
 impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     /// If the given `entity` contains the `R` [`Relationship`] component, returns the
     /// target entity of that relationship.
@@ -21,7 +19,7 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
 
     /// If the given `entity` contains the `S` [`RelationshipTarget`] component, returns the
     /// source entities stored on that component.
-    pub fn relationship_sources<S: RelationshipTarget>(
+    pub fn relationship_sources<S: SourceEntities>(
         &'w self,
         entity: Entity,
     ) -> impl Iterator<Item = Entity> + 'w
@@ -30,7 +28,7 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     {
         self.get(entity)
             .into_iter()
-            .flat_map(RelationshipTarget::iter)
+            .flat_map(SourceEntities::sources)
     }
 
     /// Recursively walks up the tree defined by the given `R` [`Relationship`] until
@@ -57,18 +55,18 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     ///
     /// For relationship graphs that contain loops, this could loop infinitely.
     /// If your relationship is not a tree (like Bevy's hierarchy), be sure to stop if you encounter a duplicate entity.
-    pub fn iter_leaves<S: RelationshipTarget>(
+    pub fn iter_leaves<S: SourceEntities>(
         &'w self,
         entity: Entity,
     ) -> impl Iterator<Item = Entity> + 'w
     where
         <D as QueryData>::ReadOnly: QueryData<Item<'w> = &'w S>,
-        SourceIter<'w, S>: DoubleEndedIterator,
+        S::Sources<'w>: DoubleEndedIterator,
     {
         self.iter_descendants_depth_first(entity).filter(|entity| {
             self.get(*entity)
                 // These are leaf nodes if they have the `Children` component but it's empty
-                .map(|children| children.len() == 0)
+                .map(|children| children.sources().next().is_none())
                 // Or if they don't have the `Children` component at all
                 .unwrap_or(true)
         })
@@ -94,11 +92,14 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     /// Iterates all descendant entities as defined by the given `entity`'s [`RelationshipTarget`] and their recursive
     /// [`RelationshipTarget`].
     ///
+    /// This also walks [many-to-many relationships](crate::relationship::MultiRelationship), through their
+    /// [`MultiRelationshipTarget`](crate::relationship::MultiRelationshipTarget).
+    ///
     /// # Warning
     ///
     /// For relationship graphs that contain loops, this could loop infinitely.
     /// If your relationship is not a tree (like Bevy's hierarchy), be sure to stop if you encounter a duplicate entity.
-    pub fn iter_descendants<S: RelationshipTarget>(
+    pub fn iter_descendants<S: SourceEntities>(
         &'w self,
         entity: Entity,
     ) -> DescendantIter<'w, 's, D, F, S>
@@ -115,24 +116,27 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     ///
     /// For relationship graphs that contain loops, this could loop infinitely.
     /// If your relationship is not a tree (like Bevy's hierarchy), be sure to stop if you encounter a duplicate entity.
-    pub fn iter_descendants_depth_first<S: RelationshipTarget>(
+    pub fn iter_descendants_depth_first<S: SourceEntities>(
         &'w self,
         entity: Entity,
     ) -> DescendantDepthFirstIter<'w, 's, D, F, S>
     where
         D::ReadOnly: QueryData<Item<'w> = &'w S>,
-        SourceIter<'w, S>: DoubleEndedIterator,
+        S::Sources<'w>: DoubleEndedIterator,
     {
         DescendantDepthFirstIter::new(self, entity)
     }
 
     /// Iterates all ancestors of the given `entity` as defined by the `R` [`Relationship`].
     ///
+    /// This also walks [many-to-many relationships](crate::relationship::MultiRelationship), in which case
+    /// the ancestors are visited breadth-first.
+    ///
     /// # Warning
     ///
     /// For relationship graphs that contain loops, this could loop infinitely.
     /// If your relationship is not a tree (like Bevy's hierarchy), be sure to stop if you encounter a duplicate entity.
-    pub fn iter_ancestors<R: Relationship>(
+    pub fn iter_ancestors<R: TargetEntities>(
         &'w self,
         entity: Entity,
     ) -> AncestorIter<'w, 's, D, F, R>
@@ -146,7 +150,7 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
 ///
 /// Traverses the hierarchy breadth-first.
-pub struct DescendantIter<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget>
+pub struct DescendantIter<'w, 's, D: QueryData, F: QueryFilter, S: SourceEntities>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
 {
@@ -154,7 +158,7 @@ where
     vecdeque: VecDeque<Entity>,
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget> DescendantIter<'w, 's, D, F, S>
+impl<'w, 's, D: QueryData, F: QueryFilter, S: SourceEntities> DescendantIter<'w, 's, D, F, S>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
 {
@@ -165,13 +169,13 @@ where
             vecdeque: children_query
                 .get(entity)
                 .into_iter()
-                .flat_map(RelationshipTarget::iter)
+                .flat_map(SourceEntities::sources)
                 .collect(),
         }
     }
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget> Iterator
+impl<'w, 's, D: QueryData, F: QueryFilter, S: SourceEntities> Iterator
     for DescendantIter<'w, 's, D, F, S>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
@@ -182,7 +186,7 @@ where
         let entity = self.vecdeque.pop_front()?;
 
         if let Ok(children) = self.children_query.get(entity) {
-            self.vecdeque.extend(children.iter());
+            self.vecdeque.extend(children.sources());
         }
 
         Some(entity)
@@ -192,7 +196,7 @@ where
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
 ///
 /// Traverses the hierarchy depth-first.
-pub struct DescendantDepthFirstIter<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget>
+pub struct DescendantDepthFirstIter<'w, 's, D: QueryData, F: QueryFilter, S: SourceEntities>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
 {
@@ -200,11 +204,11 @@ where
     stack: SmallVec<[Entity; 8]>,
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget>
+impl<'w, 's, D: QueryData, F: QueryFilter, S: SourceEntities>
     DescendantDepthFirstIter<'w, 's, D, F, S>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
-    SourceIter<'w, S>: DoubleEndedIterator,
+    S::Sources<'w>: DoubleEndedIterator,
 {
     /// Returns a new [`DescendantDepthFirstIter`].
     pub fn new(children_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
@@ -212,16 +216,18 @@ where
             children_query,
             stack: children_query
                 .get(entity)
-                .map_or(SmallVec::new(), |children| children.iter().rev().collect()),
+                .map_or(SmallVec::new(), |children| {
+                    children.sources().rev().collect()
+                }),
         }
     }
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget> Iterator
+impl<'w, 's, D: QueryData, F: QueryFilter, S: SourceEntities> Iterator
     for DescendantDepthFirstIter<'w, 's, D, F, S>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
-    SourceIter<'w, S>: DoubleEndedIterator,
+    S::Sources<'w>: DoubleEndedIterator,
 {
     type Item = Entity;
 
@@ -229,7 +235,7 @@ where
         let entity = self.stack.pop()?;
 
         if let Ok(children) = self.children_query.get(entity) {
-            self.stack.extend(children.iter().rev());
+            self.stack.extend(children.sources().rev());
         }
 
         Some(entity)
@@ -237,28 +243,48 @@ where
 }
 
 /// An [`Iterator`] of [`Entity`]s over the ancestors of an [`Entity`].
-pub struct AncestorIter<'w, 's, D: QueryData, F: QueryFilter, R: Relationship>
+///
+/// Traverses the ancestors breadth-first, when entities have more than one target.
+pub struct AncestorIter<'w, 's, D: QueryData, F: QueryFilter, R: TargetEntities>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w R>,
 {
     parent_query: &'w Query<'w, 's, D, F>,
+    // The next ancestor is kept apart from the others, so that walking
+    // a relationship with a single target does not allocate.
     next: Option<Entity>,
+    queue: VecDeque<Entity>,
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, R: Relationship> AncestorIter<'w, 's, D, F, R>
+impl<'w, 's, D: QueryData, F: QueryFilter, R: TargetEntities> AncestorIter<'w, 's, D, F, R>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w R>,
 {
     /// Returns a new [`AncestorIter`].
     pub fn new(parent_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
-        AncestorIter {
+        let mut ancestors = AncestorIter {
             parent_query,
-            next: Some(entity),
+            next: None,
+            queue: VecDeque::new(),
+        };
+        ancestors.push_targets(entity);
+        ancestors
+    }
+
+    fn push_targets(&mut self, entity: Entity) {
+        if let Ok(parents) = self.parent_query.get(entity) {
+            for parent in parents.targets() {
+                if self.next.is_none() && self.queue.is_empty() {
+                    self.next = Some(parent);
+                } else {
+                    self.queue.push_back(parent);
+                }
+            }
         }
     }
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, R: Relationship> Iterator
+impl<'w, 's, D: QueryData, F: QueryFilter, R: TargetEntities> Iterator
     for AncestorIter<'w, 's, D, F, R>
 where
     D::ReadOnly: QueryData<Item<'w> = &'w R>,
@@ -266,7 +292,8 @@ where
     type Item = Entity;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next = self.parent_query.get(self.next?).ok().map(R::get);
-        self.next
+        let entity = self.next.take().or_else(|| self.queue.pop_front())?;
+        self.push_targets(entity);
+        Some(entity)
     }
 }
@@ -2,6 +2,8 @@
 mod multi_threaded;
 mod simple;
 mod single_threaded;
+#[cfg(feature = "std")]
+mod trace;
 
 use alloc::{borrow::Cow, vec, vec::Vec};
 use core::any::TypeId;
@@ -11,6 +13,8 @@ pub use self::{simple::SimpleExecutor, single_threaded::SingleThreadedExecutor};
 
 #[cfg(feature = "std")]
 pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
+#[cfg(feature = "std")]
+pub use self::trace::*;
 
 use fixedbitset::FixedBitSet;
 
@@ -37,6 +41,14 @@ pub(super) trait SystemExecutor: Send + Sync {
         error_handler: fn(BevyError, ErrorContext),
     );
     fn set_apply_final_deferred(&mut self, value: bool);
+    /// Takes the trace recorded during the last [`run`](Self::run), if [`ExecutionTraces`] was present.
+    #[cfg(feature = "std")]
+    fn take_trace(
+        &mut self,
+        _schedule: crate::schedule::InternedScheduleLabel,
+    ) -> Option<ScheduleTrace> {
+        None
+    }
 }
 
 /// Specifies how a [`Schedule`](super::Schedule) will be run.
@@ -87,10 +99,6 @@ pub struct SystemSchedule {
     pub(super) system_dependencies: Vec<usize>,
     /// Indexed by system node id.
     /// List of systems that immediately depend on the system.
-    #[cfg_attr(
-        not(feature = "std"),
-        expect(dead_code, reason = "currently only used with the std feature")
-    )]
     pub(super) system_dependents: Vec<Vec<usize>>,
     /// Indexed by system node id.
     /// List of sets containing the system that have conditions
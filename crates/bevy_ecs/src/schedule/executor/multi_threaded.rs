@@ -1,5 +1,5 @@
-use alloc::{boxed::Box, vec::Vec};
-use bevy_platform::sync::Arc;
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+use bevy_platform::{sync::Arc, time::Instant};
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
 use bevy_utils::syncunsafecell::SyncUnsafeCell;
 use concurrent_queue::ConcurrentQueue;
@@ -7,7 +7,10 @@ use core::{any::Any, panic::AssertUnwindSafe};
 use fixedbitset::FixedBitSet;
 #[cfg(feature = "std")]
 use std::eprintln;
-use std::sync::{Mutex, MutexGuard};
+use std::{
+    sync::{Mutex, MutexGuard},
+    thread::Thread,
+};
 
 #[cfg(feature = "trace")]
 use tracing::{info_span, Span};
@@ -15,7 +18,11 @@ use tracing::{info_span, Span};
 use crate::{
     error::{ErrorContext, ErrorHandler, Result},
     prelude::Resource,
-    schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    query::AccessConflicts,
+    schedule::{
+        is_apply_deferred, BoxedCondition, ExecutionTraces, ExecutorKind, InternedScheduleLabel,
+        ScheduleTrace, SystemConflict, SystemExecutor, SystemSchedule, SystemTrace,
+    },
     system::ScheduleSystem,
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
@@ -28,6 +35,8 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<ScheduleSystem>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    /// Whether system tasks should record a [`SystemTiming`].
+    tracing: bool,
 }
 
 struct Conditions<'a> {
@@ -42,6 +51,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         executor: &'env MultiThreadedExecutor,
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
+        tracing: bool,
     ) -> Self {
         Environment {
             executor,
@@ -53,6 +63,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
             world_cell: world.as_unsafe_world_cell(),
+            tracing,
         }
     }
 }
@@ -78,6 +89,45 @@ struct SystemTaskMetadata {
 /// The result of running a system that is sent across a channel.
 struct SystemResult {
     system_index: usize,
+    /// Only recorded when tracing.
+    timing: Option<SystemTiming>,
+}
+
+/// When and where a system ran, recorded by its task when tracing.
+struct SystemTiming {
+    start: Instant,
+    end: Instant,
+    thread: Thread,
+}
+
+impl SystemTiming {
+    fn finish(start: Instant) -> Self {
+        Self {
+            start,
+            end: Instant::now(),
+            thread: std::thread::current(),
+        }
+    }
+}
+
+/// The data recorded during a traced run of the schedule. See [`ExecutionTraces`].
+struct RunTrace {
+    start: Instant,
+    /// Indexed by system index.
+    timings: Vec<Option<SystemTiming>>,
+    /// Indexed by system index.
+    /// Running systems that conflicted with the system while it was ready.
+    blocked_by: Vec<FixedBitSet>,
+    /// Indexed by system index.
+    /// Running systems that conflicted with the conditions of the system or its sets while it was ready.
+    blocked_by_conditions: Vec<FixedBitSet>,
+}
+
+/// A [`RunTrace`] resolved against the schedule, waiting to be labeled by [`SystemExecutor::take_trace`].
+struct FinishedTrace {
+    start: Instant,
+    end: Instant,
+    systems: Vec<SystemTrace>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -91,6 +141,8 @@ pub struct MultiThreadedExecutor {
     /// When set, tells the executor that a thread has panicked.
     panic_payload: Mutex<Option<Box<dyn Any + Send>>>,
     starting_systems: FixedBitSet,
+    /// The trace of the last run, if [`ExecutionTraces`] was present.
+    finished_trace: Option<FinishedTrace>,
     /// Cached tracing span
     #[cfg(feature = "trace")]
     executor_span: Span,
@@ -124,6 +176,8 @@ pub struct ExecutorState {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// The data recorded during this run, if tracing.
+    trace: Option<RunTrace>,
 }
 
 /// References to data required by the executor.
@@ -252,6 +306,8 @@ impl SystemExecutor for MultiThreadedExecutor {
             .num_dependencies_remaining
             .clone_from(&schedule.system_dependencies);
         state.ready_systems.clone_from(&self.starting_systems);
+        let tracing = world.contains_resource::<ExecutionTraces>();
+        state.trace = tracing.then(|| RunTrace::new(schedule.systems.len()));
 
         // If stepping is enabled, make sure we skip those systems that should
         // not be run.
@@ -274,7 +330,7 @@ impl SystemExecutor for MultiThreadedExecutor {
             .map(|e| e.0.clone());
         let thread_executor = thread_executor.as_deref();
 
-        let environment = &Environment::new(self, schedule, world);
+        let environment = &Environment::new(self, schedule, world, tracing);
 
         ComputeTaskPool::get_or_init(TaskPool::default).scope_with_executor(
             false,
@@ -307,6 +363,8 @@ impl SystemExecutor for MultiThreadedExecutor {
             state.unapplied_systems.clear();
         }
 
+        self.finished_trace = state.trace.take().map(|trace| trace.finish(schedule));
+
         // check to see if there was a panic
         let payload = self.panic_payload.get_mut().unwrap();
         if let Some(payload) = payload.take() {
@@ -323,6 +381,20 @@ impl SystemExecutor for MultiThreadedExecutor {
     fn set_apply_final_deferred(&mut self, value: bool) {
         self.apply_final_deferred = value;
     }
+
+    fn take_trace(&mut self, schedule: InternedScheduleLabel) -> Option<ScheduleTrace> {
+        let FinishedTrace {
+            start,
+            end,
+            systems,
+        } = self.finished_trace.take()?;
+        Some(ScheduleTrace {
+            schedule,
+            start,
+            end,
+            systems,
+        })
+    }
 }
 
 impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
@@ -331,12 +403,16 @@ impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
         system_index: usize,
         res: Result<(), Box<dyn Any + Send>>,
         system: &ScheduleSystem,
+        timing: Option<SystemTiming>,
     ) {
         // tell the executor that the system finished
         self.environment
             .executor
             .system_completion
-            .push(SystemResult { system_index })
+            .push(SystemResult {
+                system_index,
+                timing,
+            })
             .unwrap_or_else(|error| unreachable!("{}", error));
         if let Err(payload) = res {
             #[cfg(feature = "std")]
@@ -390,6 +466,7 @@ impl MultiThreadedExecutor {
             state: Mutex::new(ExecutorState::new()),
             system_completion: ConcurrentQueue::unbounded(),
             starting_systems: FixedBitSet::new(),
+            finished_trace: None,
             apply_final_deferred: true,
             panic_payload: Mutex::new(None),
             #[cfg(feature = "trace")]
@@ -414,6 +491,7 @@ impl ExecutorState {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            trace: None,
         }
     }
 
@@ -546,6 +624,11 @@ impl ExecutorState {
                 condition.update_archetype_component_access(world);
             }
             if !self.set_condition_conflicting_systems[set_idx].is_disjoint(&self.running_systems) {
+                if let Some(trace) = &mut self.trace {
+                    trace.blocked_by_conditions[system_index].union_with(
+                        &(&self.set_condition_conflicting_systems[set_idx] & &self.running_systems),
+                    );
+                }
                 return false;
             }
         }
@@ -557,6 +640,11 @@ impl ExecutorState {
             .condition_conflicting_systems
             .is_disjoint(&self.running_systems)
         {
+            if let Some(trace) = &mut self.trace {
+                trace.blocked_by_conditions[system_index].union_with(
+                    &(&system_meta.condition_conflicting_systems & &self.running_systems),
+                );
+            }
             return false;
         }
 
@@ -566,6 +654,10 @@ impl ExecutorState {
                 .conflicting_systems
                 .is_disjoint(&self.running_systems)
             {
+                if let Some(trace) = &mut self.trace {
+                    trace.blocked_by[system_index]
+                        .union_with(&(&system_meta.conflicting_systems & &self.running_systems));
+                }
                 return false;
             }
         }
@@ -681,6 +773,7 @@ impl ExecutorState {
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
+            let start = context.environment.tracing.then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -702,7 +795,7 @@ impl ExecutorState {
                     }
                 };
             }));
-            context.system_completed(system_index, res, system);
+            context.system_completed(system_index, res, system, start.map(SystemTiming::finish));
         };
 
         if system_meta.is_send {
@@ -729,8 +822,14 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
+                let start = context.environment.tracing.then(Instant::now);
                 let res = apply_deferred(&unapplied_systems, context.environment.systems, world);
-                context.system_completed(system_index, res, system);
+                context.system_completed(
+                    system_index,
+                    res,
+                    system,
+                    start.map(SystemTiming::finish),
+                );
             };
 
             context.scope.spawn_on_scope(task);
@@ -739,6 +838,7 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
+                let start = context.environment.tracing.then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
                         (context.error_handler)(
@@ -750,7 +850,12 @@ impl ExecutorState {
                         );
                     }
                 }));
-                context.system_completed(system_index, res, system);
+                context.system_completed(
+                    system_index,
+                    res,
+                    system,
+                    start.map(SystemTiming::finish),
+                );
             };
 
             context.scope.spawn_on_scope(task);
@@ -761,7 +866,14 @@ impl ExecutorState {
     }
 
     fn finish_system_and_handle_dependents(&mut self, result: SystemResult) {
-        let SystemResult { system_index, .. } = result;
+        let SystemResult {
+            system_index,
+            timing,
+        } = result;
+
+        if let Some(trace) = &mut self.trace {
+            trace.timings[system_index] = timing;
+        }
 
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
@@ -797,6 +909,85 @@ impl ExecutorState {
     }
 }
 
+impl RunTrace {
+    fn new(sys_count: usize) -> Self {
+        Self {
+            start: Instant::now(),
+            timings: (0..sys_count).map(|_| None).collect(),
+            blocked_by: (0..sys_count)
+                .map(|_| FixedBitSet::with_capacity(sys_count))
+                .collect(),
+            blocked_by_conditions: (0..sys_count)
+                .map(|_| FixedBitSet::with_capacity(sys_count))
+                .collect(),
+        }
+    }
+
+    /// Resolves the recorded system indices into names and access conflicts.
+    fn finish(self, schedule: &SystemSchedule) -> FinishedTrace {
+        let conflict = |index: usize, other: usize, condition: bool| {
+            let other_access = schedule.systems[other].component_access_set();
+            let conflicts = if condition {
+                let mut conflicts = AccessConflicts::empty();
+                let set_conditions = schedule.sets_with_conditions_of_systems[index]
+                    .ones()
+                    .flat_map(|set_idx| &schedule.set_conditions[set_idx]);
+                for condition in schedule.system_conditions[index]
+                    .iter()
+                    .chain(set_conditions)
+                {
+                    conflicts.add(&condition.component_access_set().get_conflicts(other_access));
+                }
+                conflicts
+            } else {
+                schedule.systems[index]
+                    .component_access_set()
+                    .get_conflicts(other_access)
+            };
+            SystemConflict {
+                node: schedule.system_ids[other],
+                name: schedule.systems[other].name(),
+                condition,
+                conflicts,
+            }
+        };
+
+        let mut systems: Vec<_> = self
+            .timings
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, timing)| {
+                let SystemTiming { start, end, thread } = timing?;
+                let blocked_by = self.blocked_by[index]
+                    .ones()
+                    .map(|other| conflict(index, other, false))
+                    .chain(
+                        self.blocked_by_conditions[index]
+                            .ones()
+                            .map(|other| conflict(index, other, true)),
+                    )
+                    .collect();
+                Some(SystemTrace {
+                    node: schedule.system_ids[index],
+                    name: schedule.systems[index].name(),
+                    start,
+                    end,
+                    thread: thread.id(),
+                    thread_name: thread.name().map(ToString::to_string),
+                    blocked_by,
+                })
+            })
+            .collect();
+        systems.sort_by_key(|system| system.start);
+
+        FinishedTrace {
+            start: self.start,
+            end: Instant::now(),
+            systems,
+        }
+    }
+}
+
 fn apply_deferred(
     unapplied_systems: &FixedBitSet,
     systems: &[SyncUnsafeCell<ScheduleSystem>],
@@ -887,16 +1078,72 @@ impl MainThreadExecutor {
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
+
     use crate::{
         prelude::Resource,
-        schedule::{ExecutorKind, IntoScheduleConfigs, Schedule},
-        system::Commands,
+        query::AccessConflicts,
+        schedule::{ExecutionTraces, ExecutorKind, IntoScheduleConfigs, Schedule},
+        system::{Commands, ResMut},
         world::World,
     };
 
     #[derive(Resource)]
     struct R;
 
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    #[test]
+    fn traces_record_timings_and_conflicts() {
+        fn first(mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+        fn second(mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let counter_id = world.resource_id::<Counter>().unwrap();
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+        schedule.add_systems((first, second, (|| {}).run_if(|| false)));
+
+        // Nothing is recorded unless the resource is present.
+        schedule.run(&mut world);
+        world.init_resource::<ExecutionTraces>();
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let traces = world.resource::<ExecutionTraces>();
+        assert_eq!(traces.len(), 2);
+        let trace = traces.latest(schedule.label()).unwrap();
+        assert!(trace.start <= trace.end);
+        // The skipped system is not part of the trace.
+        assert_eq!(trace.systems.len(), 2);
+        for system in &trace.systems {
+            assert!(trace.start <= system.start && system.start <= system.end);
+            assert!(system.end <= trace.end);
+        }
+
+        // Both systems write `Counter`, so whichever starts second waits for the first.
+        let blocked: Vec<_> = trace.blocked_systems().collect();
+        assert_eq!(blocked.len(), 1);
+        let blocked = blocked[0];
+        assert_eq!(blocked.node, trace.systems[1].node);
+        assert_eq!(blocked.blocked_by.len(), 1);
+        let conflict = &blocked.blocked_by[0];
+        assert_eq!(conflict.node, trace.systems[0].node);
+        assert_eq!(conflict.name, trace.systems[0].name);
+        assert!(!conflict.condition);
+        let AccessConflicts::Individual(conflicts) = &conflict.conflicts else {
+            panic!("expected individual conflicts");
+        };
+        assert_eq!(conflicts.ones().collect::<Vec<_>>(), [counter_id.index()]);
+        assert!(trace.systems[0].end <= blocked.start);
+    }
+
     #[test]
     fn skipped_systems_notify_dependents() {
         let mut world = World::new();
@@ -0,0 +1,189 @@
+use alloc::{borrow::Cow, collections::VecDeque, string::String, vec::Vec};
+use bevy_platform::time::Instant;
+use core::time::Duration;
+use std::thread::ThreadId;
+
+use crate::{
+    prelude::Resource,
+    query::AccessConflicts,
+    schedule::{InternedScheduleLabel, NodeId, ScheduleLabel},
+};
+
+/// Collects the [`ScheduleTrace`]s recorded while running schedules.
+///
+/// Inserting this resource into the [`World`](crate::world::World) enables tracing: each run of a
+/// schedule using the [`MultiThreadedExecutor`](super::MultiThreadedExecutor) then records when
+/// and on which thread each of its systems ran, and which running systems prevented it from
+/// starting because of conflicting data access. Other executors run systems one at a time and
+/// don't record traces.
+///
+/// Only the most recent [`capacity`](Self::capacity) traces are kept, so consumers such as
+/// diagnostics should [`drain`](Self::drain) them regularly.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ExecutionTraces, ExecutorKind};
+/// let mut world = World::new();
+/// world.init_resource::<ExecutionTraces>();
+///
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+/// schedule.add_systems(|| {});
+/// schedule.run(&mut world);
+///
+/// let traces = world.resource::<ExecutionTraces>();
+/// assert_eq!(traces.len(), 1);
+/// assert_eq!(traces.iter().next().unwrap().systems.len(), 1);
+/// ```
+#[derive(Resource, Debug)]
+pub struct ExecutionTraces {
+    traces: VecDeque<ScheduleTrace>,
+    capacity: usize,
+}
+
+impl Default for ExecutionTraces {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl ExecutionTraces {
+    /// The number of traces kept by [`ExecutionTraces::default`].
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Creates an empty collection that keeps at most `capacity` traces.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            traces: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of traces kept before the oldest ones are discarded.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of traces kept, discarding the oldest traces if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.traces.len() > capacity {
+            self.traces.pop_front();
+        }
+    }
+
+    /// Adds a trace, discarding the oldest one if the collection is full.
+    pub fn push(&mut self, trace: ScheduleTrace) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.traces.len() == self.capacity {
+            self.traces.pop_front();
+        }
+        self.traces.push_back(trace);
+    }
+
+    /// Returns the number of stored traces.
+    pub fn len(&self) -> usize {
+        self.traces.len()
+    }
+
+    /// Returns `true` if no traces are stored.
+    pub fn is_empty(&self) -> bool {
+        self.traces.is_empty()
+    }
+
+    /// Iterates over the stored traces, from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &ScheduleTrace> {
+        self.traces.iter()
+    }
+
+    /// Returns the most recent trace of the schedule with the given label.
+    pub fn latest(&self, label: impl ScheduleLabel) -> Option<&ScheduleTrace> {
+        let label = label.intern();
+        self.traces
+            .iter()
+            .rev()
+            .find(|trace| trace.schedule == label)
+    }
+
+    /// Removes and returns all stored traces, from oldest to newest.
+    pub fn drain(&mut self) -> impl DoubleEndedIterator<Item = ScheduleTrace> + '_ {
+        self.traces.drain(..)
+    }
+
+    /// Removes all stored traces.
+    pub fn clear(&mut self) {
+        self.traces.clear();
+    }
+}
+
+/// A record of a single run of a schedule. See [`ExecutionTraces`].
+#[derive(Debug)]
+pub struct ScheduleTrace {
+    /// The label of the schedule that ran.
+    pub schedule: InternedScheduleLabel,
+    /// When the executor started running the schedule.
+    pub start: Instant,
+    /// When the executor finished running the schedule, including applying deferred buffers.
+    pub end: Instant,
+    /// The systems that ran, in the order they started.
+    ///
+    /// Systems skipped by their run conditions are not included.
+    pub systems: Vec<SystemTrace>,
+}
+
+impl ScheduleTrace {
+    /// Returns how long the schedule took to run.
+    pub fn duration(&self) -> Duration {
+        self.end.saturating_duration_since(self.start)
+    }
+
+    /// Iterates over the systems that had to wait for a conflicting system to finish.
+    pub fn blocked_systems(&self) -> impl Iterator<Item = &SystemTrace> {
+        self.systems
+            .iter()
+            .filter(|system| !system.blocked_by.is_empty())
+    }
+}
+
+/// A record of a single system run within a [`ScheduleTrace`].
+#[derive(Debug)]
+pub struct SystemTrace {
+    /// The id of the system in the [`ScheduleGraph`](crate::schedule::ScheduleGraph).
+    pub node: NodeId,
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+    /// When the system started running.
+    pub start: Instant,
+    /// When the system finished running.
+    pub end: Instant,
+    /// The thread the system ran on.
+    pub thread: ThreadId,
+    /// The name of the thread the system ran on, if it has one.
+    pub thread_name: Option<String>,
+    /// The running systems that prevented this system from starting once it was ready,
+    /// because their data access conflicted with it.
+    pub blocked_by: Vec<SystemConflict>,
+}
+
+impl SystemTrace {
+    /// Returns how long the system took to run.
+    pub fn duration(&self) -> Duration {
+        self.end.saturating_duration_since(self.start)
+    }
+}
+
+/// A system that blocked another system from running. See [`SystemTrace::blocked_by`].
+#[derive(Debug)]
+pub struct SystemConflict {
+    /// The id of the blocking system in the [`ScheduleGraph`](crate::schedule::ScheduleGraph).
+    pub node: NodeId,
+    /// The name of the blocking system.
+    pub name: Cow<'static, str>,
+    /// `true` if the conflict was with the run conditions of the blocked system or its sets,
+    /// rather than with the system itself.
+    pub condition: bool,
+    /// The components and resources the two accesses conflict on.
+    pub conflicts: AccessConflicts,
+}
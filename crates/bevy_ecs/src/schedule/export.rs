@@ -0,0 +1,722 @@
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_platform::collections::{HashMap, HashSet};
+use core::fmt::Write;
+use disqualified::ShortName;
+
+use crate::{
+    component::Components,
+    schedule::{is_apply_deferred, NodeId, Schedule, Schedules},
+    system::ScheduleSystem,
+};
+
+/// A snapshot of the structure of a [`Schedule`], intended for visualizing and diffing system ordering.
+///
+/// Created with [`Schedule::export_graph`] and rendered with [`to_dot`](Self::to_dot) (Graphviz).
+/// With the `serialize` feature, the export implements `serde::Serialize`, so it can also be written
+/// as JSON or any other format supported by `serde`. Nodes and edges are sorted, so the output of
+/// an unchanged schedule is stable from run to run.
+///
+/// Sync points inserted by [`AutoInsertApplyDeferredPass`](crate::schedule::passes::AutoInsertApplyDeferredPass)
+/// and detected ambiguities only exist once the schedule has been built, for example by
+/// running it or calling [`Schedule::initialize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct ScheduleGraphExport {
+    /// The debug representation of the schedule's label.
+    pub label: String,
+    /// The systems and system sets in the schedule, sorted by [`NodeId`].
+    ///
+    /// System type sets (the implicit set every system belongs to) are only included
+    /// if they take part in an ordering dependency or have run conditions.
+    pub nodes: Vec<ExportedNode>,
+    /// The edges between nodes, sorted by kind and then by [`NodeId`].
+    pub edges: Vec<ExportedEdge>,
+    /// Pairs of systems with conflicting data access and no ordering between them.
+    pub ambiguities: Vec<ExportedAmbiguity>,
+}
+
+/// A system or system set in a [`ScheduleGraphExport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct ExportedNode {
+    /// The id of the node in the [`ScheduleGraph`](crate::schedule::ScheduleGraph).
+    pub id: NodeId,
+    /// What kind of node this is.
+    pub kind: ExportedNodeKind,
+    /// The name of the system or system set.
+    pub name: String,
+    /// The names of the run conditions attached directly to this node.
+    pub conditions: Vec<String>,
+}
+
+/// The kind of an [`ExportedNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ExportedNodeKind {
+    /// A system added to the schedule.
+    System,
+    /// An [`ApplyDeferred`](crate::schedule::ApplyDeferred) system inserted by
+    /// [`AutoInsertApplyDeferredPass`](crate::schedule::passes::AutoInsertApplyDeferredPass).
+    SyncPoint,
+    /// A named system set.
+    SystemSet,
+    /// The implicit set containing every instance of a system.
+    SystemTypeSet,
+    /// A set created by configuring a tuple of systems.
+    AnonymousSet,
+}
+
+impl ExportedNodeKind {
+    /// Returns the name of this kind, as used when serializing.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ExportedNodeKind::System => "system",
+            ExportedNodeKind::SyncPoint => "sync_point",
+            ExportedNodeKind::SystemSet => "system_set",
+            ExportedNodeKind::SystemTypeSet => "system_type_set",
+            ExportedNodeKind::AnonymousSet => "anonymous_set",
+        }
+    }
+}
+
+/// A directed edge in a [`ScheduleGraphExport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct ExportedEdge {
+    /// The parent set, or the node that runs first.
+    pub from: NodeId,
+    /// The child node, or the node that runs later.
+    pub to: NodeId,
+    /// What kind of relationship the edge represents.
+    pub kind: ExportedEdgeKind,
+}
+
+/// The kind of an [`ExportedEdge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ExportedEdgeKind {
+    /// `to` is a member of the set `from`.
+    Hierarchy,
+    /// `from` runs before `to`, as configured with `before`, `after` or `chain`.
+    Dependency,
+    /// An ordering edge to or from an automatically inserted sync point.
+    SyncPoint,
+}
+
+impl ExportedEdgeKind {
+    /// Returns the name of this kind, as used when serializing.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ExportedEdgeKind::Hierarchy => "hierarchy",
+            ExportedEdgeKind::Dependency => "dependency",
+            ExportedEdgeKind::SyncPoint => "sync_point",
+        }
+    }
+}
+
+/// A pair of systems in a [`ScheduleGraphExport`] whose execution order is ambiguous.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct ExportedAmbiguity {
+    /// The first system.
+    pub first: NodeId,
+    /// The second system.
+    pub second: NodeId,
+    /// The names of the components and resources both systems access, at least one of them mutably.
+    ///
+    /// This is empty if the systems conflict on world access as a whole.
+    pub conflicts: Vec<String>,
+}
+
+impl Schedule {
+    /// Captures the systems, system sets, ordering, run conditions and ambiguities of this schedule
+    /// as a [`ScheduleGraphExport`].
+    ///
+    /// `components` is used to name the data that ambiguous systems conflict on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::schedule::ExportedNodeKind;
+    /// fn spawn(mut commands: Commands) {}
+    /// fn count(query: Query<Entity>) {}
+    ///
+    /// let mut world = World::new();
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems((spawn, count).chain());
+    /// schedule.initialize(&mut world).unwrap();
+    ///
+    /// let export = schedule.export_graph(world.components());
+    /// assert!(export.to_dot().contains("spawn"));
+    /// assert!(export
+    ///     .nodes
+    ///     .iter()
+    ///     .any(|node| node.kind == ExportedNodeKind::SyncPoint));
+    /// ```
+    pub fn export_graph(&self, components: &Components) -> ScheduleGraphExport {
+        let graph = self.graph();
+        let executable = self.executable();
+        let use_shortnames = self.get_build_settings().use_shortnames;
+        let shorten = |name: Cow<'_, str>| {
+            if use_shortnames {
+                ShortName(&name).to_string()
+            } else {
+                name.into_owned()
+            }
+        };
+
+        // Systems and their conditions are moved into the executable schedule once it is built.
+        let executable_systems: HashMap<NodeId, usize> = executable
+            .system_ids
+            .iter()
+            .enumerate()
+            .map(|(index, &id)| (id, index))
+            .collect();
+        let system_at = |id: NodeId| -> Option<(&ScheduleSystem, &[_])> {
+            match executable_systems.get(&id) {
+                Some(&index) => Some((
+                    &executable.systems[index],
+                    executable.system_conditions[index].as_slice(),
+                )),
+                None => graph
+                    .get_system_at(id)
+                    .map(|system| (system, graph.system_conditions[id.index()].as_slice())),
+            }
+        };
+        let executable_set_conditions: HashMap<NodeId, usize> = executable
+            .set_ids
+            .iter()
+            .enumerate()
+            .map(|(index, &id)| (id, index))
+            .collect();
+
+        let dependency = graph.dependency().graph();
+        let hierarchy = graph.hierarchy().graph();
+
+        let mut nodes = Vec::new();
+        let mut names = HashMap::<NodeId, String>::default();
+        for index in 0..graph.systems.len() {
+            let id = NodeId::System(index);
+            let Some((system, conditions)) = system_at(id) else {
+                continue;
+            };
+            // Auto sync points are added directly to the flattened dependency graph.
+            let kind = if is_apply_deferred(system) && !dependency.contains_node(id) {
+                ExportedNodeKind::SyncPoint
+            } else {
+                ExportedNodeKind::System
+            };
+            let name = shorten(system.name());
+            names.insert(id, name.clone());
+            nodes.push(ExportedNode {
+                id,
+                kind,
+                name,
+                conditions: conditions
+                    .iter()
+                    .map(|condition| shorten(condition.name()))
+                    .collect(),
+            });
+        }
+
+        let mut sets: Vec<_> = graph.system_sets().collect();
+        sets.sort_by_key(|(id, ..)| *id);
+        for (id, set, conditions) in sets {
+            let conditions = match executable_set_conditions.get(&id) {
+                Some(&index) => executable.set_conditions[index].as_slice(),
+                None => conditions,
+            };
+            let kind = if set.is_anonymous() {
+                ExportedNodeKind::AnonymousSet
+            } else if set.system_type().is_some() {
+                ExportedNodeKind::SystemTypeSet
+            } else {
+                ExportedNodeKind::SystemSet
+            };
+            if kind == ExportedNodeKind::SystemTypeSet
+                && conditions.is_empty()
+                && dependency.edges(id).next().is_none()
+            {
+                continue;
+            }
+            let name = if kind == ExportedNodeKind::AnonymousSet {
+                let members: Vec<_> = hierarchy
+                    .neighbors_directed(id, crate::schedule::Direction::Outgoing)
+                    .filter_map(|member| names.get(&member).map(String::as_str))
+                    .collect();
+                format!("({})", members.join(", "))
+            } else {
+                shorten(format!("{set:?}").into())
+            };
+            names.insert(id, name.clone());
+            nodes.push(ExportedNode {
+                id,
+                kind,
+                name,
+                conditions: conditions
+                    .iter()
+                    .map(|condition| shorten(condition.name()))
+                    .collect(),
+            });
+        }
+
+        let included = |id: NodeId| names.contains_key(&id);
+        let mut edges: Vec<ExportedEdge> = hierarchy
+            .all_edges()
+            .filter(|&(from, to)| included(from) && included(to))
+            .map(|(from, to)| ExportedEdge {
+                from,
+                to,
+                kind: ExportedEdgeKind::Hierarchy,
+            })
+            .chain(
+                dependency
+                    .all_edges()
+                    .filter(|&(from, to)| included(from) && included(to))
+                    .map(|(from, to)| ExportedEdge {
+                        from,
+                        to,
+                        kind: ExportedEdgeKind::Dependency,
+                    }),
+            )
+            .collect();
+
+        let sync_points: HashSet<NodeId> = nodes
+            .iter()
+            .filter(|node| node.kind == ExportedNodeKind::SyncPoint)
+            .map(|node| node.id)
+            .collect();
+        for (index, dependents) in executable.system_dependents.iter().enumerate() {
+            let from = executable.system_ids[index];
+            for &dependent in dependents {
+                let to = executable.system_ids[dependent];
+                if sync_points.contains(&from) || sync_points.contains(&to) {
+                    edges.push(ExportedEdge {
+                        from,
+                        to,
+                        kind: ExportedEdgeKind::SyncPoint,
+                    });
+                }
+            }
+        }
+        edges.sort_by_key(|edge| (edge.kind, edge.from, edge.to));
+
+        let mut ambiguities: Vec<_> = graph
+            .conflicting_systems()
+            .iter()
+            .map(|(a, b, conflicts)| ExportedAmbiguity {
+                first: *a.min(b),
+                second: *a.max(b),
+                conflicts: conflicts
+                    .iter()
+                    .map(|&id| {
+                        components
+                            .get_name(id)
+                            .map(&shorten)
+                            .unwrap_or_else(|| format!("{id:?}"))
+                    })
+                    .collect(),
+            })
+            .collect();
+        ambiguities.sort_by_key(|ambiguity| (ambiguity.first, ambiguity.second));
+
+        ScheduleGraphExport {
+            label: format!("{:?}", self.label()),
+            nodes,
+            edges,
+            ambiguities,
+        }
+    }
+}
+
+impl Schedules {
+    /// Captures every schedule as a [`ScheduleGraphExport`], sorted by label.
+    ///
+    /// See [`Schedule::export_graph`] for details.
+    pub fn export_graphs(&self, components: &Components) -> Vec<ScheduleGraphExport> {
+        let mut exports: Vec<_> = self
+            .iter()
+            .map(|(_, schedule)| schedule.export_graph(components))
+            .collect();
+        exports.sort_by(|a, b| a.label.cmp(&b.label));
+        exports
+    }
+
+    /// Renders every schedule as a single Graphviz DOT graph, with one cluster per schedule.
+    pub fn export_dot(&self, components: &Components) -> String {
+        let mut out = String::from("digraph schedules {\n    compound=true;\n    rankdir=LR;\n");
+        for export in self.export_graphs(components) {
+            writeln!(
+                out,
+                "    subgraph {} {{\n        label={};",
+                quote(&format!("cluster_{}", export.label)),
+                quote(&export.label)
+            )
+            .unwrap();
+            export.write_dot_body(&mut out, &format!("{}/", export.label), "        ");
+            out.push_str("    }\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl ScheduleGraphExport {
+    /// Returns the node with the given id, if it is part of the export.
+    pub fn node(&self, id: NodeId) -> Option<&ExportedNode> {
+        self.nodes
+            .binary_search_by_key(&id, |node| node.id)
+            .ok()
+            .map(|index| &self.nodes[index])
+    }
+
+    /// Renders the schedule as a Graphviz DOT graph.
+    ///
+    /// Systems are drawn as boxes and sets as ellipses, with their run conditions listed below
+    /// their name. Set membership is drawn as dashed edges, ordering as solid edges, automatically
+    /// inserted sync points as diamonds and ambiguities as undirected red edges labeled with the
+    /// conflicting data.
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph {} {{\n    rankdir=LR;\n", quote(&self.label));
+        self.write_dot_body(&mut out, "", "    ");
+        out.push_str("}\n");
+        out
+    }
+
+    fn write_dot_body(&self, out: &mut String, prefix: &str, indent: &str) {
+        let node_name = |id: NodeId| quote(&format!("{prefix}{}", node_key(id)));
+        for node in &self.nodes {
+            let mut label = node.name.clone();
+            for condition in &node.conditions {
+                write!(label, "\nrun_if: {condition}").unwrap();
+            }
+            let style = match node.kind {
+                ExportedNodeKind::System => "shape=box",
+                ExportedNodeKind::SyncPoint => "shape=diamond, style=filled, fillcolor=lightgrey",
+                ExportedNodeKind::SystemSet => "shape=ellipse",
+                ExportedNodeKind::SystemTypeSet | ExportedNodeKind::AnonymousSet => {
+                    "shape=ellipse, style=dashed"
+                }
+            };
+            writeln!(
+                out,
+                "{indent}{} [label={}, {style}];",
+                node_name(node.id),
+                quote(&label)
+            )
+            .unwrap();
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                ExportedEdgeKind::Hierarchy => "style=dashed, arrowhead=none",
+                ExportedEdgeKind::Dependency => "style=solid",
+                ExportedEdgeKind::SyncPoint => "style=dotted",
+            };
+            writeln!(
+                out,
+                "{indent}{} -> {} [{style}];",
+                node_name(edge.from),
+                node_name(edge.to)
+            )
+            .unwrap();
+        }
+        for ambiguity in &self.ambiguities {
+            writeln!(
+                out,
+                "{indent}{} -> {} [dir=none, color=red, constraint=false, label={}];",
+                node_name(ambiguity.first),
+                node_name(ambiguity.second),
+                quote(&ambiguity.conflicts.join(", "))
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn node_key(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system:{index}"),
+        NodeId::Set(index) => format!("set:{index}"),
+    }
+}
+
+/// Quotes and escapes a string as a DOT ID.
+///
+/// Line feeds become `\n` line breaks in labels. Other control characters, such as `\r` which
+/// Graphviz would read as a right-justified line break, are replaced with spaces.
+fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::{ScheduleBuildSettings, ScheduleLabel},
+    };
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Physics;
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Other;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+    fn count(query: Query<Entity>, mut counter: ResMut<Counter>) {
+        counter.0 = query.iter().len() as u32;
+    }
+    fn reset(mut counter: ResMut<Counter>) {
+        counter.0 = 0;
+    }
+    fn enabled() -> bool {
+        true
+    }
+
+    fn node_named<'a>(export: &'a ScheduleGraphExport, name: &str) -> &'a ExportedNode {
+        export
+            .nodes
+            .iter()
+            .find(|node| node.name == name)
+            .unwrap_or_else(|| panic!("no node named {name}"))
+    }
+
+    #[test]
+    fn export_contains_sets_edges_conditions_and_sync_points() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.configure_sets(Physics.run_if(enabled));
+        schedule.add_systems((spawn, count).chain().in_set(Physics));
+        schedule.initialize(&mut world).unwrap();
+
+        let export = schedule.export_graph(world.components());
+        let spawn = node_named(&export, "spawn");
+        let count = node_named(&export, "count");
+        let physics = node_named(&export, "Physics");
+        assert_eq!(spawn.kind, ExportedNodeKind::System);
+        assert_eq!(physics.kind, ExportedNodeKind::SystemSet);
+        assert_eq!(physics.conditions, vec![String::from("enabled")]);
+
+        assert!(export.edges.contains(&ExportedEdge {
+            from: spawn.id,
+            to: count.id,
+            kind: ExportedEdgeKind::Dependency,
+        }));
+        assert!(export
+            .edges
+            .iter()
+            .any(|edge| edge.kind == ExportedEdgeKind::Hierarchy && edge.from == physics.id));
+
+        let sync_points: Vec<_> = export
+            .nodes
+            .iter()
+            .filter(|node| node.kind == ExportedNodeKind::SyncPoint)
+            .collect();
+        assert_eq!(sync_points.len(), 1);
+        let sync = sync_points[0].id;
+        assert!(export.edges.contains(&ExportedEdge {
+            from: spawn.id,
+            to: sync,
+            kind: ExportedEdgeKind::SyncPoint,
+        }));
+        assert!(export.edges.contains(&ExportedEdge {
+            from: sync,
+            to: count.id,
+            kind: ExportedEdgeKind::SyncPoint,
+        }));
+        assert_eq!(export.node(sync).unwrap().name, "apply_deferred");
+    }
+
+    #[test]
+    fn export_reports_ambiguities() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            auto_insert_apply_deferred: false,
+            ..Default::default()
+        });
+        schedule.add_systems((count, reset));
+        schedule.initialize(&mut world).unwrap();
+
+        let export = schedule.export_graph(world.components());
+        assert_eq!(export.ambiguities.len(), 1);
+        let ambiguity = &export.ambiguities[0];
+        assert_eq!(ambiguity.conflicts, vec![String::from("Counter")]);
+        let mut names = [
+            export.node(ambiguity.first).unwrap().name.as_str(),
+            export.node(ambiguity.second).unwrap().name.as_str(),
+        ];
+        names.sort();
+        assert_eq!(names, ["count", "reset"]);
+        assert!(export.to_dot().contains("color=red"));
+    }
+
+    #[test]
+    fn export_output_is_stable_and_escaped() {
+        let build = || {
+            let mut world = World::new();
+            world.init_resource::<Counter>();
+            let mut schedule = Schedule::default();
+            schedule.add_systems((spawn, count.run_if(enabled), reset).chain());
+            schedule.initialize(&mut world).unwrap();
+            schedule.export_graph(world.components())
+        };
+        let export = build();
+        assert_eq!(export, build());
+
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph \"DefaultSchedule\" {"));
+        assert!(dot.contains("[label=\"count\\nrun_if: enabled\", shape=box];"));
+        assert_eq!(quote("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+        assert_eq!(quote("a\rb\tc\u{1}"), "\"a b c \"");
+    }
+
+    #[test]
+    fn export_all_schedules() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_schedule(Schedule::new(Other));
+        world.schedule_scope(Other, |_, schedule| {
+            schedule.add_systems(reset);
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(count);
+        world.add_schedule(schedule);
+
+        let schedules = world.resource::<Schedules>();
+        let exports = schedules.export_graphs(world.components());
+        let labels: Vec<_> = exports.iter().map(|export| export.label.as_str()).collect();
+        assert_eq!(labels, ["DefaultSchedule", "Other"]);
+
+        let dot = schedules.export_dot(world.components());
+        assert!(dot.contains("subgraph \"cluster_Other\""));
+        assert!(dot.contains("\"Other/system:0\" [label=\"reset\""));
+    }
+}
+
+#[cfg(all(test, feature = "serialize"))]
+mod serde_tests {
+    use alloc::{string::String, vec};
+
+    use super::*;
+
+    use serde_test::{assert_ser_tokens, Token};
+
+    #[test]
+    fn serialize_export() {
+        let export = ScheduleGraphExport {
+            label: String::from("Update"),
+            nodes: vec![ExportedNode {
+                id: NodeId::System(0),
+                kind: ExportedNodeKind::SyncPoint,
+                name: String::from("apply_deferred"),
+                conditions: vec![],
+            }],
+            edges: vec![ExportedEdge {
+                from: NodeId::Set(1),
+                to: NodeId::System(0),
+                kind: ExportedEdgeKind::Hierarchy,
+            }],
+            ambiguities: vec![],
+        };
+        assert_ser_tokens(
+            &export,
+            &[
+                Token::Struct {
+                    name: "ScheduleGraphExport",
+                    len: 4,
+                },
+                Token::Str("label"),
+                Token::Str("Update"),
+                Token::Str("nodes"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "ExportedNode",
+                    len: 4,
+                },
+                Token::Str("id"),
+                Token::NewtypeVariant {
+                    name: "NodeId",
+                    variant: "System",
+                },
+                Token::U64(0),
+                Token::Str("kind"),
+                Token::UnitVariant {
+                    name: "ExportedNodeKind",
+                    variant: "sync_point",
+                },
+                Token::Str("name"),
+                Token::Str("apply_deferred"),
+                Token::Str("conditions"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("edges"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "ExportedEdge",
+                    len: 3,
+                },
+                Token::Str("from"),
+                Token::NewtypeVariant {
+                    name: "NodeId",
+                    variant: "Set",
+                },
+                Token::U64(1),
+                Token::Str("to"),
+                Token::NewtypeVariant {
+                    name: "NodeId",
+                    variant: "System",
+                },
+                Token::U64(0),
+                Token::Str("kind"),
+                Token::UnitVariant {
+                    name: "ExportedEdgeKind",
+                    variant: "hierarchy",
+                },
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("ambiguities"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -4,6 +4,7 @@ use core::fmt::Debug;
 ///
 /// [`ScheduleGraph`]: crate::schedule::ScheduleGraph
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
 pub enum NodeId {
     /// Identifier for a system.
     System(usize),
@@ -497,6 +497,13 @@ impl Schedule {
                 error_handler,
             );
         }
+
+        #[cfg(feature = "std")]
+        if let Some(trace) = self.executor.take_trace(self.label) {
+            if let Some(mut traces) = world.get_resource_mut::<ExecutionTraces>() {
+                traces.push(trace);
+            }
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
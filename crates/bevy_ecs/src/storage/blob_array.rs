@@ -204,6 +204,19 @@ impl BlobArray {
         }
     }
 
+    /// Drops all elements and frees the memory of this [`BlobArray`]. Unlike [`drop`](Self::drop), the
+    /// array stays usable afterwards, with a capacity of 0.
+    ///
+    /// # Safety
+    /// - `cap` and `len` are indeed the capacity and length of this [`BlobArray`]
+    /// - The caller must treat the array as having a capacity of 0 afterwards, and allocate it again
+    ///   with [`alloc`](Self::alloc) before storing elements.
+    pub unsafe fn dealloc(&mut self, cap: usize, len: usize) {
+        self.drop(cap, len);
+        let align = NonZeroUsize::new(self.item_layout.align()).expect("alignment must be > 0");
+        self.data = bevy_ptr::dangling_with_align(align);
+    }
+
     /// Drops the last element in this [`BlobArray`].
     ///
     /// # Safety
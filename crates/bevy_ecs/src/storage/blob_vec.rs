@@ -1,3 +1,4 @@
+use crate::storage::MemoryUsage;
 use alloc::alloc::handle_alloc_error;
 use bevy_ptr::{OwningPtr, Ptr, PtrMut};
 use bevy_utils::OnDrop;
@@ -8,7 +9,7 @@ use core::{alloc::Layout, cell::UnsafeCell, num::NonZero, ptr::NonNull};
 /// Used to densely store homogeneous ECS data. A blob is usually just an arbitrary block of contiguous memory without any identity, and
 /// could be used to represent any arbitrary data (i.e. string, arrays, etc). This type is an extendable and re-allocatable blob, which makes
 /// it a blobby Vec, a `BlobVec`.
-pub(super) struct BlobVec {
+pub(crate) struct BlobVec {
     item_layout: Layout,
     capacity: usize,
     /// Number of elements, not bytes
@@ -94,6 +95,13 @@ impl BlobVec {
         self.item_layout
     }
 
+    /// Returns the memory used by the elements of the vector and the memory allocated for it.
+    #[inline]
+    pub(crate) fn memory_usage(&self) -> MemoryUsage {
+        // Zero-sized types report a capacity of `usize::MAX`, but never allocate.
+        MemoryUsage::of_array(self.item_layout.size(), self.len, self.capacity)
+    }
+
     /// Reserves the minimum capacity for at least `additional` more elements to be inserted in the given `BlobVec`.
     /// After calling `reserve_exact`, capacity will be greater than or equal to `self.len() + additional`. Does nothing if
     /// the capacity is already sufficient.
@@ -131,6 +139,42 @@ impl BlobVec {
         }
     }
 
+    /// Shrinks the capacity of the vector to its length, freeing the allocation if it is empty.
+    pub fn shrink_to_fit(&mut self) {
+        // Zero-sized types never allocate.
+        if self.item_layout.size() == 0 || self.capacity == self.len {
+            return;
+        }
+        let current_layout =
+            array_layout(&self.item_layout, self.capacity).expect("array layout should be valid");
+        if self.len == 0 {
+            // SAFETY:
+            // - ptr was allocated via this allocator
+            // - the layout of the ptr is `array_layout(self.item_layout, self.capacity)`
+            unsafe { alloc::alloc::dealloc(self.get_ptr_mut().as_ptr(), current_layout) };
+            let align =
+                NonZero::<usize>::new(self.item_layout.align()).expect("alignment must be > 0");
+            self.data = bevy_ptr::dangling_with_align(align);
+        } else {
+            let new_layout =
+                array_layout(&self.item_layout, self.len).expect("array layout should be valid");
+            // SAFETY:
+            // - ptr was allocated via this allocator
+            // - the layout of the ptr is `array_layout(self.item_layout, self.capacity)`
+            // - `item_layout.size() > 0` and `len > 0`, so the layout size is non-zero
+            // - the new size is smaller than the current size, so it can't overflow
+            let new_data = unsafe {
+                alloc::alloc::realloc(
+                    self.get_ptr_mut().as_ptr(),
+                    current_layout,
+                    new_layout.size(),
+                )
+            };
+            self.data = NonNull::new(new_data).unwrap_or_else(|| handle_alloc_error(new_layout));
+        }
+        self.capacity = self.len;
+    }
+
     /// Grows the capacity by `increment` elements.
     ///
     /// # Panics
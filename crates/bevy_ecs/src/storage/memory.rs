@@ -0,0 +1,570 @@
+use crate::{
+    archetype::ArchetypeId,
+    component::{ComponentId, Tick},
+    resource::Resource,
+    storage::TableId,
+    world::World,
+};
+use alloc::vec::Vec;
+use bevy_platform::collections::HashMap;
+use core::{
+    iter::Sum,
+    mem::size_of,
+    ops::{Add, AddAssign},
+};
+
+/// An amount of memory used by some storage, along with the amount of memory allocated for it.
+///
+/// The difference between the two is the capacity left over by the growth strategy of the
+/// underlying buffers, as reported by [`wasted_bytes`](Self::wasted_bytes).
+/// Heap allocations owned by the stored values themselves are not included.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    /// The number of bytes occupied by stored values.
+    pub used_bytes: usize,
+    /// The number of bytes allocated, including unused capacity.
+    pub allocated_bytes: usize,
+}
+
+impl MemoryUsage {
+    /// No memory at all.
+    pub const ZERO: Self = Self {
+        used_bytes: 0,
+        allocated_bytes: 0,
+    };
+
+    /// Creates a new [`MemoryUsage`].
+    pub const fn new(used_bytes: usize, allocated_bytes: usize) -> Self {
+        Self {
+            used_bytes,
+            allocated_bytes,
+        }
+    }
+
+    /// Returns the number of bytes that are allocated but not used.
+    pub const fn wasted_bytes(&self) -> usize {
+        self.allocated_bytes.saturating_sub(self.used_bytes)
+    }
+
+    /// The memory used by an array of `len` elements of `item_size` bytes, with room for `capacity` elements.
+    pub(crate) const fn of_array(item_size: usize, len: usize, capacity: usize) -> Self {
+        Self::new(item_size * len, item_size * capacity)
+    }
+
+    /// The memory used by the elements of a [`Vec`].
+    pub(crate) fn of_vec<T>(vec: &Vec<T>) -> Self {
+        Self::of_array(size_of::<T>(), vec.len(), vec.capacity())
+    }
+}
+
+impl Add for MemoryUsage {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(
+            self.used_bytes + rhs.used_bytes,
+            self.allocated_bytes + rhs.allocated_bytes,
+        )
+    }
+}
+
+impl AddAssign for MemoryUsage {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for MemoryUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+/// The memory used by the values of a single component, in a [`Table`](super::Table) column
+/// or in a [`ComponentSparseSet`](super::ComponentSparseSet).
+#[derive(Clone, Debug)]
+pub struct ColumnMemoryStats {
+    /// The component stored in the column.
+    pub component: ComponentId,
+    /// The number of values stored in the column.
+    pub len: usize,
+    /// The number of values the column can store without reallocating.
+    pub capacity: usize,
+    /// The memory used by the component values.
+    pub data: MemoryUsage,
+    /// The memory used by the change detection ticks (and change locations, if tracked) of the values.
+    pub change_detection: MemoryUsage,
+}
+
+impl ColumnMemoryStats {
+    /// Returns the memory used by the whole column.
+    pub fn total(&self) -> MemoryUsage {
+        self.data + self.change_detection
+    }
+}
+
+/// The memory used by a [`Table`](super::Table).
+#[derive(Clone, Debug)]
+pub struct TableMemoryStats {
+    /// The id of the table.
+    pub id: TableId,
+    /// The number of entities stored in the table.
+    pub entity_count: usize,
+    /// The number of entities the table can store without reallocating its columns.
+    pub capacity: usize,
+    /// The memory used by the list of entities in the table.
+    pub entities: MemoryUsage,
+    /// The memory used by each column of the table.
+    pub columns: Vec<ColumnMemoryStats>,
+}
+
+impl TableMemoryStats {
+    /// Returns the memory used by the whole table.
+    pub fn total(&self) -> MemoryUsage {
+        self.entities + self.columns.iter().map(ColumnMemoryStats::total).sum()
+    }
+}
+
+/// The memory used by a [`ComponentSparseSet`](super::ComponentSparseSet).
+#[derive(Clone, Debug)]
+pub struct SparseSetMemoryStats {
+    /// The memory used by the dense list of component values.
+    pub column: ColumnMemoryStats,
+    /// The memory used by the dense list of entities.
+    pub entities: MemoryUsage,
+    /// The memory used by the sparse array mapping entities to their value.
+    ///
+    /// It grows with the highest entity index that ever had the component, which makes it large
+    /// for components added to few, recently spawned entities.
+    pub sparse: MemoryUsage,
+}
+
+impl SparseSetMemoryStats {
+    /// Returns the memory used by the whole sparse set.
+    pub fn total(&self) -> MemoryUsage {
+        self.column.total() + self.entities + self.sparse
+    }
+}
+
+/// The memory used by a resource.
+#[derive(Clone, Debug)]
+pub struct ResourceMemoryStats {
+    /// The component id of the resource.
+    pub component: ComponentId,
+    /// Whether the resource is [`Send`], or is a non-send resource.
+    pub send: bool,
+    /// The memory used by the resource value, if it is present.
+    pub data: MemoryUsage,
+}
+
+/// The memory used by the bookkeeping of an [`Archetype`](crate::archetype::Archetype).
+///
+/// The components of the archetype's entities are stored in its table and sparse sets.
+#[derive(Clone, Debug)]
+pub struct ArchetypeMemoryStats {
+    /// The id of the archetype.
+    pub id: ArchetypeId,
+    /// The table storing the table components of the archetype's entities.
+    pub table: TableId,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The number of components of the archetype.
+    pub component_count: usize,
+    /// The memory used by the list of entities in the archetype.
+    pub entities: MemoryUsage,
+}
+
+/// A snapshot of the memory used by the storages of a [`World`], returned by [`World::memory_stats`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Position(f32, f32);
+///
+/// let mut world = World::new();
+/// world.spawn_batch((0..100).map(|i| Position(i as f32, 0.0)));
+///
+/// let stats = world.memory_stats();
+/// let position = world.component_id::<Position>().unwrap();
+/// let usage = stats.component(position);
+/// assert!(usage.used_bytes >= 100 * size_of::<Position>());
+/// assert!(stats.total().allocated_bytes >= usage.allocated_bytes);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct WorldMemoryStats {
+    /// The statistics of every archetype.
+    pub archetypes: Vec<ArchetypeMemoryStats>,
+    /// The statistics of every table.
+    pub tables: Vec<TableMemoryStats>,
+    /// The statistics of every component sparse set.
+    pub sparse_sets: Vec<SparseSetMemoryStats>,
+    /// The statistics of every resource, including non-send resources.
+    pub resources: Vec<ResourceMemoryStats>,
+}
+
+impl WorldMemoryStats {
+    /// Collects the memory statistics of `world`.
+    pub fn new(world: &World) -> Self {
+        let storages = world.storages();
+        let resources = storages
+            .resources
+            .iter()
+            .map(|(component, data)| data.memory_stats(component))
+            .chain(
+                storages
+                    .non_send_resources
+                    .iter()
+                    .map(|(component, data)| data.memory_stats(component)),
+            )
+            .collect();
+        Self {
+            archetypes: world
+                .archetypes()
+                .iter()
+                .map(crate::archetype::Archetype::memory_stats)
+                .collect(),
+            tables: storages
+                .tables
+                .iter()
+                .enumerate()
+                .map(|(index, table)| table.memory_stats(TableId::from_usize(index)))
+                .collect(),
+            sparse_sets: storages
+                .sparse_sets
+                .iter()
+                .map(|(component, sparse_set)| sparse_set.memory_stats(component))
+                .collect(),
+            resources,
+        }
+    }
+
+    /// Returns the memory used by archetype bookkeeping.
+    pub fn archetypes_total(&self) -> MemoryUsage {
+        self.archetypes
+            .iter()
+            .map(|archetype| archetype.entities)
+            .sum()
+    }
+
+    /// Returns the memory used by tables.
+    pub fn tables_total(&self) -> MemoryUsage {
+        self.tables.iter().map(TableMemoryStats::total).sum()
+    }
+
+    /// Returns the memory used by sparse sets.
+    pub fn sparse_sets_total(&self) -> MemoryUsage {
+        self.sparse_sets
+            .iter()
+            .map(SparseSetMemoryStats::total)
+            .sum()
+    }
+
+    /// Returns the memory used by resources.
+    pub fn resources_total(&self) -> MemoryUsage {
+        self.resources.iter().map(|resource| resource.data).sum()
+    }
+
+    /// Returns the memory used by all the storages of the world.
+    pub fn total(&self) -> MemoryUsage {
+        self.archetypes_total()
+            + self.tables_total()
+            + self.sparse_sets_total()
+            + self.resources_total()
+    }
+
+    /// Returns the memory used by the values of the given component, across all tables and sparse sets,
+    /// or by the given resource.
+    pub fn component(&self, component: ComponentId) -> MemoryUsage {
+        self.by_component()
+            .get(&component)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the memory used by the values of every component and resource, including their
+    /// change detection data.
+    pub fn by_component(&self) -> HashMap<ComponentId, MemoryUsage> {
+        let mut usage = HashMap::<ComponentId, MemoryUsage>::default();
+        let columns = self
+            .tables
+            .iter()
+            .flat_map(|table| &table.columns)
+            .chain(self.sparse_sets.iter().map(|sparse_set| &sparse_set.column));
+        for column in columns {
+            *usage.entry(column.component).or_default() += column.total();
+        }
+        for resource in &self.resources {
+            *usage.entry(resource.component).or_default() += resource.data;
+        }
+        usage
+    }
+}
+
+/// The settings of the [`auto_compact`] system.
+///
+/// When the system runs, once at least [`check_interval`](Self::check_interval) change ticks have
+/// passed since the previous check, the [`WorldMemoryStats`] are collected and the
+/// [`World`] is [compacted](World::compact) if its storages waste at least
+/// [`min_wasted_bytes`](Self::min_wasted_bytes). The [default](Self::default) settings are used
+/// if this resource does not exist.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::storage::{auto_compact, AutoCompaction};
+/// let mut world = World::new();
+/// world.insert_resource(AutoCompaction::new(64 * 1024 * 1024).with_check_interval(10_000));
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(auto_compact);
+/// schedule.run(&mut world);
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct AutoCompaction {
+    /// The minimum number of change ticks between two checks of the memory used by the world.
+    ///
+    /// Every system run advances the change tick, so this roughly counts system runs.
+    pub check_interval: u32,
+    /// The minimum number of unused bytes allocated by the storages for the world to be compacted.
+    pub min_wasted_bytes: usize,
+    pub(crate) last_check: Tick,
+}
+
+impl Default for AutoCompaction {
+    fn default() -> Self {
+        Self::new(16 * 1024 * 1024)
+    }
+}
+
+impl AutoCompaction {
+    /// Compacts the world when its storages waste at least `min_wasted_bytes`,
+    /// checking every 100,000 change ticks.
+    pub const fn new(min_wasted_bytes: usize) -> Self {
+        Self {
+            check_interval: 100_000,
+            min_wasted_bytes,
+            last_check: Tick::new(0),
+        }
+    }
+
+    /// Sets the minimum number of change ticks between two checks.
+    pub const fn with_check_interval(mut self, check_interval: u32) -> Self {
+        self.check_interval = check_interval;
+        self
+    }
+}
+
+/// An exclusive system [compacting](World::compact) the [`World`] when it wastes enough memory,
+/// as configured by the [`AutoCompaction`] resource.
+///
+/// Checking the memory used by the world requires walking all of its storages, so the check only
+/// happens every [`check_interval`](AutoCompaction::check_interval) change ticks. Add this system
+/// to a schedule that runs regularly, such as the one running once per frame.
+pub fn auto_compact(world: &mut World) {
+    let change_tick = world.change_tick();
+    let mut settings = world.get_resource_or_init::<AutoCompaction>();
+    if change_tick.relative_to(settings.last_check).get() < settings.check_interval {
+        return;
+    }
+    settings.last_check = change_tick;
+    let min_wasted_bytes = settings.min_wasted_bytes;
+
+    if world.memory_stats().total().wasted_bytes() >= min_wasted_bytes {
+        world.compact();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{change_detection::ResMut, component::Component, resource::Resource};
+
+    #[derive(Component)]
+    struct A(#[expect(dead_code, reason = "only the size matters")] u64);
+
+    #[derive(Component)]
+    struct C(#[expect(dead_code, reason = "only the size matters")] u64);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct B(#[expect(dead_code, reason = "only the size matters")] u32);
+
+    #[derive(Resource)]
+    struct R(#[expect(dead_code, reason = "only the size matters")] [u8; 16]);
+
+    #[test]
+    fn reports_component_and_resource_usage() {
+        let mut world = World::new();
+        world.insert_resource(R([0; 16]));
+        let entities: Vec<_> = world.spawn_batch((0..10).map(|i| (A(i), B(0)))).collect();
+        for entity in &entities[5..] {
+            world.despawn(*entity);
+        }
+
+        let stats = world.memory_stats();
+        let a = world.component_id::<A>().unwrap();
+        let b = world.component_id::<B>().unwrap();
+        let r = world.resource_id::<R>().unwrap();
+
+        let table = stats
+            .tables
+            .iter()
+            .find(|table| table.columns.iter().any(|column| column.component == a))
+            .unwrap();
+        assert_eq!(table.entity_count, 5);
+        assert!(table.capacity >= 10);
+        let column = &table.columns[0];
+        assert_eq!(column.len, 5);
+        assert_eq!(column.data.used_bytes, 5 * size_of::<A>());
+        assert_eq!(column.data.allocated_bytes, table.capacity * size_of::<A>());
+        assert!(column.data.wasted_bytes() >= 5 * size_of::<A>());
+
+        let sparse_set = stats
+            .sparse_sets
+            .iter()
+            .find(|sparse_set| sparse_set.column.component == b)
+            .unwrap();
+        assert_eq!(sparse_set.column.len, 5);
+        assert_eq!(sparse_set.column.data.used_bytes, 5 * size_of::<B>());
+        assert!(sparse_set.sparse.used_bytes > 0);
+
+        assert_eq!(stats.component(r), MemoryUsage::new(16, 16));
+        assert_eq!(stats.component(a), column.total());
+
+        let archetype = stats
+            .archetypes
+            .iter()
+            .find(|archetype| archetype.table == table.id)
+            .unwrap();
+        assert_eq!(archetype.entity_count, 5);
+        assert_eq!(archetype.component_count, 2);
+
+        let total = stats.total();
+        assert_eq!(
+            total,
+            stats.archetypes_total()
+                + stats.tables_total()
+                + stats.sparse_sets_total()
+                + stats.resources_total()
+        );
+        assert!(total.used_bytes <= total.allocated_bytes);
+    }
+
+    #[test]
+    fn compact_releases_unused_capacity() {
+        let mut world = World::new();
+        let mut query = world.query::<(&A, &B)>();
+        let mut entities: Vec<_> = world.spawn_batch((0..100).map(|i| (A(i), B(0)))).collect();
+        let kept = entities.split_off(90);
+        for entity in entities {
+            world.despawn(entity);
+        }
+        let only_a: Vec<_> = world.spawn_batch((0..100).map(A)).collect();
+        for entity in only_a {
+            world.despawn(entity);
+        }
+        let with_c: Vec<_> = world.spawn_batch((0..100).map(|i| (A(i), C(i)))).collect();
+        for entity in with_c {
+            world.despawn(entity);
+        }
+
+        let before = world.memory_stats();
+        world.compact();
+        let after = world.memory_stats();
+        assert!(after.total().allocated_bytes < before.total().allocated_bytes);
+        assert_eq!(after.total().wasted_bytes(), 0);
+        // The archetypes with only `A` and with `(A, C)` were removed, along with the table of the
+        // latter. The table of the former is still used by the archetype with `(A, B)`.
+        assert_eq!(after.archetypes.len(), before.archetypes.len() - 2);
+        assert_eq!(after.tables.len(), before.tables.len() - 1);
+
+        let a = world.component_id::<A>().unwrap();
+        for table in &after.tables {
+            assert_eq!(table.capacity, table.entity_count);
+        }
+        let table = after
+            .tables
+            .iter()
+            .find(|table| table.entity_count == 10)
+            .unwrap();
+        assert!(table.columns.iter().any(|column| column.component == a));
+        for archetype in world.archetypes().iter() {
+            for (row, entity) in archetype.entities().iter().enumerate() {
+                let location = world.entities().get(entity.id()).unwrap();
+                assert_eq!(location.archetype_id, archetype.id());
+                assert_eq!(location.archetype_row.index(), row);
+                assert_eq!(location.table_id, archetype.table_id());
+            }
+        }
+
+        // The cached query state is rebuilt, and the storages keep working.
+        assert_eq!(query.iter(&world).count(), 10);
+        for entity in &kept {
+            assert!(world.get::<A>(*entity).is_some());
+        }
+        world.spawn_batch((0..50).map(A));
+        world.spawn_batch((0..50).map(|i| (A(i), B(0))));
+        assert_eq!(query.iter(&world).count(), 60);
+        assert_eq!(world.query::<&A>().iter(&world).count(), 110);
+    }
+
+    #[test]
+    fn compact_rebuilds_system_queries() {
+        use crate::{query::With, schedule::Schedule, system::Query};
+
+        #[derive(Resource, Default)]
+        struct Counts(Vec<usize>);
+
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|query: Query<&A, With<B>>, mut counts: ResMut<Counts>| {
+            counts.0.push(query.iter().count());
+        });
+
+        let removed: Vec<_> = world.spawn_batch((0..10).map(A)).collect();
+        world.spawn_batch((0..5).map(|i| (A(i), B(0))));
+        schedule.run(&mut world);
+        for entity in removed {
+            world.despawn(entity);
+        }
+        world.compact();
+        schedule.run(&mut world);
+        world.spawn_batch((0..5).map(|i| (A(i), B(0))));
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Counts>().0, [5, 5, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The World was compacted")]
+    fn compact_invalidates_manual_queries() {
+        let mut world = World::new();
+        let mut query = world.query::<&A>();
+        let entity = world.spawn(A(0)).id();
+        query.update_archetypes(&world);
+        world.despawn(entity);
+        world.compact();
+        query.iter_manual(&world).count();
+    }
+
+    #[test]
+    fn auto_compaction() {
+        use crate::schedule::Schedule;
+
+        let mut world = World::new();
+        let entities: Vec<_> = world.spawn_batch((0..100).map(A)).collect();
+        for entity in entities {
+            world.despawn(entity);
+        }
+        let mut schedule = Schedule::default();
+        schedule.add_systems(auto_compact);
+
+        world.insert_resource(AutoCompaction::new(usize::MAX).with_check_interval(0));
+        schedule.run(&mut world);
+        assert!(world.memory_stats().total().wasted_bytes() > 0);
+
+        world.insert_resource(AutoCompaction::new(1).with_check_interval(0));
+        schedule.run(&mut world);
+        assert_eq!(world.memory_stats().total().wasted_bytes(), 0);
+    }
+}
@@ -13,20 +13,26 @@
 //!    lookup and regular insertion/removal of components.
 //!  - [`Resources`] - singleton storage for the resources in the world
 //!
+//! The memory used by all of these can be inspected with [`World::memory_stats`].
+//!
 //! # Safety
 //! To avoid trivially unsound use of the APIs in this module, it is explicitly impossible to get a mutable
 //! reference to [`Storages`] from [`World`], and none of the types publicly expose a mutable interface.
 //!
 //! [`World`]: crate::world::World
 //! [`World::storages`]: crate::world::World::storages
+//! [`World::memory_stats`]: crate::world::World::memory_stats
 
 mod blob_array;
 mod blob_vec;
+mod memory;
 mod resource;
 mod sparse_set;
 mod table;
 mod thin_array_ptr;
 
+pub(crate) use blob_vec::BlobVec;
+pub use memory::*;
 pub use resource::*;
 pub use sparse_set::*;
 pub use table::*;
@@ -2,7 +2,7 @@ use crate::{
     archetype::ArchetypeComponentId,
     change_detection::{MaybeLocation, MutUntyped, TicksMut},
     component::{ComponentId, ComponentTicks, Components, Tick, TickCells},
-    storage::{blob_vec::BlobVec, SparseSet},
+    storage::{blob_vec::BlobVec, ResourceMemoryStats, SparseSet},
 };
 use alloc::string::String;
 use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
@@ -100,6 +100,15 @@ impl<const SEND: bool> ResourceData<SEND> {
         !self.data.is_empty()
     }
 
+    /// Returns the memory used by this resource, which has the given `component` id.
+    pub(crate) fn memory_stats(&self, component: ComponentId) -> ResourceMemoryStats {
+        ResourceMemoryStats {
+            component,
+            send: SEND,
+            data: self.data.memory_usage(),
+        }
+    }
+
     /// Gets the [`ArchetypeComponentId`] for the resource.
     #[inline]
     pub fn id(&self) -> ArchetypeComponentId {
@@ -2,7 +2,7 @@ use crate::{
     change_detection::MaybeLocation,
     component::{ComponentId, ComponentInfo, ComponentTicks, Tick, TickCells},
     entity::Entity,
-    storage::{Column, TableRow},
+    storage::{Column, MemoryUsage, SparseSetMemoryStats, TableRow},
 };
 use alloc::{boxed::Box, vec::Vec};
 use bevy_ptr::{OwningPtr, Ptr};
@@ -102,6 +102,17 @@ impl<I: SparseSetIndex, V> SparseArray<I, V> {
         self.values.clear();
     }
 
+    /// Drops the trailing empty slots of the array and shrinks its capacity to fit.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let len = self
+            .values
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |index| index + 1);
+        self.values.truncate(len);
+        self.values.shrink_to_fit();
+    }
+
     /// Converts the [`SparseArray`] into an immutable variant.
     pub(crate) fn into_immutable(self) -> ImmutableSparseArray<I, V> {
         ImmutableSparseArray {
@@ -157,6 +168,22 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// Shrinks the capacity of the sparse set to the number of values it stores.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.dense.shrink_to_fit();
+        self.entities.shrink_to_fit();
+        self.sparse.shrink_to_fit();
+    }
+
+    /// Returns the memory used by this sparse set, which stores the given `component`.
+    pub(crate) fn memory_stats(&self, component: ComponentId) -> SparseSetMemoryStats {
+        SparseSetMemoryStats {
+            column: self.dense.memory_stats(component),
+            entities: MemoryUsage::of_vec(&self.entities),
+            sparse: MemoryUsage::of_vec(&self.sparse.values),
+        }
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse
     /// set.
     ///
@@ -647,6 +674,13 @@ impl SparseSets {
         }
     }
 
+    /// Shrinks the capacity of every [`ComponentSparseSet`] to the number of values it stores.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for set in self.sets.values_mut() {
+            set.shrink_to_fit();
+        }
+    }
+
     pub(crate) fn check_change_ticks(&mut self, change_tick: Tick) {
         for set in self.sets.values_mut() {
             set.check_change_ticks(change_tick);
@@ -2,11 +2,13 @@ use super::*;
 use crate::{
     change_detection::MaybeLocation,
     component::TickCells,
-    storage::{blob_array::BlobArray, thin_array_ptr::ThinArrayPtr},
+    storage::{
+        blob_array::BlobArray, thin_array_ptr::ThinArrayPtr, ColumnMemoryStats, MemoryUsage,
+    },
 };
 use alloc::vec::Vec;
 use bevy_ptr::PtrMut;
-use core::panic::Location;
+use core::{mem::size_of, panic::Location};
 
 /// Very similar to a normal [`Column`], but with the capacities and lengths cut out for performance reasons.
 ///
@@ -122,6 +124,28 @@ impl ThinColumn {
             .map(|changed_by| changed_by.realloc(current_capacity, new_capacity));
     }
 
+    /// Returns the memory used by this column, which stores `len` values and has room for `capacity` values.
+    pub(crate) fn memory_stats(
+        &self,
+        component: ComponentId,
+        len: usize,
+        capacity: usize,
+    ) -> ColumnMemoryStats {
+        let ticks_size = 2 * size_of::<UnsafeCell<Tick>>()
+            + self
+                .changed_by
+                .as_ref()
+                .into_option()
+                .map_or(0, |_| size_of::<UnsafeCell<&'static Location<'static>>>());
+        ColumnMemoryStats {
+            component,
+            len,
+            capacity,
+            data: MemoryUsage::of_array(self.data.layout().size(), len, capacity),
+            change_detection: MemoryUsage::of_array(ticks_size, len, capacity),
+        }
+    }
+
     /// Call [`alloc`](std::alloc::alloc) to allocate memory for this [`ThinColumn`]
     /// The caller should make sure their saved `capacity` value is updated to `new_capacity` after this operation.
     pub(crate) fn alloc(&mut self, new_capacity: NonZeroUsize) {
@@ -281,6 +305,23 @@ impl ThinColumn {
             .map(|changed_by| changed_by.drop(cap, len));
     }
 
+    /// Drops all components and frees the memory of this column. Unlike [`drop`](Self::drop), the
+    /// column stays usable afterwards, with a capacity of 0.
+    ///
+    /// # Safety
+    /// - `len` is indeed the length of the column
+    /// - `cap` is indeed the capacity of the column
+    /// - the owner must treat the column as having a capacity of 0 afterwards, and allocate it again
+    ///   with [`alloc`](Self::alloc) before storing components
+    pub(crate) unsafe fn dealloc(&mut self, cap: usize, len: usize) {
+        self.added_ticks.dealloc(cap, len);
+        self.changed_ticks.dealloc(cap, len);
+        self.data.dealloc(cap, len);
+        if let Some(changed_by) = self.changed_by.as_mut().into_option() {
+            changed_by.dealloc(cap, len);
+        }
+    }
+
     /// Drops the last component in this column.
     ///
     /// # Safety
@@ -371,6 +412,32 @@ impl Column {
         self.data.layout()
     }
 
+    /// Shrinks the capacity of the column to its length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+        self.added_ticks.shrink_to_fit();
+        self.changed_ticks.shrink_to_fit();
+        if let Some(changed_by) = self.changed_by.as_mut().into_option() {
+            changed_by.shrink_to_fit();
+        }
+    }
+
+    /// Returns the memory used by this column.
+    pub(crate) fn memory_stats(&self, component: ComponentId) -> ColumnMemoryStats {
+        let mut change_detection =
+            MemoryUsage::of_vec(&self.added_ticks) + MemoryUsage::of_vec(&self.changed_ticks);
+        if let Some(changed_by) = self.changed_by.as_ref().into_option() {
+            change_detection += MemoryUsage::of_vec(changed_by);
+        }
+        ColumnMemoryStats {
+            component,
+            len: self.len(),
+            capacity: self.added_ticks.capacity(),
+            data: self.data.memory_usage(),
+            change_detection,
+        }
+    }
+
     /// Writes component data to the column at given row.
     /// Assumes the slot is initialized, calls drop.
     ///
@@ -3,7 +3,7 @@ use crate::{
     component::{ComponentId, ComponentInfo, ComponentTicks, Components, Tick},
     entity::Entity,
     query::DebugCheckedUnwrap,
-    storage::{blob_vec::BlobVec, ImmutableSparseSet, SparseSet},
+    storage::{blob_vec::BlobVec, ImmutableSparseSet, MemoryUsage, SparseSet, TableMemoryStats},
 };
 use alloc::{boxed::Box, vec, vec::Vec};
 use bevy_platform::collections::HashMap;
@@ -625,6 +625,58 @@ impl Table {
         }
     }
 
+    /// Shrinks the capacity of the table to the number of entities it stores,
+    /// freeing the memory of its columns if it is empty.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let len = self.entity_count();
+        let column_cap = self.capacity();
+        if len == column_cap {
+            return;
+        }
+        if len == 0 {
+            for col in self.columns.values_mut() {
+                // SAFETY:
+                // - `column_cap` is the capacity of the columns, and they store no elements
+                // - the columns get a capacity of 0 along with `entities`, so the next `reserve`
+                //   allocates them again
+                unsafe { col.dealloc(column_cap, 0) };
+            }
+            self.entities = Vec::new();
+            return;
+        }
+
+        self.entities.shrink_to_fit();
+        // use entities vector capacity as driving capacity for all related allocations
+        let new_capacity = self.entities.capacity();
+        if new_capacity != column_cap {
+            // SAFETY:
+            // - `column_cap` is indeed the columns' capacity
+            // - both capacities are at least `len`, which is not 0
+            unsafe {
+                self.realloc_columns(
+                    NonZeroUsize::new_unchecked(column_cap),
+                    NonZeroUsize::new_unchecked(new_capacity),
+                );
+            }
+        }
+    }
+
+    /// Returns the memory used by this table, which has the given `id`.
+    pub(crate) fn memory_stats(&self, id: TableId) -> TableMemoryStats {
+        let (len, capacity) = (self.entity_count(), self.capacity());
+        TableMemoryStats {
+            id,
+            entity_count: len,
+            capacity,
+            entities: MemoryUsage::of_vec(&self.entities),
+            columns: self
+                .columns
+                .iter()
+                .map(|(component, column)| column.memory_stats(*component, len, capacity))
+                .collect(),
+        }
+    }
+
     /// Iterates over the [`ThinColumn`]s of the [`Table`].
     pub fn iter_columns(&self) -> impl Iterator<Item = &ThinColumn> {
         self.columns.values()
@@ -786,6 +838,52 @@ impl Tables {
             table.check_change_ticks(change_tick);
         }
     }
+
+    /// Shrinks the capacity of every [`Table`] to the number of entities it stores.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for table in &mut self.tables {
+            table.shrink_to_fit();
+        }
+    }
+
+    /// Removes the empty tables for which `is_used` returns `false`, except for the
+    /// [empty table](TableId::empty), and gives the remaining tables consecutive ids, keeping
+    /// their order.
+    ///
+    /// Returns the new id of every table, indexed by its previous id, or `None` if it was removed.
+    pub(crate) fn remove_unused(
+        &mut self,
+        is_used: impl Fn(TableId) -> bool,
+    ) -> Vec<Option<TableId>> {
+        let mut new_ids = Vec::with_capacity(self.tables.len());
+        let mut kept = 0;
+        for (index, table) in self.tables.iter().enumerate() {
+            let id = TableId::from_usize(index);
+            if id == TableId::empty() || !table.is_empty() || is_used(id) {
+                new_ids.push(Some(TableId::from_usize(kept)));
+                kept += 1;
+            } else {
+                new_ids.push(None);
+            }
+        }
+        if kept == self.tables.len() {
+            return new_ids;
+        }
+
+        let mut index = 0;
+        self.tables.retain(|_| {
+            index += 1;
+            new_ids[index - 1].is_some()
+        });
+        self.table_ids.retain(|_, id| match new_ids[id.as_usize()] {
+            Some(new_id) => {
+                *id = new_id;
+                true
+            }
+            None => false,
+        });
+        new_ids
+    }
 }
 
 impl Index<TableId> for Tables {
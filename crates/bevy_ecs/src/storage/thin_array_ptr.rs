@@ -292,6 +292,28 @@ impl<T> ThinArrayPtr<T> {
         self.set_capacity(0);
     }
 
+    /// Drops all elements and frees the memory of the array. Unlike [`drop`](Self::drop), the array
+    /// stays usable afterwards, with a capacity of 0.
+    ///
+    /// # Safety
+    /// - `current_len` is indeed the length of the array
+    /// - `current_capacity` is indeed the capacity of the array
+    /// - The caller must treat the array as having a capacity of 0 afterwards, and allocate it again
+    ///   with [`alloc`](Self::alloc) before storing elements.
+    pub unsafe fn dealloc(&mut self, current_capacity: usize, current_len: usize) {
+        #[cfg(debug_assertions)]
+        assert_eq!(self.capacity, current_capacity);
+        if current_capacity != 0 {
+            self.clear_elements(current_len);
+            if size_of::<T>() != 0 {
+                let layout = Layout::array::<T>(current_capacity).expect("layout should be valid");
+                alloc::alloc::dealloc(self.data.as_ptr().cast(), layout);
+            }
+        }
+        self.data = NonNull::dangling();
+        self.set_capacity(0);
+    }
+
     /// Get the [`ThinArrayPtr`] as a slice with a given length.
     ///
     /// # Safety
@@ -1045,6 +1045,19 @@ impl<'w, 's> Commands<'w, 's> {
         self.queue(command::run_system_cached_with(system, input).handle_error_with(warn));
     }
 
+    /// Spawns an async task that can await futures and access the world through an
+    /// [`AsyncWorld`](crate::world::AsyncWorld).
+    ///
+    /// See [`World::spawn_task`] for details.
+    #[cfg(feature = "std")]
+    pub fn spawn_task<F, Fut>(&mut self, task: F)
+    where
+        F: FnOnce(crate::world::AsyncWorld) -> Fut + Send + 'static,
+        Fut: bevy_tasks::ConditionalSendFuture<Output = ()> + 'static,
+    {
+        self.queue(move |world: &mut World| world.spawn_task(task));
+    }
+
     /// Sends a "global" [`Trigger`](crate::observer::Trigger) without any targets.
     ///
     /// This will run any [`Observer`] of the given [`Event`] that isn't scoped to specific targets.
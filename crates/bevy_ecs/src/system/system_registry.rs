@@ -6,7 +6,7 @@ use crate::{
     system::{input::SystemInput, BoxedSystem, IntoSystem, SystemParamValidationError},
     world::World,
 };
-use alloc::boxed::Box;
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
 use bevy_ecs_macros::{Component, Resource};
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
@@ -471,6 +471,38 @@ impl World {
         let id = self.register_system_cached(system);
         self.run_system_with(id, input)
     }
+
+    /// Returns the [`SystemId`] and name of every registered system with the given input and output types.
+    ///
+    /// Systems that are currently running are not included, as they are taken out of the
+    /// registry while they run.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// fn greet() {}
+    ///
+    /// let mut world = World::new();
+    /// let id = world.register_system(greet);
+    /// let systems = world.registered_systems::<(), ()>();
+    /// assert_eq!(systems.len(), 1);
+    /// assert_eq!(systems[0].0, id);
+    /// assert!(systems[0].1.ends_with("greet"));
+    /// ```
+    pub fn registered_systems<I, O>(&mut self) -> Vec<(SystemId<I, O>, Cow<'static, str>)>
+    where
+        I: SystemInput + 'static,
+        O: 'static,
+    {
+        self.query::<(Entity, &RegisteredSystem<I, O>)>()
+            .iter(self)
+            .map(|(entity, registered_system)| {
+                (
+                    SystemId::from_entity(entity),
+                    registered_system.system.name(),
+                )
+            })
+            .collect()
+    }
 }
 
 /// An operation with stored systems failed.
@@ -915,4 +947,28 @@ mod tests {
         world.run_system_cached(system.pipe(system)).unwrap();
         world.run_system_cached(system.map(|()| {})).unwrap();
     }
+
+    #[test]
+    fn registered_systems() {
+        fn first() {}
+        fn second() {}
+        fn double(In(x): In<u8>) -> u8 {
+            x * 2
+        }
+
+        let mut world = World::new();
+        let first_id = world.register_system(first);
+        let second_id = world.register_system(second);
+        let double_id = world.register_system(double);
+        world.unregister_system(second_id).unwrap();
+
+        let systems = world.registered_systems::<(), ()>();
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].0, first_id);
+        assert!(systems[0].1.ends_with("first"));
+
+        let systems = world.registered_systems::<In<u8>, u8>();
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].0, double_id);
+    }
 }
@@ -0,0 +1,333 @@
+use crate::{
+    resource::Resource,
+    system::Commands,
+    world::{command_queue::CommandQueue, World},
+};
+use alloc::{boxed::Box, vec::Vec};
+use bevy_platform::sync::{Arc, Mutex, PoisonError};
+use bevy_tasks::{futures::check_ready, AsyncComputeTaskPool, ConditionalSendFuture, Task};
+use concurrent_queue::ConcurrentQueue;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+type WorldAccessFn = Box<dyn FnOnce(&mut World) + Send>;
+
+/// A [`Resource`] holding the tasks spawned with [`World::spawn_task`] and the world accesses
+/// they requested through their [`AsyncWorld`].
+///
+/// Requested accesses are applied in order by [`apply_async_tasks`], which `bevy_app` runs in
+/// `PreUpdate`. Dropping this resource cancels the tasks.
+#[derive(Resource)]
+pub struct AsyncTasks {
+    queue: Arc<ConcurrentQueue<WorldAccessFn>>,
+    tasks: Vec<Task<()>>,
+}
+
+impl Default for AsyncTasks {
+    fn default() -> Self {
+        Self {
+            queue: Arc::new(ConcurrentQueue::unbounded()),
+            tasks: Vec::new(),
+        }
+    }
+}
+
+impl AsyncTasks {
+    /// Returns a handle that tasks can use to request access to the [`World`].
+    pub fn world(&self) -> AsyncWorld {
+        AsyncWorld {
+            queue: self.queue.clone(),
+        }
+    }
+
+    /// Returns the number of spawned tasks that haven't finished yet.
+    pub fn len(&self) -> usize {
+        self.tasks.iter().filter(|task| !task.is_finished()).count()
+    }
+
+    /// Returns `true` if all spawned tasks have finished.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of world accesses waiting to be applied.
+    pub fn pending_accesses(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+/// Applies the world accesses requested by async tasks through their [`AsyncWorld`], in the order
+/// they were requested, then wakes the tasks waiting for them.
+///
+/// Accesses requested while this runs, including by the accesses themselves, are applied the next
+/// time it runs. Finished tasks are removed from [`AsyncTasks`].
+///
+/// # Panics
+///
+/// Panics if a task panicked, resuming its panic.
+pub fn apply_async_tasks(world: &mut World) {
+    let Some(mut tasks) = world.get_resource_mut::<AsyncTasks>() else {
+        return;
+    };
+    tasks.tasks.retain_mut(|task| {
+        if !task.is_finished() {
+            return true;
+        }
+        // Polling a finished task resumes the panic it ended with, if any.
+        check_ready(task);
+        false
+    });
+    let queue = tasks.queue.clone();
+
+    for _ in 0..queue.len() {
+        let Ok(access) = queue.pop() else {
+            break;
+        };
+        access(world);
+        world.flush();
+    }
+}
+
+/// A handle given to tasks spawned with [`World::spawn_task`], used to access the [`World`] from
+/// async code.
+///
+/// Accesses are deferred: they run when [`apply_async_tasks`] next runs, with exclusive access
+/// to the world, and the task resumes once its access has been applied.
+#[derive(Clone)]
+pub struct AsyncWorld {
+    queue: Arc<ConcurrentQueue<WorldAccessFn>>,
+}
+
+impl AsyncWorld {
+    /// Requests to run `f` with exclusive access to the [`World`], returning a future resolving
+    /// to its output once it ran.
+    ///
+    /// The access is queued as soon as this is called, even if the returned future is never
+    /// awaited. If the [`AsyncTasks`] resource is removed before the access is applied,
+    /// the future never resolves.
+    pub fn run<R, F>(&self, f: F) -> WorldAccess<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut World) -> R + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(AccessState {
+            output: None,
+            waker: None,
+        }));
+        let access_state = state.clone();
+        let access: WorldAccessFn = Box::new(move |world| {
+            let output = f(world);
+            let waker = {
+                let mut state = access_state.lock().unwrap_or_else(PoisonError::into_inner);
+                state.output = Some(output);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        // The queue is unbounded and never closed.
+        let _ = self.queue.push(access);
+        WorldAccess { state }
+    }
+
+    /// Requests to run `f` with the [`Commands`] of the [`World`], returning a future resolving to
+    /// its output once the commands have been applied.
+    pub fn commands<R, F>(&self, f: F) -> WorldAccess<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(Commands) -> R + Send + 'static,
+    {
+        self.run(move |world| {
+            let output = f(world.commands());
+            world.flush();
+            output
+        })
+    }
+
+    /// Requests to apply a [`CommandQueue`] to the [`World`], returning a future resolving once
+    /// it has been applied.
+    pub fn apply(&self, mut queue: CommandQueue) -> WorldAccess<()> {
+        self.run(move |world| queue.apply(world))
+    }
+}
+
+struct AccessState<R> {
+    output: Option<R>,
+    waker: Option<Waker>,
+}
+
+/// A future resolving to the output of a world access requested through an [`AsyncWorld`].
+#[must_use = "The world access is applied even if this future is dropped, but its output is lost"]
+pub struct WorldAccess<R> {
+    state: Arc<Mutex<AccessState<R>>>,
+}
+
+impl<R> Future for WorldAccess<R> {
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl World {
+    /// Spawns an async task on the [`AsyncComputeTaskPool`], which can await futures and access
+    /// this world through the given [`AsyncWorld`].
+    ///
+    /// The task is stored in the [`AsyncTasks`] resource, which is initialized if needed, and is
+    /// canceled if the resource is removed. Its world accesses are applied by [`apply_async_tasks`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`AsyncComputeTaskPool`] has not been initialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::world::apply_async_tasks;
+    /// # use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+    /// # AsyncComputeTaskPool::get_or_init(TaskPool::new);
+    /// #[derive(Resource)]
+    /// struct Level(String);
+    ///
+    /// let mut world = World::new();
+    /// world.spawn_task(async move |world| {
+    ///     let name = String::from("level_1"); // e.g. read from a file
+    ///     world.commands(move |mut commands| commands.insert_resource(Level(name))).await;
+    /// });
+    ///
+    /// // Usually run by a system every frame.
+    /// while !world.contains_resource::<Level>() {
+    ///     apply_async_tasks(&mut world);
+    /// #   AsyncComputeTaskPool::get().with_local_executor(|ex| while ex.try_tick() {});
+    /// }
+    /// ```
+    pub fn spawn_task<F, Fut>(&mut self, task: F)
+    where
+        F: FnOnce(AsyncWorld) -> Fut,
+        Fut: ConditionalSendFuture<Output = ()> + 'static,
+    {
+        let mut tasks = self.get_resource_or_init::<AsyncTasks>();
+        let future = task(tasks.world());
+        let task = AsyncComputeTaskPool::get().spawn(future);
+        tasks.tasks.push(task);
+    }
+}
+
+// These tests drive the single-threaded task pool by hand to control when tasks resume.
+#[cfg(all(test, not(feature = "multi_threaded")))]
+mod tests {
+    use super::*;
+    use crate::{component::Component, resource::Resource};
+    use bevy_tasks::TaskPool;
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Loaded(u32);
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    /// Applies world accesses and runs the tasks they woke up on the single-threaded task pool.
+    fn update(world: &mut World) {
+        apply_async_tasks(world);
+        AsyncComputeTaskPool::get().with_local_executor(|executor| while executor.try_tick() {});
+    }
+
+    #[test]
+    fn tasks_access_the_world_in_order() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::new);
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+
+        world.spawn_task(async move |world| {
+            let count = world
+                .run(|world: &mut World| {
+                    world.resource_mut::<Counter>().0 += 1;
+                    world.resource::<Counter>().0
+                })
+                .await;
+            assert_eq!(count, 1);
+
+            let entity = world
+                .commands(move |mut commands| commands.spawn(Loaded(count)).id())
+                .await;
+            let mut queue = CommandQueue::default();
+            queue.push(move |world: &mut World| {
+                world.entity_mut(entity).insert(Loaded(2));
+            });
+            world.apply(queue).await;
+            world
+                .run(|world| world.resource_mut::<Counter>().0 += 1)
+                .await;
+        });
+        assert_eq!(world.resource::<AsyncTasks>().len(), 1);
+
+        // Every access is applied at the next update, after the task awaited the previous one.
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert_eq!(world.query::<&Loaded>().iter(&world).count(), 0);
+        update(&mut world);
+        assert_eq!(world.query::<&Loaded>().single(&world).unwrap(), &Loaded(1));
+        update(&mut world);
+        assert_eq!(world.query::<&Loaded>().single(&world).unwrap(), &Loaded(2));
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 2);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+        assert_eq!(world.resource::<AsyncTasks>().pending_accesses(), 0);
+    }
+
+    #[test]
+    fn tasks_spawned_from_commands() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::new);
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+
+        // Stands in for IO: the value is only available once the other world applies its accesses.
+        let mut io = World::new();
+        let value = io.get_resource_or_init::<AsyncTasks>().world().run(|_| 5);
+
+        world.commands().spawn_task(async move |world| {
+            let value = value.await;
+            world
+                .run(move |world| world.resource_mut::<Counter>().0 = value)
+                .await;
+        });
+        world.flush();
+        update(&mut world);
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 0);
+        assert_eq!(world.resource::<AsyncTasks>().len(), 1);
+
+        // The task resumes once the future it awaits is ready.
+        apply_async_tasks(&mut io);
+        update(&mut world);
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 5);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "task failed")]
+    fn panics_in_tasks_propagate() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::new);
+        let mut world = World::new();
+        world.spawn_task(async move |_| panic!("task failed"));
+
+        // The executor catches the panic, which is resumed when the finished task is removed.
+        AsyncComputeTaskPool::get().with_local_executor(|executor| while executor.try_tick() {});
+        assert!(world.resource::<AsyncTasks>().is_empty());
+        apply_async_tasks(&mut world);
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     component::ComponentId,
     entity::{Entity, EntityDoesNotExistError},
     schedule::InternedScheduleLabel,
+    world::WorldId,
 };
 
 /// The error type returned by [`World::try_run_schedule`] if the provided schedule does not exist.
@@ -69,3 +70,14 @@ pub enum ResourceFetchError {
     #[error("Cannot get access to the resource with ID {0:?} in the world as it conflicts with an on going operation.")]
     NoResourceAccess(ComponentId),
 }
+
+/// An error that occurs when restoring a [`WorldSnapshot`](crate::world::WorldSnapshot).
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreSnapshotError {
+    /// The snapshot was taken from the world with the given [`WorldId`].
+    #[error("The snapshot was taken from another world with ID {0:?}.")]
+    WorldMismatch(WorldId),
+    /// The entity could not be respawned because its row is used by an entity that is not part of the snapshot.
+    #[error("Could not respawn entity {0} because its index is used by an entity that is not part of the snapshot.")]
+    EntityRowOccupied(Entity),
+}
@@ -1,5 +1,7 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+#[cfg(feature = "std")]
+mod async_world;
 pub(crate) mod command_queue;
 mod component_constants;
 mod deferred_world;
@@ -8,6 +10,7 @@ mod entity_ref;
 pub mod error;
 mod filtered_resource;
 mod identifier;
+mod snapshot;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 
@@ -19,6 +22,8 @@ pub use crate::{
     change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD},
     world::command_queue::CommandQueue,
 };
+#[cfg(feature = "std")]
+pub use async_world::{apply_async_tasks, AsyncTasks, AsyncWorld, WorldAccess};
 pub use bevy_ecs_macros::FromWorld;
 pub use component_constants::*;
 pub use deferred_world::DeferredWorld;
@@ -29,6 +34,7 @@ pub use entity_ref::{
 };
 pub use filtered_resource::*;
 pub use identifier::WorldId;
+pub use snapshot::{SnapshotRegistry, WorldSnapshot};
 pub use spawn_batch::*;
 
 use crate::{
@@ -52,7 +58,7 @@ use crate::{
     removal_detection::RemovedComponentEvents,
     resource::Resource,
     schedule::{Schedule, ScheduleLabel, Schedules},
-    storage::{ResourceData, Storages},
+    storage::{ResourceData, Storages, WorldMemoryStats},
     system::Commands,
     world::{
         command_queue::RawCommandQueue,
@@ -65,6 +71,7 @@ use alloc::{boxed::Box, vec::Vec};
 use bevy_platform::sync::atomic::{AtomicU32, Ordering};
 use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
 use core::{any::TypeId, fmt};
+use fixedbitset::FixedBitSet;
 use log::warn;
 use unsafe_world_cell::{UnsafeEntityCell, UnsafeWorldCell};
 
@@ -245,6 +252,14 @@ impl World {
         &self.storages
     }
 
+    /// Collects the memory used by this world's archetypes, tables, sparse sets and resources.
+    ///
+    /// This walks every storage, so it should be called sparingly, for example to look for
+    /// leaks or unused capacity. See [`WorldMemoryStats`] for details.
+    pub fn memory_stats(&self) -> WorldMemoryStats {
+        WorldMemoryStats::new(self)
+    }
+
     /// Retrieves this world's [`Bundles`] collection.
     #[inline]
     pub fn bundles(&self) -> &Bundles {
@@ -2985,6 +3000,66 @@ impl World {
         self.last_check_tick = change_tick;
     }
 
+    /// Removes the empty archetypes and tables of this [`World`], and shrinks its remaining storages
+    /// to the number of entities they store.
+    ///
+    /// Every archetype without entities is removed, except for the empty archetype, along with
+    /// the empty tables no remaining archetype uses. The remaining archetypes and tables get new
+    /// [`ArchetypeId`]s and [`TableId`](crate::storage::TableId)s, so ids obtained before this call
+    /// must not be used afterwards. [`Entity`] ids are not affected. Then the capacity of every
+    /// table, sparse set and archetype is shrunk to the number of entities it stores, and the
+    /// bookkeeping of [`Entities`] is shrunk.
+    ///
+    /// Systems and [`QueryState`]s rebuild their cached archetypes the next time they are updated,
+    /// which makes that update proportional to the number of archetypes. [`QueryState`] methods
+    /// that do not update the state, such as [`QueryState::iter_manual`], panic until
+    /// [`QueryState::update_archetypes`] is called.
+    ///
+    /// Storages grow again as needed, so this is most useful after despawning many entities at
+    /// once, for example when unloading a level. See [`auto_compact`](crate::storage::auto_compact)
+    /// to compact the world automatically, and [`World::memory_stats`] to measure the memory that
+    /// can be reclaimed.
+    pub fn compact(&mut self) {
+        self.flush();
+
+        #[cfg(feature = "trace")]
+        let _span = tracing::info_span!("compact world").entered();
+        if self.archetypes.remove_empty() {
+            let mut used_tables = FixedBitSet::with_capacity(self.storages.tables.len());
+            for archetype in self.archetypes.iter() {
+                used_tables.insert(archetype.table_id().as_usize());
+            }
+            let new_table_ids = self
+                .storages
+                .tables
+                .remove_unused(|table_id| used_tables.contains(table_id.as_usize()));
+            // SAFETY: The tables of the archetypes were kept, and got the new ids.
+            unsafe { self.archetypes.remap_tables(&new_table_ids) };
+            for archetype in self.archetypes.iter() {
+                for (index, entity) in archetype.entities().iter().enumerate() {
+                    let location = EntityLocation {
+                        archetype_id: archetype.id(),
+                        archetype_row: ArchetypeRow::new(index),
+                        table_id: archetype.table_id(),
+                        table_row: entity.table_row(),
+                    };
+                    // SAFETY: The entity is stored in the archetype, at that location.
+                    unsafe { self.entities.set(entity.id().index(), location) };
+                }
+            }
+        }
+
+        let Storages {
+            ref mut tables,
+            ref mut sparse_sets,
+            ..
+        } = self.storages;
+        tables.shrink_to_fit();
+        sparse_sets.shrink_to_fit();
+        self.archetypes.shrink_to_fit();
+        self.entities.shrink_to_fit();
+    }
+
     /// Runs both [`clear_entities`](Self::clear_entities) and [`clear_resources`](Self::clear_resources),
     /// invalidating all [`Entity`] and resource fetches such as [`Res`](crate::system::Res), [`ResMut`](crate::system::ResMut)
     pub fn clear_all(&mut self) {
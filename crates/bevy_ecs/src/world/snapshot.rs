@@ -0,0 +1,691 @@
+//! Capturing and restoring opted-in parts of a [`World`], e.g. for rollback networking.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    any::{Any, TypeId},
+    ptr::NonNull,
+};
+
+use bevy_ptr::{OwningPtr, Ptr};
+use bumpalo::Bump;
+
+use crate::{
+    archetype::{Archetype, ArchetypeEntity},
+    change_detection::{DetectChanges, DetectChangesMut, MaybeLocation},
+    component::{
+        Component, ComponentCloneBehavior, ComponentId, ComponentTicks, Components, StorageType,
+    },
+    entity::{Entity, EntityCloner, EntityHashSet},
+    query::DebugCheckedUnwrap,
+    resource::Resource,
+    storage::{BlobVec, TableRow},
+    world::{error::RestoreSnapshotError, World, WorldId},
+};
+
+/// The components and resources captured by [`World::snapshot`].
+///
+/// Only registered types are captured and restored, everything else in the [`World`] is left
+/// untouched by [`World::restore_snapshot`]. Entities that have at least one registered component
+/// are considered to be part of the snapshot: they are despawned, respawned or updated to match it.
+///
+/// Components are copied with their [`ComponentCloneBehavior`] into one compact buffer per component
+/// type, while resources are copied with their [`Clone`] implementation. Components that are
+/// [ignored](ComponentCloneBehavior::Ignore) by their clone behavior are left untouched.
+#[derive(Resource, Default, Clone)]
+pub struct SnapshotRegistry {
+    components: Vec<ComponentSnapshotFns>,
+    resources: Vec<ResourceSnapshotFns>,
+}
+
+impl SnapshotRegistry {
+    /// Registers the component `C` to be captured by snapshots.
+    pub fn register_component<C: Component>(&mut self) -> &mut Self {
+        if !self.contains_component::<C>() {
+            self.components.push(ComponentSnapshotFns::of::<C>());
+        }
+        self
+    }
+
+    /// Registers the resource `R` to be captured by snapshots.
+    pub fn register_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        if !self.contains_resource::<R>() {
+            self.resources.push(ResourceSnapshotFns::of::<R>());
+        }
+        self
+    }
+
+    /// Returns `true` if the component `C` is captured by snapshots.
+    pub fn contains_component<C: Component>(&self) -> bool {
+        let type_id = TypeId::of::<C>();
+        self.components.iter().any(|fns| fns.type_id == type_id)
+    }
+
+    /// Returns `true` if the resource `R` is captured by snapshots.
+    pub fn contains_resource<R: Resource>(&self) -> bool {
+        let type_id = TypeId::of::<R>();
+        self.resources.iter().any(|fns| fns.type_id == type_id)
+    }
+}
+
+/// A copy of the components and resources registered in a [`SnapshotRegistry`], taken with
+/// [`World::snapshot`] and restored with [`World::restore_snapshot`].
+///
+/// A snapshot can be restored any number of times, but only into the [`World`] it was taken from.
+pub struct WorldSnapshot {
+    world_id: WorldId,
+    /// Every entity that was alive when the snapshot was taken, sorted.
+    alive: Vec<Entity>,
+    /// The entities with at least one captured component, sorted.
+    entities: Vec<Entity>,
+    components: Vec<ComponentSnapshot>,
+    resources: Vec<ResourceSnapshot>,
+}
+
+impl WorldSnapshot {
+    /// The [`WorldId`] of the [`World`] this snapshot was taken from.
+    pub fn world_id(&self) -> WorldId {
+        self.world_id
+    }
+
+    /// The entities that had at least one captured component when the snapshot was taken, sorted.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns `true` if `entity` had at least one captured component when the snapshot was taken.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.binary_search(&entity).is_ok()
+    }
+}
+
+impl World {
+    /// Captures the components and resources registered in `registry`, together with their change
+    /// ticks.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::world::SnapshotRegistry;
+    /// #[derive(Component, Clone, PartialEq, Debug)]
+    /// struct Position(f32);
+    ///
+    /// let mut registry = SnapshotRegistry::default();
+    /// registry.register_component::<Position>();
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn(Position(0.0)).id();
+    /// let snapshot = world.snapshot(&registry);
+    ///
+    /// world.entity_mut(entity).insert(Position(1.0));
+    /// world.spawn(Position(2.0));
+    ///
+    /// world.restore_snapshot(&snapshot).unwrap();
+    /// assert_eq!(world.get::<Position>(entity), Some(&Position(0.0)));
+    /// assert_eq!(world.query::<&Position>().iter(&world).count(), 1);
+    /// ```
+    pub fn snapshot(&self, registry: &SnapshotRegistry) -> WorldSnapshot {
+        let mut alive: Vec<Entity> = self
+            .archetypes()
+            .iter()
+            .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+            .collect();
+        alive.sort_unstable();
+
+        let components: Vec<ComponentSnapshot> = registry
+            .components
+            .iter()
+            .map(|fns| ComponentSnapshot {
+                fns: *fns,
+                data: (fns.component_id)(self.components())
+                    .map(|component_id| capture_component(self, component_id)),
+            })
+            .collect();
+
+        let mut entities: Vec<Entity> = components
+            .iter()
+            .filter_map(|component| component.data.as_ref())
+            .flat_map(|data| data.entities.iter().copied())
+            .collect();
+        entities.sort_unstable();
+        entities.dedup();
+
+        let resources = registry
+            .resources
+            .iter()
+            .map(|fns| ResourceSnapshot {
+                fns: *fns,
+                data: (fns.capture)(self),
+            })
+            .collect();
+
+        WorldSnapshot {
+            world_id: self.id(),
+            alive,
+            entities,
+            components,
+            resources,
+        }
+    }
+
+    /// Restores the state captured by [`World::snapshot`].
+    ///
+    /// - Entities with captured components that were spawned after the snapshot was taken are despawned.
+    /// - Entities in the snapshot that were despawned since are respawned with the same [`Entity`] id.
+    /// - Captured components and resources are written back in place with their captured change ticks.
+    ///   Immutable components are re-inserted instead, so that their hooks and observers run.
+    /// - Captured component types that were inserted since the snapshot was taken are removed again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error and leaves the world untouched if the snapshot was taken from another world,
+    /// or if the id of an entity to respawn is now used by an entity that isn't part of the snapshot.
+    pub fn restore_snapshot(
+        &mut self,
+        snapshot: &WorldSnapshot,
+    ) -> Result<(), RestoreSnapshotError> {
+        if snapshot.world_id != self.id() {
+            return Err(RestoreSnapshotError::WorldMismatch(snapshot.world_id));
+        }
+        self.flush();
+
+        // Components ignored by their clone behavior are never captured, so they are left alone.
+        let restored_id = |component: &ComponentSnapshot| {
+            let component_id = (component.fns.component_id)(self.components())?;
+            let info = self.components().get_info(component_id)?;
+            (!matches!(info.clone_behavior(), ComponentCloneBehavior::Ignore))
+                .then_some(component_id)
+        };
+        let component_ids: Vec<ComponentId> =
+            snapshot.components.iter().filter_map(restored_id).collect();
+        let snapshot_entities: Vec<(ComponentId, EntityHashSet)> = snapshot
+            .components
+            .iter()
+            .filter_map(|component| {
+                let component_id = restored_id(component)?;
+                let entities = component
+                    .data
+                    .as_ref()
+                    .map(|data| data.entities.iter().copied().collect())
+                    .unwrap_or_default();
+                Some((component_id, entities))
+            })
+            .collect();
+
+        let mut despawned = EntityHashSet::default();
+        let mut removed = Vec::new();
+        for archetype in self.archetypes().iter() {
+            if !component_ids.iter().any(|&id| archetype.contains(id)) {
+                continue;
+            }
+            for entity in archetype.entities().iter().map(ArchetypeEntity::id) {
+                if snapshot.alive.binary_search(&entity).is_err() {
+                    despawned.insert(entity);
+                    continue;
+                }
+                for (component_id, entities) in &snapshot_entities {
+                    if archetype.contains(*component_id) && !entities.contains(&entity) {
+                        removed.push((entity, *component_id));
+                    }
+                }
+            }
+        }
+
+        for &entity in &snapshot.entities {
+            if self.entities.contains(entity) {
+                continue;
+            }
+            if let Some(occupant) = self.entities.resolve_from_id(entity.row()) {
+                if self.entities.get(occupant).is_some() && !despawned.contains(&occupant) {
+                    return Err(RestoreSnapshotError::EntityRowOccupied(entity));
+                }
+            }
+        }
+
+        for entity in despawned {
+            if let Ok(entity) = self.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+        for (entity, component_id) in removed {
+            if let Ok(mut entity) = self.get_entity_mut(entity) {
+                entity.remove_by_id(component_id);
+            }
+        }
+        for &entity in &snapshot.entities {
+            if self.entities.contains(entity) {
+                continue;
+            }
+            self.flush();
+            if self.entities.alloc_at(entity) {
+                // SAFETY: the entity was just allocated
+                unsafe { self.spawn_at_empty_internal(entity, MaybeLocation::caller()) };
+            }
+        }
+
+        for component in &snapshot.components {
+            if let Some(data) = &component.data {
+                if let Some(component_id) = (component.fns.component_id)(self.components()) {
+                    restore_component(self, component_id, data);
+                }
+            }
+        }
+        for resource in &snapshot.resources {
+            (resource.fns.restore)(self, resource.data.as_ref());
+        }
+        self.flush();
+
+        Ok(())
+    }
+}
+
+struct ComponentSnapshot {
+    fns: ComponentSnapshotFns,
+    /// `None` if the component wasn't registered in the world when the snapshot was taken.
+    data: Option<ComponentSnapshotData>,
+}
+
+struct ComponentSnapshotData {
+    entities: Vec<Entity>,
+    ticks: Vec<ComponentTicks>,
+    /// One value of the component per entity.
+    values: BlobVec,
+}
+
+// SAFETY: `values` only stores values of a `Component` type, which are `Send`.
+unsafe impl Send for ComponentSnapshotData {}
+// SAFETY: `values` only stores values of a `Component` type, which are `Sync`.
+unsafe impl Sync for ComponentSnapshotData {}
+
+#[derive(Clone, Copy)]
+struct ComponentSnapshotFns {
+    type_id: TypeId,
+    component_id: fn(&Components) -> Option<ComponentId>,
+}
+
+impl ComponentSnapshotFns {
+    fn of<C: Component>() -> Self {
+        Self {
+            type_id: TypeId::of::<C>(),
+            component_id: Components::component_id::<C>,
+        }
+    }
+}
+
+fn capture_component(world: &World, component_id: ComponentId) -> ComponentSnapshotData {
+    let info = world
+        .components()
+        .get_info(component_id)
+        .expect("snapshot components are registered");
+    let allocator = Bump::new();
+    let mut entities = Vec::new();
+    let mut ticks = Vec::new();
+    // SAFETY: `drop` matches the values of the component described by `info`
+    let mut values = unsafe { BlobVec::new(info.layout(), info.drop(), 0) };
+
+    let mut push = |entity: Entity, value: Ptr<'_>, value_ticks: ComponentTicks| {
+        // SAFETY: `info` comes from `world` and `value` is a value of its component
+        let clone =
+            unsafe { EntityCloner::clone_component_value(world, info, entity, value, &allocator) };
+        if let Some(clone) = clone {
+            // SAFETY: `clone` is an owned value of the component stored in `values`
+            unsafe { values.push(clone) };
+            ticks.push(value_ticks);
+            entities.push(entity);
+        }
+    };
+
+    match info.storage_type() {
+        StorageType::Table => {
+            for table in world.storages().tables.iter() {
+                if table.get_column(component_id).is_none() {
+                    continue;
+                }
+                for (row, &entity) in table.entities().iter().enumerate() {
+                    let row = TableRow::from_usize(row);
+                    // SAFETY:
+                    // - `row` is in bounds of the table, which has a column for the component
+                    // - nothing can mutate the table while the world is borrowed immutably
+                    unsafe {
+                        push(
+                            entity,
+                            table
+                                .get_component(component_id, row)
+                                .debug_checked_unwrap(),
+                            table
+                                .get_ticks_unchecked(component_id, row)
+                                .debug_checked_unwrap(),
+                        );
+                    }
+                }
+            }
+        }
+        StorageType::SparseSet => {
+            if let Some(sparse_set) = world.storages().sparse_sets.get(component_id) {
+                let archetypes = world
+                    .archetypes()
+                    .iter()
+                    .filter(|archetype| archetype.contains(component_id));
+                for entity in archetypes.flat_map(Archetype::entities) {
+                    let Some((value, tick_cells, _)) = sparse_set.get_with_ticks(entity.id())
+                    else {
+                        continue;
+                    };
+                    // SAFETY: nothing can mutate the sparse set while the world is borrowed immutably
+                    push(entity.id(), value, unsafe { tick_cells.read() });
+                }
+            }
+        }
+    }
+
+    ComponentSnapshotData {
+        entities,
+        ticks,
+        values,
+    }
+}
+
+fn restore_component(world: &mut World, component_id: ComponentId, data: &ComponentSnapshotData) {
+    let info = world
+        .components()
+        .get_info(component_id)
+        .expect("snapshot components are registered");
+    let mutable = info.mutable();
+    let drop = info.drop();
+    let size = info.layout().size();
+    let allocator = Bump::new();
+
+    for (index, (&entity, ticks)) in data.entities.iter().zip(&data.ticks).enumerate() {
+        if !world.entities().contains(entity) {
+            continue;
+        }
+        let info = world.components().get_info(component_id).unwrap();
+        // SAFETY:
+        // - `info` comes from `world`
+        // - `values` stores one value of the component per entity
+        let value = unsafe {
+            EntityCloner::clone_component_value(
+                world,
+                info,
+                entity,
+                data.values.get_unchecked(index),
+                &allocator,
+            )
+        };
+        let Some(value) = value else {
+            continue;
+        };
+
+        let mut entity = world.entity_mut(entity);
+        if mutable && entity.contains_id(component_id) {
+            // SAFETY: the component is mutable
+            let mut component = unsafe { entity.get_mut_assume_mutable_by_id(component_id) }
+                .expect("entity contains the component");
+            let target = component.bypass_change_detection().reborrow().as_ptr();
+            // SAFETY:
+            // - `target` points to a valid value of the component, which is dropped and then overwritten
+            // - `value` is an owned value of the same component, which is moved into `target`
+            unsafe {
+                if let Some(drop) = drop {
+                    drop(OwningPtr::new(NonNull::new_unchecked(target)));
+                }
+                core::ptr::copy_nonoverlapping(value.as_ptr(), target, size);
+            }
+        } else {
+            // SAFETY: `value` is an owned value of the component
+            unsafe { entity.insert_by_id(component_id, value) };
+        }
+
+        // SAFETY: only the ticks are modified
+        if let Ok(mut component) = unsafe { entity.get_mut_assume_mutable_by_id(component_id) } {
+            component.set_last_added(ticks.added);
+            component.set_last_changed(ticks.changed);
+        }
+    }
+}
+
+struct ResourceSnapshot {
+    fns: ResourceSnapshotFns,
+    /// `None` if the resource didn't exist when the snapshot was taken.
+    data: Option<ResourceSnapshotData>,
+}
+
+struct ResourceSnapshotData {
+    ticks: ComponentTicks,
+    /// The `R` value.
+    value: Box<dyn Any + Send + Sync>,
+}
+
+#[derive(Clone, Copy)]
+struct ResourceSnapshotFns {
+    type_id: TypeId,
+    capture: fn(&World) -> Option<ResourceSnapshotData>,
+    restore: fn(&mut World, Option<&ResourceSnapshotData>),
+}
+
+impl ResourceSnapshotFns {
+    fn of<R: Resource + Clone>() -> Self {
+        Self {
+            type_id: TypeId::of::<R>(),
+            capture: capture_resource::<R>,
+            restore: restore_resource::<R>,
+        }
+    }
+}
+
+fn capture_resource<R: Resource + Clone>(world: &World) -> Option<ResourceSnapshotData> {
+    let resource = world.get_resource_ref::<R>()?;
+    Some(ResourceSnapshotData {
+        ticks: ComponentTicks {
+            added: resource.added(),
+            changed: resource.last_changed(),
+        },
+        value: Box::new(R::clone(&resource)),
+    })
+}
+
+fn restore_resource<R: Resource + Clone>(world: &mut World, data: Option<&ResourceSnapshotData>) {
+    let Some(data) = data else {
+        world.remove_resource::<R>();
+        return;
+    };
+    let value = data
+        .value
+        .downcast_ref::<R>()
+        .expect("snapshot values have the type of their resource")
+        .clone();
+    if let Some(mut resource) = world.get_resource_mut::<R>() {
+        *resource.bypass_change_detection() = value;
+    } else {
+        world.insert_resource(value);
+    }
+    let mut resource = world.resource_mut::<R>();
+    resource.set_last_added(data.ticks.added);
+    resource.set_last_changed(data.ticks.changed);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        change_detection::DetectChanges,
+        component::Component,
+        prelude::*,
+        world::{error::RestoreSnapshotError, SnapshotRegistry},
+    };
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct A(u32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(u32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[component(immutable)]
+    struct C(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct NotCaptured(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(clone_behavior = Ignore)]
+    struct Ignored(u32);
+
+    #[derive(Resource, Clone, Debug, PartialEq)]
+    struct R(u32);
+
+    fn registry() -> SnapshotRegistry {
+        let mut registry = SnapshotRegistry::default();
+        registry
+            .register_component::<A>()
+            .register_component::<B>()
+            .register_component::<C>()
+            .register_component::<Ignored>()
+            .register_resource::<R>();
+        registry
+    }
+
+    #[test]
+    fn restore_component_values() {
+        let mut world = World::new();
+        let e1 = world
+            .spawn((A(1), B(1), C(1), Ignored(1), NotCaptured(1)))
+            .id();
+        let e2 = world.spawn(A(2)).id();
+        let snapshot = world.snapshot(&registry());
+        assert!(snapshot.contains(e1) && snapshot.contains(e2));
+        assert_eq!(snapshot.entities().len(), 2);
+
+        world
+            .entity_mut(e1)
+            .insert((A(10), B(10), C(10), Ignored(10), NotCaptured(10)));
+        world.entity_mut(e2).remove::<A>().insert(B(20));
+
+        world.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(world.get::<A>(e1), Some(&A(1)));
+        assert_eq!(world.get::<B>(e1), Some(&B(1)));
+        assert_eq!(world.get::<C>(e1), Some(&C(1)));
+        assert_eq!(world.get::<Ignored>(e1), Some(&Ignored(10)));
+        assert_eq!(world.get::<NotCaptured>(e1), Some(&NotCaptured(10)));
+        assert_eq!(world.get::<A>(e2), Some(&A(2)));
+        assert_eq!(world.get::<B>(e2), None);
+
+        // Snapshots can be restored more than once.
+        world.entity_mut(e1).insert(A(100));
+        world.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(world.get::<A>(e1), Some(&A(1)));
+    }
+
+    #[test]
+    fn restore_change_ticks() {
+        let mut world = World::new();
+        let entity = world.spawn(A(1)).id();
+        world.insert_resource(R(1));
+        let ticks = world.entity(entity).get_ref::<A>().unwrap().last_changed();
+        let resource_ticks = world.resource_ref::<R>().last_changed();
+        let snapshot = world.snapshot(&registry());
+
+        world.increment_change_tick();
+        world.entity_mut(entity).get_mut::<A>().unwrap().0 = 2;
+        world.resource_mut::<R>().0 = 2;
+        assert_ne!(
+            world.entity(entity).get_ref::<A>().unwrap().last_changed(),
+            ticks
+        );
+
+        world.restore_snapshot(&snapshot).unwrap();
+        let component = world.entity(entity).get_ref::<A>().unwrap();
+        assert_eq!(component.added(), ticks);
+        assert_eq!(component.last_changed(), ticks);
+        let resource = world.resource_ref::<R>();
+        assert_eq!(*resource, R(1));
+        assert_eq!(resource.last_changed(), resource_ticks);
+    }
+
+    #[test]
+    fn restore_spawned_and_despawned_entities() {
+        let mut world = World::new();
+        let kept = world.spawn(A(1)).id();
+        let despawned = world.spawn((A(2), B(2))).id();
+        let unrelated = world.spawn(NotCaptured(0)).id();
+        let snapshot = world.snapshot(&registry());
+
+        world.despawn(despawned);
+        let spawned = world.spawn(A(3)).id();
+        let spawned_unrelated = world.spawn(NotCaptured(1)).id();
+        world.entity_mut(unrelated).insert(A(4));
+
+        world.restore_snapshot(&snapshot).unwrap();
+        assert!(world.get_entity(spawned).is_err());
+        assert!(world.get_entity(spawned_unrelated).is_ok());
+        assert_eq!(world.get::<A>(kept), Some(&A(1)));
+        assert_eq!(world.get::<A>(despawned), Some(&A(2)));
+        assert_eq!(world.get::<B>(despawned), Some(&B(2)));
+        assert_eq!(world.get::<A>(unrelated), None);
+        assert!(world.get_entity(unrelated).is_ok());
+    }
+
+    #[test]
+    fn restore_resources() {
+        let mut world = World::new();
+        let empty = world.snapshot(&registry());
+        world.insert_resource(R(1));
+        let snapshot = world.snapshot(&registry());
+
+        world.remove_resource::<R>();
+        world.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(world.get_resource::<R>(), Some(&R(1)));
+
+        world.restore_snapshot(&empty).unwrap();
+        assert_eq!(world.get_resource::<R>(), None);
+    }
+
+    #[test]
+    fn restore_into_other_world() {
+        let world = World::new();
+        let snapshot = world.snapshot(&registry());
+        let mut other = World::new();
+        assert_eq!(
+            other.restore_snapshot(&snapshot),
+            Err(RestoreSnapshotError::WorldMismatch(world.id()))
+        );
+    }
+
+    #[test]
+    fn restore_occupied_entity() {
+        let mut world = World::new();
+        let entity = world.spawn(A(1)).id();
+        let snapshot = world.snapshot(&registry());
+
+        world.despawn(entity);
+        let occupant = world.spawn(NotCaptured(0)).id();
+        assert_eq!(occupant.index(), entity.index());
+        assert_eq!(
+            world.restore_snapshot(&snapshot),
+            Err(RestoreSnapshotError::EntityRowOccupied(entity))
+        );
+        assert!(world.get_entity(occupant).is_ok());
+    }
+
+    #[test]
+    fn restore_never_rolls_back_generations() {
+        let mut world = World::new();
+        let entity = world.spawn(A(1)).id();
+        let snapshot = world.snapshot(&registry());
+
+        world.despawn(entity);
+        let reused = world.spawn(A(2)).id();
+        assert_eq!(reused.index(), entity.index());
+        world.despawn(reused);
+
+        world.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(world.get::<A>(entity), Some(&A(1)));
+        assert!(world.get_entity(reused).is_err());
+
+        // The row continues after the newest generation, so stale handles stay invalid.
+        world.despawn(entity);
+        let respawned = world.spawn(A(3)).id();
+        assert_eq!(respawned.index(), entity.index());
+        assert!(respawned.generation() > reused.generation());
+        assert!(world.get_entity(reused).is_err());
+        assert!(world.get_entity(entity).is_err());
+    }
+}
@@ -0,0 +1,720 @@
+//! Mapping of raw inputs to user-defined actions.
+//!
+//! Rather than checking for specific keys or buttons, gameplay code can define the actions a
+//! player may take, and query whether they are active through an [`ActionState`]. The inputs
+//! triggering each action are described by an [`ActionMap`], which can be changed at runtime and
+//! serialized, for example to let players rebind their controls.
+//!
+//! Both [`ActionMap`] and [`ActionState`] are components, so that each player of a local
+//! multiplayer game can have their own bindings and gamepad.
+//!
+//! ```
+//! # use bevy_app::{App, Update};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::{action::*, prelude::*};
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+//! enum PlayerAction {
+//!     Jump,
+//!     Move,
+//!     Save,
+//! }
+//!
+//! fn spawn_player(mut commands: Commands) {
+//!     let bindings = ActionMap::default()
+//!         .with(PlayerAction::Jump, KeyCode::Space)
+//!         .with(PlayerAction::Jump, GamepadButton::South)
+//!         .with(
+//!             PlayerAction::Move,
+//!             InputBinding::virtual_axis(KeyCode::KeyA, KeyCode::KeyD),
+//!         )
+//!         .with(PlayerAction::Move, GamepadAxis::LeftStickX)
+//!         .with(
+//!             PlayerAction::Save,
+//!             InputBinding::chord([KeyCode::ControlLeft, KeyCode::KeyS]),
+//!         );
+//!     commands.spawn(bindings);
+//! }
+//!
+//! fn move_player(players: Query<&ActionState<PlayerAction>>) {
+//!     for actions in &players {
+//!         if actions.just_pressed(PlayerAction::Jump) {
+//!             println!("Jump!");
+//!         }
+//!         println!("Moving at {}", actions.value(PlayerAction::Move));
+//!     }
+//! }
+//!
+//! App::new()
+//!     .add_plugins(InputActionPlugin::<PlayerAction>::default())
+//!     .add_systems(Update, move_player);
+//! ```
+
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, ButtonInput, InputSystems,
+};
+use alloc::vec::Vec;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::{Query, Res, SystemParam},
+};
+use bevy_math::ops;
+use bevy_platform::collections::HashMap;
+use core::{fmt::Debug, hash::Hash, marker::PhantomData};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A type that can be used as an action of an [`ActionMap`].
+///
+/// This is implemented for every suitable type, typically a field-less enum listing the actions
+/// of a game.
+pub trait InputAction: Debug + Copy + Eq + Hash + Send + Sync + 'static {}
+
+impl<T: Debug + Copy + Eq + Hash + Send + Sync + 'static> InputAction for T {}
+
+/// Updates the [`ActionState`] of every entity with an [`ActionMap`] for the action type `A`.
+pub struct InputActionPlugin<A: InputAction>(PhantomData<A>);
+
+impl<A: InputAction> Default for InputActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: InputAction> Plugin for InputActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            update_action_states::<A>
+                .in_set(InputActionSystems)
+                .after(InputSystems),
+        );
+    }
+}
+
+/// Label for the systems updating [`ActionState`]s, which run after [`InputSystems`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct InputActionSystems;
+
+/// An input that can trigger an action.
+///
+/// Each binding has a value: `1.0` for pressed keys and mouse buttons, the position of gamepad
+/// buttons and axes, and the combination described below for chords and virtual axes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone),
+    reflect(no_field_bounds)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputBinding {
+    /// A key of the keyboard.
+    Key(KeyCode),
+    /// A button of the mouse.
+    Mouse(MouseButton),
+    /// A button of the gamepad.
+    GamepadButton(GamepadButton),
+    /// An axis of the gamepad.
+    ///
+    /// The axis is pressed while its distance from the center reaches the
+    /// [axis threshold](ActionMap::axis_threshold) of the map.
+    GamepadAxis(GamepadAxis),
+    /// A combination of bindings, pressed while all of them are pressed.
+    ///
+    /// Its value is `1.0` while pressed, and `0.0` otherwise.
+    Chord(Vec<InputBinding>),
+    /// An axis made of bindings pulling in opposite directions, like the arrow keys.
+    ///
+    /// Its value is the largest value of the `positive` bindings, minus the largest value of the
+    /// `negative` ones. It is pressed like a [gamepad axis](Self::GamepadAxis).
+    VirtualAxis {
+        /// The bindings decreasing the value of the axis.
+        negative: Vec<InputBinding>,
+        /// The bindings increasing the value of the axis.
+        positive: Vec<InputBinding>,
+    },
+}
+
+impl InputBinding {
+    /// Creates a [chord](Self::Chord) pressed while all of the `bindings` are pressed.
+    pub fn chord(bindings: impl IntoIterator<Item = impl Into<InputBinding>>) -> Self {
+        Self::Chord(bindings.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a [virtual axis](Self::VirtualAxis) from a single binding in each direction.
+    pub fn virtual_axis(
+        negative: impl Into<InputBinding>,
+        positive: impl Into<InputBinding>,
+    ) -> Self {
+        Self::VirtualAxis {
+            negative: alloc::vec![negative.into()],
+            positive: alloc::vec![positive.into()],
+        }
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for InputBinding {
+    fn from(button: GamepadButton) -> Self {
+        Self::GamepadButton(button)
+    }
+}
+
+impl From<GamepadAxis> for InputBinding {
+    fn from(axis: GamepadAxis) -> Self {
+        Self::GamepadAxis(axis)
+    }
+}
+
+/// The bindings of the actions of type `A` for an entity, usually a player.
+///
+/// The [`InputActionPlugin`] evaluates them every frame to update the [`ActionState`] of the
+/// entity, which is added along with the map.
+///
+/// An action can have any number of bindings, and is pressed while any of them is pressed. Its
+/// value is the value of the binding furthest from zero.
+#[derive(Component, Debug, Clone, PartialEq)]
+#[require(ActionState<A>)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionMap<A: InputAction> {
+    /// The bindings of each action.
+    bindings: HashMap<A, Vec<InputBinding>>,
+    /// The gamepad read by the bindings, or `None` to read all gamepads.
+    gamepad: Option<Entity>,
+    /// The distance from the center at which axes are considered pressed.
+    axis_threshold: f32,
+}
+
+impl<A: InputAction> Default for ActionMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::default(),
+            gamepad: None,
+            axis_threshold: 0.5,
+        }
+    }
+}
+
+impl<A: InputAction> ActionMap<A> {
+    /// Adds a `binding` to the `action`, returning the map.
+    pub fn with(mut self, action: A, binding: impl Into<InputBinding>) -> Self {
+        self.bind(action, binding);
+        self
+    }
+
+    /// Only reads input from the given `gamepad`, returning the map.
+    pub fn with_gamepad(mut self, gamepad: Entity) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Adds a `binding` to the `action`, unless the action already has it.
+    pub fn bind(&mut self, action: A, binding: impl Into<InputBinding>) -> &mut Self {
+        let binding = binding.into();
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// Replaces all the bindings of the `action` with the given `binding`.
+    pub fn rebind(&mut self, action: A, binding: impl Into<InputBinding>) -> &mut Self {
+        self.bindings.insert(action, alloc::vec![binding.into()]);
+        self
+    }
+
+    /// Removes a `binding` from the `action`, returning `true` if the action had it.
+    pub fn unbind(&mut self, action: A, binding: &InputBinding) -> bool {
+        let Some(bindings) = self.bindings.get_mut(&action) else {
+            return false;
+        };
+        let len = bindings.len();
+        bindings.retain(|other| other != binding);
+        bindings.len() != len
+    }
+
+    /// Removes all the bindings of the `action`, returning them.
+    pub fn clear(&mut self, action: A) -> Vec<InputBinding> {
+        self.bindings.remove(&action).unwrap_or_default()
+    }
+
+    /// Returns the bindings of the `action`.
+    pub fn bindings(&self, action: A) -> &[InputBinding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns an iterator over the actions with bindings, and their bindings.
+    pub fn iter(&self) -> impl Iterator<Item = (A, &[InputBinding])> {
+        self.bindings
+            .iter()
+            .map(|(action, bindings)| (*action, bindings.as_slice()))
+    }
+
+    /// Returns the gamepad read by the bindings, or `None` if all gamepads are read.
+    pub fn gamepad(&self) -> Option<Entity> {
+        self.gamepad
+    }
+
+    /// Sets the gamepad read by the bindings, or `None` to read all gamepads.
+    pub fn set_gamepad(&mut self, gamepad: Option<Entity>) {
+        self.gamepad = gamepad;
+    }
+
+    /// Returns the distance from the center at which axes are considered pressed.
+    ///
+    /// Defaults to `0.5`.
+    pub fn axis_threshold(&self) -> f32 {
+        self.axis_threshold
+    }
+
+    /// Sets the distance from the center at which axes are considered pressed.
+    pub fn set_axis_threshold(&mut self, threshold: f32) {
+        self.axis_threshold = threshold;
+    }
+}
+
+/// The state of the actions of type `A` for an entity, updated from its [`ActionMap`].
+#[derive(Component, Debug)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, Default))]
+pub struct ActionState<A: InputAction> {
+    /// Whether each action is pressed.
+    buttons: ButtonInput<A>,
+    /// The value of each action.
+    values: Axis<A>,
+}
+
+impl<A: InputAction> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            buttons: ButtonInput::default(),
+            values: Axis::default(),
+        }
+    }
+}
+
+impl<A: InputAction> ActionState<A> {
+    /// Returns `true` if the `action` is pressed.
+    pub fn pressed(&self, action: A) -> bool {
+        self.buttons.pressed(action)
+    }
+
+    /// Returns `true` if the `action` has been pressed during the current frame.
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.buttons.just_pressed(action)
+    }
+
+    /// Returns `true` if the `action` has been released during the current frame.
+    pub fn just_released(&self, action: A) -> bool {
+        self.buttons.just_released(action)
+    }
+
+    /// Returns the value of the `action`, or `0.0` if it has no bindings.
+    ///
+    /// This will be clamped between [`Axis::MIN`] and [`Axis::MAX`] inclusive.
+    pub fn value(&self, action: A) -> f32 {
+        self.values.get(action).unwrap_or(0.0)
+    }
+
+    /// Returns the pressed state of every action.
+    pub fn buttons(&self) -> &ButtonInput<A> {
+        &self.buttons
+    }
+
+    /// Returns the mutable pressed state of every action, to simulate input.
+    ///
+    /// The changes are overwritten on the next update of the [`InputActionPlugin`].
+    pub fn buttons_mut(&mut self) -> &mut ButtonInput<A> {
+        &mut self.buttons
+    }
+
+    /// Returns the value of every action.
+    pub fn values(&self) -> &Axis<A> {
+        &self.values
+    }
+
+    /// Returns the mutable value of every action, to simulate input.
+    ///
+    /// The changes are overwritten on the next update of the [`InputActionPlugin`].
+    pub fn values_mut(&mut self) -> &mut Axis<A> {
+        &mut self.values
+    }
+}
+
+/// A [`SystemParam`] reading the state of every input that can be bound to an action.
+///
+/// This can be used to evaluate [`InputBinding`]s directly, or to let players pick a new binding
+/// with [`just_pressed_binding`](Self::just_pressed_binding).
+#[derive(SystemParam)]
+pub struct InputSources<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, (Entity, &'static Gamepad)>,
+}
+
+impl InputSources<'_, '_> {
+    /// Returns `true` if the `binding` is pressed.
+    ///
+    /// Gamepad bindings only read the given `gamepad`, or every gamepad if it is `None`.
+    pub fn pressed(
+        &self,
+        binding: &InputBinding,
+        gamepad: Option<Entity>,
+        axis_threshold: f32,
+    ) -> bool {
+        match binding {
+            InputBinding::Key(key) => self.keys.pressed(*key),
+            InputBinding::Mouse(button) => self.mouse_buttons.pressed(*button),
+            InputBinding::GamepadButton(button) => self
+                .gamepads(gamepad)
+                .any(|gamepad| gamepad.pressed(*button)),
+            InputBinding::GamepadAxis(_) | InputBinding::VirtualAxis { .. } => {
+                ops::abs(self.value(binding, gamepad)) >= axis_threshold
+            }
+            InputBinding::Chord(bindings) => bindings
+                .iter()
+                .all(|binding| self.pressed(binding, gamepad, axis_threshold)),
+        }
+    }
+
+    /// Returns the value of the `binding`.
+    ///
+    /// Gamepad bindings only read the given `gamepad`, or every gamepad if it is `None`, in which
+    /// case the value furthest from zero is returned.
+    pub fn value(&self, binding: &InputBinding, gamepad: Option<Entity>) -> f32 {
+        match binding {
+            InputBinding::Key(key) => button_value(self.keys.pressed(*key)),
+            InputBinding::Mouse(button) => button_value(self.mouse_buttons.pressed(*button)),
+            InputBinding::GamepadButton(button) => {
+                furthest(self.gamepads(gamepad).map(|gamepad| {
+                    gamepad
+                        .get(*button)
+                        .unwrap_or_else(|| button_value(gamepad.pressed(*button)))
+                }))
+            }
+            InputBinding::GamepadAxis(axis) => furthest(
+                self.gamepads(gamepad)
+                    .map(|gamepad| gamepad.get(*axis).unwrap_or(0.0)),
+            ),
+            // Chords have no analog value: the threshold does not matter for them.
+            InputBinding::Chord(_) => button_value(self.pressed(binding, gamepad, 0.5)),
+            InputBinding::VirtualAxis { negative, positive } => {
+                let largest = |bindings: &[InputBinding]| {
+                    bindings
+                        .iter()
+                        .map(|binding| self.value(binding, gamepad))
+                        .fold(0.0, f32::max)
+                };
+                largest(positive) - largest(negative)
+            }
+        }
+    }
+
+    /// Returns a key, mouse button or gamepad button pressed during the current frame, if any.
+    ///
+    /// This is useful to let players choose a new binding for an action, by waiting for the next
+    /// input. Only buttons of the given `gamepad` are returned, or of every gamepad if it is `None`.
+    pub fn just_pressed_binding(&self, gamepad: Option<Entity>) -> Option<InputBinding> {
+        let key = self.keys.get_just_pressed().next().copied().map(Into::into);
+        let mouse_button = || {
+            self.mouse_buttons
+                .get_just_pressed()
+                .next()
+                .copied()
+                .map(Into::into)
+        };
+        let gamepad_button = || {
+            self.gamepads(gamepad)
+                .find_map(|gamepad| gamepad.get_just_pressed().next().copied())
+                .map(Into::into)
+        };
+        key.or_else(mouse_button).or_else(gamepad_button)
+    }
+
+    fn gamepads(&self, gamepad: Option<Entity>) -> impl Iterator<Item = &Gamepad> {
+        self.gamepads
+            .iter()
+            .filter(move |(entity, _)| gamepad.is_none_or(|gamepad| gamepad == *entity))
+            .map(|(_, gamepad)| gamepad)
+    }
+}
+
+fn button_value(pressed: bool) -> f32 {
+    if pressed {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Returns the value furthest from zero, or `0.0` if there are none.
+fn furthest(values: impl Iterator<Item = f32>) -> f32 {
+    values.fold(0.0, |furthest, value| {
+        if ops::abs(value) > ops::abs(furthest) {
+            value
+        } else {
+            furthest
+        }
+    })
+}
+
+/// Updates the [`ActionState`] of each entity from its [`ActionMap`].
+pub fn update_action_states<A: InputAction>(
+    sources: InputSources,
+    mut players: Query<(&ActionMap<A>, &mut ActionState<A>)>,
+) {
+    for (map, mut state) in &mut players {
+        let state = &mut *state;
+        state.buttons.clear();
+
+        for (action, bindings) in map.iter() {
+            let pressed = bindings
+                .iter()
+                .any(|binding| sources.pressed(binding, map.gamepad, map.axis_threshold));
+            if pressed {
+                state.buttons.press(action);
+            } else {
+                state.buttons.release(action);
+            }
+            let value = furthest(
+                bindings
+                    .iter()
+                    .map(|binding| sources.value(binding, map.gamepad)),
+            );
+            state.values.set(action, value);
+        }
+
+        // Release the actions whose bindings have been removed.
+        let unbound = state
+            .values
+            .all_axes()
+            .filter(|action| map.bindings(**action).is_empty())
+            .copied()
+            .collect::<Vec<_>>();
+        for action in unbound {
+            state.buttons.release(action);
+            state.values.remove(action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputPlugin;
+    use bevy_app::App;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "bevy_reflect", derive(Reflect))]
+    enum Action {
+        Jump,
+        Move,
+        Save,
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputActionPlugin::<Action>::default()));
+        app
+    }
+
+    fn state(app: &App, entity: Entity) -> &ActionState<Action> {
+        app.world().get::<ActionState<Action>>(entity).unwrap()
+    }
+
+    #[test]
+    fn buttons_and_chords() {
+        let mut app = app();
+        let player = app
+            .world_mut()
+            .spawn(
+                ActionMap::default()
+                    .with(Action::Jump, KeyCode::Space)
+                    .with(Action::Jump, MouseButton::Left)
+                    .with(
+                        Action::Save,
+                        InputBinding::chord([KeyCode::ControlLeft, KeyCode::KeyS]),
+                    ),
+            )
+            .id();
+
+        app.update();
+        assert!(!state(&app, player).pressed(Action::Jump));
+
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(KeyCode::Space);
+        keys.press(KeyCode::KeyS);
+        app.update();
+        let state_ = state(&app, player);
+        assert!(state_.just_pressed(Action::Jump));
+        assert_eq!(state_.value(Action::Jump), 1.0);
+        assert!(!state_.pressed(Action::Save));
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ControlLeft);
+        app.update();
+        let state_ = state(&app, player);
+        assert!(state_.pressed(Action::Jump));
+        assert!(!state_.just_pressed(Action::Jump));
+        assert!(state_.just_pressed(Action::Save));
+
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release(KeyCode::Space);
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+        // Another binding of the action is pressed.
+        assert!(state(&app, player).pressed(Action::Jump));
+        assert!(!state(&app, player).just_released(Action::Jump));
+    }
+
+    #[test]
+    fn axes() {
+        let mut app = app();
+        let player = app
+            .world_mut()
+            .spawn(
+                ActionMap::default()
+                    .with(
+                        Action::Move,
+                        InputBinding::virtual_axis(KeyCode::KeyA, KeyCode::KeyD),
+                    )
+                    .with(Action::Move, GamepadAxis::LeftStickX),
+            )
+            .id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyA);
+        app.update();
+        assert!(state(&app, player).pressed(Action::Move));
+        assert_eq!(state(&app, player).value(Action::Move), -1.0);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyD);
+        app.update();
+        assert!(state(&app, player).just_released(Action::Move));
+        assert_eq!(state(&app, player).value(Action::Move), 0.0);
+
+        let mut gamepad = Gamepad::default();
+        gamepad.analog_mut().set(GamepadAxis::LeftStickX, 0.3);
+        let gamepad = app.world_mut().spawn(gamepad).id();
+        app.update();
+        assert!(!state(&app, player).pressed(Action::Move));
+        assert_eq!(state(&app, player).value(Action::Move), 0.3);
+
+        app.world_mut()
+            .get_mut::<ActionMap<Action>>(player)
+            .unwrap()
+            .set_axis_threshold(0.2);
+        app.update();
+        assert!(state(&app, player).just_pressed(Action::Move));
+
+        app.world_mut().despawn(gamepad);
+        app.update();
+        assert!(state(&app, player).just_released(Action::Move));
+    }
+
+    #[test]
+    fn local_multiplayer() {
+        let mut app = app();
+        let first_gamepad = app.world_mut().spawn(Gamepad::default()).id();
+        let second_gamepad = app.world_mut().spawn(Gamepad::default()).id();
+        let bindings = ActionMap::default().with(Action::Jump, GamepadButton::South);
+        let first = app
+            .world_mut()
+            .spawn(bindings.clone().with_gamepad(first_gamepad))
+            .id();
+        let second = app
+            .world_mut()
+            .spawn(bindings.with_gamepad(second_gamepad))
+            .id();
+
+        app.world_mut()
+            .get_mut::<Gamepad>(second_gamepad)
+            .unwrap()
+            .digital_mut()
+            .press(GamepadButton::South);
+        app.update();
+        assert!(!state(&app, first).pressed(Action::Jump));
+        assert!(state(&app, second).just_pressed(Action::Jump));
+    }
+
+    #[test]
+    fn rebinding() {
+        let mut app = app();
+        let player = app
+            .world_mut()
+            .spawn(ActionMap::default().with(Action::Jump, KeyCode::Space))
+            .id();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        app.update();
+        assert!(state(&app, player).pressed(Action::Jump));
+
+        // Wait for the next input to pick the new binding.
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyW);
+        let binding = app
+            .world_mut()
+            .run_system_cached(|sources: InputSources| sources.just_pressed_binding(None))
+            .unwrap();
+        assert_eq!(binding, Some(InputBinding::Key(KeyCode::KeyW)));
+
+        let mut map = app
+            .world_mut()
+            .get_mut::<ActionMap<Action>>(player)
+            .unwrap();
+        map.rebind(Action::Jump, binding.unwrap());
+        assert_eq!(
+            map.bindings(Action::Jump),
+            &[InputBinding::Key(KeyCode::KeyW)]
+        );
+        assert!(!map.unbind(Action::Jump, &InputBinding::Key(KeyCode::Space)));
+        app.update();
+        assert!(state(&app, player).pressed(Action::Jump));
+
+        app.world_mut()
+            .get_mut::<ActionMap<Action>>(player)
+            .unwrap()
+            .clear(Action::Jump);
+        app.update();
+        assert!(state(&app, player).just_released(Action::Jump));
+        assert_eq!(state(&app, player).value(Action::Jump), 0.0);
+    }
+}
@@ -17,6 +17,7 @@ extern crate std;
 
 extern crate alloc;
 
+pub mod action;
 mod axis;
 mod button_input;
 /// Common run conditions
@@ -36,6 +37,7 @@ pub use button_input::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        action::{ActionMap, ActionState, InputActionPlugin, InputBinding},
         gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadSettings},
         keyboard::KeyCode,
         mouse::MouseButton,
@@ -44,6 +46,8 @@ pub mod prelude {
     };
 }
 
+#[cfg(feature = "bevy_reflect")]
+use action::InputBinding;
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 #[cfg(feature = "bevy_reflect")]
@@ -137,6 +141,7 @@ impl Plugin for InputPlugin {
         {
             // Register common types
             app.register_type::<ButtonState>()
+                .register_type::<InputBinding>()
                 .register_type::<KeyboardInput>()
                 .register_type::<MouseButtonInput>()
                 .register_type::<PinchGesture>()
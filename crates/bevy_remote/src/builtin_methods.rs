@@ -9,8 +9,9 @@ use bevy_ecs::{
     event::EventCursor,
     hierarchy::ChildOf,
     query::QueryBuilder,
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectEvent, ReflectResource},
     removal_detection::RemovedComponentEntity,
+    schedule::{InternedScheduleLabel, NodeId, Schedule, Schedules, Stepping},
     system::{In, Local},
     world::{EntityRef, EntityWorldMut, FilteredEntityRef, World},
 };
@@ -82,6 +83,24 @@ pub const BRP_LIST_RESOURCES_METHOD: &str = "bevy/list_resources";
 /// The method path for a `bevy/registry/schema` request.
 pub const BRP_REGISTRY_SCHEMA_METHOD: &str = "bevy/registry/schema";
 
+/// The method path for a `bevy/send_event` request.
+pub const BRP_SEND_EVENT_METHOD: &str = "bevy/send_event";
+
+/// The method path for a `bevy/trigger` request.
+pub const BRP_TRIGGER_METHOD: &str = "bevy/trigger";
+
+/// The method path for a `bevy/list_schedules` request.
+pub const BRP_LIST_SCHEDULES_METHOD: &str = "bevy/list_schedules";
+
+/// The method path for a `bevy/list_systems` request.
+pub const BRP_LIST_SYSTEMS_METHOD: &str = "bevy/list_systems";
+
+/// The method path for a `bevy/run_system` request.
+pub const BRP_RUN_SYSTEM_METHOD: &str = "bevy/run_system";
+
+/// The method path for a `bevy/stepping` request.
+pub const BRP_STEPPING_METHOD: &str = "bevy/stepping";
+
 /// The method path for a `rpc.discover` request.
 pub const RPC_DISCOVER_METHOD: &str = "rpc.discover";
 
@@ -227,6 +246,40 @@ pub struct BrpInsertResourceParams {
     pub value: Value,
 }
 
+/// `bevy/send_event`: Sends an event, to be read by event readers.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSendEventParams {
+    /// The [full path] of the event type to send.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub event: String,
+
+    /// The serialized value of the event to be sent.
+    pub value: Value,
+}
+
+/// `bevy/trigger`: Triggers an event, running the observers watching for it.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpTriggerParams {
+    /// The [full path] of the event type to trigger.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub event: String,
+
+    /// The serialized value of the event to be triggered.
+    pub value: Value,
+
+    /// The entities to trigger the event for.
+    ///
+    /// If empty, only global observers are run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<Entity>,
+}
+
 /// `bevy/reparent`: Assign a new parent to one or more entities.
 ///
 /// The server responds with a null.
@@ -295,6 +348,57 @@ pub struct BrpMutateResourceParams {
     pub value: Value,
 }
 
+/// `bevy/list_systems`: Lists the systems and system sets of a schedule, along with the edges
+/// between them.
+///
+/// The server responds with a [`BrpListSystemsResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpListSystemsParams {
+    /// The name of the schedule, as listed by `bevy/list_schedules`.
+    pub schedule: String,
+}
+
+/// `bevy/run_system`: Runs a registered one-shot system.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpRunSystemParams {
+    /// The full name of the system, such as `my_game::systems::spawn_wave`.
+    ///
+    /// Only systems that take no input and return `()` can be run.
+    pub system: String,
+}
+
+/// `bevy/stepping`: Controls system stepping, and reports its state.
+///
+/// The server responds with a [`BrpSteppingResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpSteppingParams {
+    /// The action to take, if any.
+    #[serde(default)]
+    pub action: Option<BrpSteppingAction>,
+
+    /// The names of schedules to enable stepping for.
+    #[serde(default)]
+    pub schedules: Vec<String>,
+}
+
+/// An action that can be taken with a `bevy/stepping` request.
+///
+/// Actions are applied at the start of the next frame.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpSteppingAction {
+    /// Start stepping through the systems of the stepping schedules.
+    Enable,
+    /// Stop stepping and resume normal execution.
+    Disable,
+    /// Run the next system.
+    Step,
+    /// Run all remaining systems of the frame, stopping at breakpoints.
+    Continue,
+}
+
 /// Describes the data that is to be fetched in a query.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct BrpQuery {
@@ -434,6 +538,71 @@ pub type BrpListResponse = Vec<String>;
 /// The response to a `bevy/list_resources` request.
 pub type BrpListResourcesResponse = Vec<String>;
 
+/// The response to a `bevy/list_schedules` request.
+pub type BrpListSchedulesResponse = Vec<String>;
+
+/// Identifies a system or system set within a schedule.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpScheduleNodeId {
+    /// The index of a system.
+    System(usize),
+    /// The index of a system set.
+    Set(usize),
+}
+
+impl From<NodeId> for BrpScheduleNodeId {
+    fn from(id: NodeId) -> Self {
+        match id {
+            NodeId::System(index) => Self::System(index),
+            NodeId::Set(index) => Self::Set(index),
+        }
+    }
+}
+
+/// A system or system set in a `bevy/list_systems` response.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleNode {
+    /// The ID of the system or system set within its schedule.
+    pub id: BrpScheduleNodeId,
+    /// The name of the system or system set.
+    pub name: String,
+}
+
+/// The response to a `bevy/list_systems` request.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpListSystemsResponse {
+    /// The systems of the schedule, in the order they were last run in if the schedule has been
+    /// initialized.
+    pub systems: Vec<BrpScheduleNode>,
+    /// The system sets of the schedule.
+    pub sets: Vec<BrpScheduleNode>,
+    /// Pairs of a system set and a system or set it contains.
+    pub hierarchy: Vec<(BrpScheduleNodeId, BrpScheduleNodeId)>,
+    /// Pairs of systems or sets where the first must run before the second.
+    pub dependencies: Vec<(BrpScheduleNodeId, BrpScheduleNodeId)>,
+}
+
+/// The response to a `bevy/stepping` request.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingResponse {
+    /// Whether stepping is enabled.
+    pub enabled: bool,
+    /// The schedules stepping is enabled for, in the order they run in.
+    pub schedules: Vec<String>,
+    /// The system that will run on the next step, if stepping is enabled.
+    pub cursor: Option<BrpSteppingCursor>,
+}
+
+/// The position of [`Stepping`] within a frame.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingCursor {
+    /// The name of the schedule containing the next system.
+    pub schedule: String,
+    /// The next system to run.
+    pub system: BrpScheduleNode,
+}
+
 /// A single response from a `bevy/list+watch` request.
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 pub struct BrpListWatchingResponse {
@@ -898,6 +1067,61 @@ pub fn process_remote_insert_resource_request(
     Ok(Value::Null)
 }
 
+/// Handles a `bevy/send_event` request coming from a client.
+pub fn process_remote_send_event_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSendEventParams {
+        event: event_path,
+        value,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let reflected_event =
+        deserialize_event(&type_registry, &event_path, value).map_err(BrpError::event_error)?;
+
+    let reflect_event =
+        get_reflect_event(&type_registry, &event_path).map_err(BrpError::event_error)?;
+    if !reflect_event.send(world, &*reflected_event, &type_registry) {
+        return Err(BrpError::event_error(format!(
+            "Event `{event_path}` can't be sent, as it hasn't been added to the app"
+        )));
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/trigger` request coming from a client.
+pub fn process_remote_trigger_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpTriggerParams {
+        event: event_path,
+        value,
+        targets,
+    } = parse_some(params)?;
+
+    for &target in &targets {
+        get_entity(world, target)?;
+    }
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let reflected_event =
+        deserialize_event(&type_registry, &event_path, value).map_err(BrpError::event_error)?;
+
+    let reflect_event =
+        get_reflect_event(&type_registry, &event_path).map_err(BrpError::event_error)?;
+    reflect_event.trigger(world, &*reflected_event, &targets, &type_registry);
+
+    Ok(Value::Null)
+}
+
 /// Handles a `bevy/mutate_component` request coming from a client.
 ///
 /// This method allows you to mutate a single field inside an Entity's
@@ -1248,6 +1472,197 @@ pub fn export_registry_types(In(params): In<Option<Value>>, world: &World) -> Br
     serde_json::to_value(schemas).map_err(BrpError::internal)
 }
 
+/// Handles a `bevy/list_schedules` request coming from a client.
+pub fn process_remote_list_schedules_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let mut response = BrpListSchedulesResponse::default();
+
+    if let Some(schedules) = world.get_resource::<Schedules>() {
+        for (label, _) in schedules.iter() {
+            response.push(format!("{label:?}"));
+        }
+    }
+
+    response.sort();
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/list_systems` request coming from a client.
+pub fn process_remote_list_systems_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpListSystemsParams { schedule } = parse_some(params)?;
+    let schedule = get_schedule(world, &schedule)?;
+    let graph = schedule.graph();
+
+    let mut response = BrpListSystemsResponse::default();
+
+    // Once a schedule is initialized, its systems are moved out of the graph.
+    match schedule.systems() {
+        Ok(systems) => {
+            for (id, system) in systems {
+                response.systems.push(BrpScheduleNode {
+                    id: id.into(),
+                    name: system.name().into_owned(),
+                });
+            }
+        }
+        Err(_) => {
+            for (id, system, _) in graph.systems() {
+                response.systems.push(BrpScheduleNode {
+                    id: id.into(),
+                    name: system.name().into_owned(),
+                });
+            }
+        }
+    }
+
+    let mut sets = graph.system_sets().collect::<Vec<_>>();
+    sets.sort_by_key(|(id, _, _)| id.index());
+    for (id, set, _) in sets {
+        response.sets.push(BrpScheduleNode {
+            id: id.into(),
+            name: format!("{set:?}"),
+        });
+    }
+
+    response.hierarchy = graph
+        .hierarchy()
+        .graph()
+        .all_edges()
+        .map(|(parent, child)| (parent.into(), child.into()))
+        .collect();
+    response.dependencies = graph
+        .dependency()
+        .graph()
+        .all_edges()
+        .map(|(before, after)| (before.into(), after.into()))
+        .collect();
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/run_system` request coming from a client.
+pub fn process_remote_run_system_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpRunSystemParams { system } = parse_some(params)?;
+
+    let mut matching = world
+        .registered_systems::<(), ()>()
+        .into_iter()
+        .filter(|(_, name)| *name == system);
+    let (id, _) = matching
+        .next()
+        .ok_or_else(|| BrpError::system_not_found(&system))?;
+    if matching.next().is_some() {
+        return Err(BrpError::system_error(format!(
+            "More than one system named `{system}` is registered"
+        )));
+    }
+
+    world.run_system(id).map_err(BrpError::system_error)?;
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping` request coming from a client.
+pub fn process_remote_stepping_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingParams { action, schedules } = match params {
+        Some(params) => parse(params)?,
+        None => Default::default(),
+    };
+
+    if !world.contains_resource::<Stepping>() {
+        return Err(BrpError::resource_not_present("Stepping"));
+    }
+    let labels = schedules
+        .iter()
+        .map(|schedule| get_schedule_label(world, schedule))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if action.is_some() || !labels.is_empty() {
+        let mut stepping = world.resource_mut::<Stepping>();
+        for label in labels {
+            stepping.add_schedule(label);
+        }
+        if let Some(action) = action {
+            match action {
+                BrpSteppingAction::Enable => stepping.enable(),
+                BrpSteppingAction::Disable => stepping.disable(),
+                BrpSteppingAction::Step => stepping.step_frame(),
+                BrpSteppingAction::Continue => stepping.continue_frame(),
+            };
+        }
+    }
+
+    let stepping = world.resource::<Stepping>();
+    let response = BrpSteppingResponse {
+        enabled: stepping.is_enabled(),
+        schedules: stepping
+            .schedules()
+            .map(|labels| labels.iter().map(|label| format!("{label:?}")).collect())
+            .unwrap_or_default(),
+        cursor: stepping.cursor().map(|(label, id)| {
+            let name = world
+                .get_resource::<Schedules>()
+                .and_then(|schedules| schedules.get(label))
+                .and_then(|schedule| schedule.systems().ok())
+                .and_then(|mut systems| systems.find(|(system_id, _)| *system_id == id))
+                .map(|(_, system)| system.name().into_owned())
+                .unwrap_or_default();
+            BrpSteppingCursor {
+                schedule: format!("{label:?}"),
+                system: BrpScheduleNode {
+                    id: id.into(),
+                    name,
+                },
+            }
+        }),
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Resolves the name of a schedule in [`Schedules`] to its [`InternedScheduleLabel`], returning an
+/// error if there is no schedule with that name.
+fn get_schedule_label(world: &World, name: &str) -> Result<InternedScheduleLabel, BrpError> {
+    world
+        .get_resource::<Schedules>()
+        .and_then(|schedules| {
+            schedules
+                .iter()
+                .map(|(_, schedule)| schedule.label())
+                .find(|label| format!("{label:?}") == name)
+        })
+        .ok_or_else(|| BrpError::schedule_not_found(name))
+}
+
+/// Retrieves a schedule from the [`World`] by name, returning an error if it
+/// isn't present.
+///
+/// Schedules that are currently running, such as the one processing remote
+/// requests, can't be retrieved.
+fn get_schedule<'w>(world: &'w World, name: &str) -> Result<&'w Schedule, BrpError> {
+    world
+        .get_resource::<Schedules>()
+        .and_then(|schedules| {
+            schedules
+                .iter()
+                .find(|(label, _)| format!("{label:?}") == name)
+        })
+        .map(|(_, schedule)| schedule)
+        .ok_or_else(|| BrpError::schedule_not_found(name))
+}
+
 /// Immutably retrieves an entity from the [`World`], returning an error if the
 /// entity isn't present.
 fn get_entity(world: &World, entity: Entity) -> Result<EntityRef<'_>, BrpError> {
@@ -1406,6 +1821,23 @@ fn deserialize_resource(
     Ok(reflected)
 }
 
+/// Given an event's type path and its serialized value, deserialize it using
+/// the given `type_registry`.
+fn deserialize_event(
+    type_registry: &TypeRegistry,
+    event_path: &str,
+    value: Value,
+) -> AnyhowResult<Box<dyn PartialReflect>> {
+    let Some(event_type) = type_registry.get_with_type_path(event_path) else {
+        return Err(anyhow!("Unknown event type: `{}`", event_path));
+    };
+    let reflected: Box<dyn PartialReflect> =
+        TypedReflectDeserializer::new(event_type, type_registry)
+            .deserialize(&value)
+            .map_err(|err| anyhow!("{event_path} is invalid: {err}"))?;
+    Ok(reflected)
+}
+
 /// Given a collection `reflect_components` of reflected component values, insert them into
 /// the given entity (`entity_world_mut`).
 fn insert_reflected_components(
@@ -1470,6 +1902,19 @@ fn get_resource_type_registration<'r>(
         .ok_or_else(|| anyhow!("Unknown resource type: `{}`", resource_path))
 }
 
+/// Given an event's type path, return the associated [`ReflectEvent`] from the given
+/// `type_registry` if possible.
+fn get_reflect_event<'r>(
+    type_registry: &'r TypeRegistry,
+    event_path: &str,
+) -> AnyhowResult<&'r ReflectEvent> {
+    type_registry
+        .get_with_type_path(event_path)
+        .ok_or_else(|| anyhow!("Unknown event type: `{}`", event_path))?
+        .data::<ReflectEvent>()
+        .ok_or_else(|| anyhow!("Event `{}` isn't reflectable", event_path))
+}
+
 #[cfg(test)]
 mod tests {
     /// A generic function that tests serialization and deserialization of any type
@@ -1513,5 +1958,192 @@ mod tests {
         test_serialize_deserialize(BrpListParams {
             entity: Entity::from_raw_u32(0).unwrap(),
         });
+        test_serialize_deserialize(BrpSteppingParams {
+            action: Some(BrpSteppingAction::Step),
+            schedules: vec!["Update".to_owned()],
+        });
+        test_serialize_deserialize(BrpListSystemsResponse {
+            systems: vec![BrpScheduleNode {
+                id: BrpScheduleNodeId::System(0),
+                name: "a".to_owned(),
+            }],
+            sets: Vec::new(),
+            hierarchy: Vec::new(),
+            dependencies: vec![(BrpScheduleNodeId::Set(0), BrpScheduleNodeId::System(0))],
+        });
+    }
+
+    #[test]
+    fn list_systems() {
+        use bevy_ecs::schedule::{IntoScheduleConfigs, ScheduleLabel};
+
+        #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+        struct TestSchedule;
+
+        fn first() {}
+        fn second() {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((first, second).chain());
+        world.add_schedule(schedule);
+
+        let schedules = world
+            .run_system_cached_with(process_remote_list_schedules_request, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(schedules, serde_json::json!(["TestSchedule"]));
+
+        let params = Some(serde_json::json!({ "schedule": "TestSchedule" }));
+        let response: BrpListSystemsResponse = serde_json::from_value(
+            world
+                .run_system_cached_with(process_remote_list_systems_request, params)
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        let id_of = |name: &str| {
+            response
+                .systems
+                .iter()
+                .find(|system| system.name.ends_with(name))
+                .unwrap()
+                .id
+        };
+        assert_eq!(response.systems.len(), 2);
+        assert!(response
+            .dependencies
+            .contains(&(id_of("first"), id_of("second"))));
+
+        let params = Some(serde_json::json!({ "schedule": "Missing" }));
+        let error = world
+            .run_system_cached_with(process_remote_list_systems_request, params)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::SCHEDULE_NOT_FOUND);
+    }
+
+    #[test]
+    fn stepping() {
+        use bevy_ecs::schedule::ScheduleLabel;
+
+        #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+        struct TestSchedule;
+
+        let mut world = World::new();
+        world.add_schedule(Schedule::new(TestSchedule));
+
+        let params = Some(serde_json::json!({ "action": "enable" }));
+        let error = world
+            .run_system_cached_with(process_remote_stepping_request, params)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::RESOURCE_NOT_PRESENT);
+
+        world.init_resource::<Stepping>();
+        let params = Some(serde_json::json!({ "schedules": ["Missing"] }));
+        let error = world
+            .run_system_cached_with(process_remote_stepping_request, params)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::SCHEDULE_NOT_FOUND);
+
+        let params = Some(serde_json::json!({ "schedules": ["TestSchedule"] }));
+        let response: BrpSteppingResponse = serde_json::from_value(
+            world
+                .run_system_cached_with(process_remote_stepping_request, params)
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(!response.enabled);
+    }
+
+    #[test]
+    fn send_and_trigger_event() {
+        use bevy_ecs::{
+            event::{Event, Events},
+            observer::Trigger,
+            resource::Resource,
+            system::ResMut,
+        };
+        use bevy_reflect::{Reflect, TypePath};
+
+        #[derive(Event, Reflect, Debug, PartialEq)]
+        #[reflect(Event)]
+        struct Ping(u32);
+
+        #[derive(Resource, Default)]
+        struct Triggered(Vec<(u32, Entity)>);
+
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Ping>();
+        world.insert_resource(registry);
+        world.init_resource::<Triggered>();
+
+        // Sending fails until the event has been added.
+        let params = Some(serde_json::json!({ "event": Ping::type_path(), "value": 1 }));
+        let error = world
+            .run_system_cached_with(process_remote_send_event_request, params.clone())
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::EVENT_ERROR);
+
+        world.init_resource::<Events<Ping>>();
+        world
+            .run_system_cached_with(process_remote_send_event_request, params)
+            .unwrap()
+            .unwrap();
+        let events = world.resource::<Events<Ping>>();
+        let mut cursor = events.get_cursor();
+        assert_eq!(cursor.read(events).collect::<Vec<_>>(), [&Ping(1)]);
+
+        world.add_observer(|trigger: Trigger<Ping>, mut triggered: ResMut<Triggered>| {
+            triggered.0.push((trigger.event().0, trigger.target()));
+        });
+        let target = world.spawn_empty().id();
+        let params = Some(serde_json::json!({
+            "event": Ping::type_path(),
+            "value": 2,
+            "targets": [target],
+        }));
+        world
+            .run_system_cached_with(process_remote_trigger_request, params)
+            .unwrap()
+            .unwrap();
+        assert_eq!(world.resource::<Triggered>().0, [(2, target)]);
+    }
+
+    #[test]
+    fn run_system() {
+        use bevy_ecs::resource::Resource;
+
+        #[derive(Resource, Default)]
+        struct Counter(u32);
+
+        fn count(mut counter: bevy_ecs::system::ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(count);
+        let name = world.registered_systems::<(), ()>()[0].1.clone();
+        assert_eq!(world.registered_systems::<(), ()>()[0].0, id);
+
+        let params = Some(serde_json::json!({ "system": name }));
+        world
+            .run_system_cached_with(process_remote_run_system_request, params)
+            .unwrap()
+            .unwrap();
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        let params = Some(serde_json::json!({ "system": "missing" }));
+        let error = world
+            .run_system_cached_with(process_remote_run_system_request, params)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::SYSTEM_NOT_FOUND);
     }
 }
@@ -6,6 +6,11 @@
 //! over HTTP. These *remote clients* can inspect and alter the state of the
 //! entity-component system.
 //!
+//! With the `websocket` feature enabled, the `RemoteWebSocketPlugin` accepts
+//! WebSocket connections instead. Over a WebSocket, responses to watching methods
+//! like `bevy/get+watch` are pushed to the client as they happen, and a watch can be
+//! stopped with `bevy/unwatch` without closing the connection.
+//!
 //! The Bevy Remote Protocol is based on the JSON-RPC 2.0 protocol.
 //!
 //! ## Request objects
@@ -321,6 +326,77 @@
 //!
 //! `result`: An array of [fully-qualified type names] of registered resource types.
 //!
+//! ### `bevy/send_event`
+//!
+//! Send an event, to be read by [`EventReader`]s. The event type must be registered with
+//! `#[reflect(Event)]`, and added to the app with `App::add_event`.
+//!
+//! `params`:
+//! - `event`: The [fully-qualified type name] of the event to send.
+//! - `value`: The value of the event to send.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/trigger`
+//!
+//! Trigger an event, running the [`Observer`]s watching for it. The event type must be registered
+//! with `#[reflect(Event)]`.
+//!
+//! `params`:
+//! - `event`: The [fully-qualified type name] of the event to trigger.
+//! - `value`: The value of the event to trigger.
+//! - `targets` (optional): An array of entity IDs to trigger the event for. If omitted, only
+//!   global observers are run.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/list_schedules`
+//!
+//! List the names of all schedules. Schedules that are currently running, such as the one
+//! processing remote requests, are not included. This method has no parameters.
+//!
+//! `result`: An array of schedule names.
+//!
+//! ### `bevy/list_systems`
+//!
+//! List the systems and system sets of a schedule, along with the edges between them.
+//! Systems and sets are identified by objects like `{ "system": 3 }` or `{ "set": 1 }`.
+//!
+//! `params`:
+//! - `schedule`: The name of the schedule, as returned by `bevy/list_schedules`.
+//!
+//! `result`:
+//! - `systems`: An array of objects with the `id` and `name` of each system. Once the schedule
+//!   has been initialized, the systems are listed in the order they run in.
+//! - `sets`: An array of objects with the `id` and `name` of each system set.
+//! - `hierarchy`: An array of `[set, child]` pairs, where `child` is a system or set in `set`.
+//! - `dependencies`: An array of `[before, after]` pairs, where `before` must run before `after`.
+//!
+//! ### `bevy/run_system`
+//!
+//! Run a one-shot system registered with [`World::register_system`]. Only systems that take no
+//! input and return `()` can be run.
+//!
+//! `params`:
+//! - `system`: The full name of the system, such as `my_game::systems::spawn_wave`.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping`
+//!
+//! Control system stepping through the [`Stepping`] resource, which must be present in the world.
+//! Stepping only has an effect if Bevy was compiled with the `bevy_debug_stepping` feature.
+//!
+//! `params` (optional):
+//! - `action` (optional): One of `enable`, `disable`, `step` or `continue`. Actions take effect
+//!   at the start of the next frame.
+//! - `schedules` (optional): An array of names of schedules to enable stepping for.
+//!
+//! `result`:
+//! - `enabled`: Whether stepping is enabled.
+//! - `schedules`: The names of the schedules stepping is enabled for, in the order they run in.
+//! - `cursor`: The `schedule` and `system` that will run on the next step, or null.
+//!
 //! ## Custom methods
 //!
 //! In addition to the provided methods, the Bevy Remote Protocol can be extended to include custom
@@ -363,6 +439,10 @@
 //! [the `serde` documentation]: https://serde.rs/
 //! [fully-qualified type names]: bevy_reflect::TypePath::type_path
 //! [fully-qualified type name]: bevy_reflect::TypePath::type_path
+//! [`EventReader`]: bevy_ecs::event::EventReader
+//! [`Observer`]: bevy_ecs::observer::Observer
+//! [`World::register_system`]: bevy_ecs::world::World::register_system
+//! [`Stepping`]: bevy_ecs::schedule::Stepping
 
 use async_channel::{Receiver, Sender};
 use bevy_app::{prelude::*, MainScheduleOrder};
@@ -384,6 +464,8 @@ pub mod builtin_methods;
 #[cfg(feature = "http")]
 pub mod http;
 pub mod schemas;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 const CHANNEL_SIZE: usize = 16;
 
@@ -511,6 +593,30 @@ impl Default for RemotePlugin {
                 builtin_methods::BRP_REGISTRY_SCHEMA_METHOD,
                 builtin_methods::export_registry_types,
             )
+            .with_method(
+                builtin_methods::BRP_SEND_EVENT_METHOD,
+                builtin_methods::process_remote_send_event_request,
+            )
+            .with_method(
+                builtin_methods::BRP_TRIGGER_METHOD,
+                builtin_methods::process_remote_trigger_request,
+            )
+            .with_method(
+                builtin_methods::BRP_LIST_SCHEDULES_METHOD,
+                builtin_methods::process_remote_list_schedules_request,
+            )
+            .with_method(
+                builtin_methods::BRP_LIST_SYSTEMS_METHOD,
+                builtin_methods::process_remote_list_systems_request,
+            )
+            .with_method(
+                builtin_methods::BRP_RUN_SYSTEM_METHOD,
+                builtin_methods::process_remote_run_system_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_METHOD,
+                builtin_methods::process_remote_stepping_request,
+            )
     }
 }
 
@@ -818,6 +924,46 @@ impl BrpError {
         }
     }
 
+    /// An arbitrary event error. Possibly related to reflection.
+    #[must_use]
+    pub fn event_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::EVENT_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// Schedule was not present in the world.
+    #[must_use]
+    pub fn schedule_not_found(schedule: &str) -> Self {
+        Self {
+            code: error_codes::SCHEDULE_NOT_FOUND,
+            message: format!("Schedule `{schedule}` not found"),
+            data: None,
+        }
+    }
+
+    /// No one-shot system with the given name is registered.
+    #[must_use]
+    pub fn system_not_found(system: &str) -> Self {
+        Self {
+            code: error_codes::SYSTEM_NOT_FOUND,
+            message: format!("System `{system}` not registered"),
+            data: None,
+        }
+    }
+
+    /// An arbitrary error when running a system.
+    #[must_use]
+    pub fn system_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::SYSTEM_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
     /// An arbitrary internal error.
     #[must_use]
     pub fn internal<E: ToString>(error: E) -> Self {
@@ -878,6 +1024,18 @@ pub mod error_codes {
 
     /// Could not find resource in the world.
     pub const RESOURCE_NOT_PRESENT: i16 = -23502;
+
+    /// Could not find schedule in the world.
+    pub const SCHEDULE_NOT_FOUND: i16 = -23601;
+
+    /// Could not find registered system.
+    pub const SYSTEM_NOT_FOUND: i16 = -23602;
+
+    /// Could not run system.
+    pub const SYSTEM_ERROR: i16 = -23603;
+
+    /// Could not reflect, send or trigger event.
+    pub const EVENT_ERROR: i16 = -23701;
 }
 
 /// The result of a request.
@@ -0,0 +1,662 @@
+//! The BRP transport using JSON-RPC over WebSocket connections.
+//!
+//! Adding the [`RemoteWebSocketPlugin`] to your [`App`] causes Bevy to accept
+//! WebSocket connections (by default, on port 15703) while your app is running.
+//!
+//! Every text or binary message sent by a client is a JSON-RPC request, or a batch of
+//! requests, and every response is sent back as a separate text message. Responses
+//! may arrive in a different order than the requests, so clients should set an `id`
+//! on each request.
+//!
+//! Watching methods such as `bevy/get+watch` keep running after their first response:
+//! every time the watched data changes, the server pushes a new response carrying the
+//! `id` of the watching request. A connection can have any number of watches open at
+//! once. To stop one, send a [`BRP_UNWATCH_METHOD`] request whose `params` contain the
+//! `id` of the watching request:
+//!
+//! ```json
+//! {
+//!     "jsonrpc": "2.0",
+//!     "method": "bevy/unwatch",
+//!     "id": 2,
+//!     "params": { "id": 1 }
+//! }
+//! ```
+//!
+//! All watches of a connection are stopped when it is closed.
+//!
+//! Responses that are waiting to be sent are queued per connection. A client that stops reading
+//! and lets more than [`MAX_QUEUED_RESPONSES`] responses pile up is disconnected.
+
+#![cfg(not(target_family = "wasm"))]
+
+use crate::{error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpSender};
+use anyhow::Result as AnyhowResult;
+use async_channel::{Sender, TrySendError};
+use async_io::Async;
+use async_tungstenite::{tungstenite::Message, WebSocketStream};
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::{resource::Resource, system::Res};
+use bevy_platform::collections::HashMap;
+use bevy_tasks::{
+    futures_lite::{future, AsyncRead, AsyncWrite, StreamExt},
+    IoTaskPool, Task,
+};
+use core::net::{IpAddr, Ipv4Addr};
+use serde::Deserialize;
+use serde_json::Value;
+use std::net::{TcpListener, TcpStream};
+
+/// The default port that Bevy will listen on for WebSocket connections.
+pub const DEFAULT_PORT: u16 = 15703;
+
+/// The default host address that Bevy will use for its WebSocket server.
+pub const DEFAULT_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+/// The number of responses that can be waiting to be sent to a client before it is disconnected.
+pub const MAX_QUEUED_RESPONSES: usize = 256;
+
+/// The transport-level method that stops a watching request on the same connection.
+///
+/// `params`:
+/// - `id`: The `id` of the watching request to stop.
+///
+/// `result`: null.
+pub const BRP_UNWATCH_METHOD: &str = "bevy/unwatch";
+
+/// Add this plugin to your [`App`] to allow remote WebSocket connections to inspect and modify entities.
+/// It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// This BRP transport cannot be used when targeting WASM.
+///
+/// The defaults are:
+/// - [`DEFAULT_ADDR`] : 127.0.0.1.
+/// - [`DEFAULT_PORT`] : 15703.
+pub struct RemoteWebSocketPlugin {
+    /// The address that Bevy will bind to.
+    address: IpAddr,
+    /// The port that Bevy will listen on.
+    port: u16,
+}
+
+impl Default for RemoteWebSocketPlugin {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDR,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+impl Plugin for RemoteWebSocketPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WebSocketHostAddress(self.address))
+            .insert_resource(WebSocketHostPort(self.port))
+            .add_systems(Startup, start_websocket_server);
+    }
+}
+
+impl RemoteWebSocketPlugin {
+    /// Set the IP address that the server will use.
+    #[must_use]
+    pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+    /// Set the remote port that the server will listen on.
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+}
+
+/// A resource containing the IP address that Bevy will host the WebSocket server on.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the IP address that is set during the setup of the [`RemoteWebSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct WebSocketHostAddress(pub IpAddr);
+
+/// A resource containing the port number that Bevy will listen on for WebSocket connections.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the host that is set during the setup of the [`RemoteWebSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct WebSocketHostPort(pub u16);
+
+/// A system that starts up the Bevy Remote Protocol WebSocket server.
+fn start_websocket_server(
+    request_sender: Res<BrpSender>,
+    address: Res<WebSocketHostAddress>,
+    remote_port: Res<WebSocketHostPort>,
+) {
+    IoTaskPool::get()
+        .spawn(server_main(
+            address.0,
+            remote_port.0,
+            request_sender.clone(),
+        ))
+        .detach();
+}
+
+/// The Bevy Remote Protocol WebSocket server main loop.
+async fn server_main(
+    address: IpAddr,
+    port: u16,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    let listener = Async::<TcpListener>::bind((address, port))?;
+    loop {
+        let (client, _) = listener.accept().await?;
+
+        let request_sender = request_sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let _ = handle_client(client, request_sender).await;
+            })
+            .detach();
+    }
+}
+
+async fn handle_client(
+    client: Async<TcpStream>,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    let socket = async_tungstenite::accept_async(client).await?;
+    handle_socket(socket, request_sender).await
+}
+
+/// Something that happened on a WebSocket connection.
+enum SocketEvent {
+    /// The client sent a message, or closed the connection.
+    Incoming(Option<Result<Message, async_tungstenite::tungstenite::Error>>),
+    /// A response is ready to be sent to the client, or `None` if the client fell too far behind.
+    Outgoing(Option<String>),
+}
+
+async fn handle_socket<S>(
+    mut socket: WebSocketStream<S>,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (outgoing_sender, outgoing_receiver) = async_channel::bounded(MAX_QUEUED_RESPONSES);
+    let mut connection = BrpConnection::new(request_sender, outgoing_sender);
+
+    loop {
+        let event = future::or(
+            async { SocketEvent::Incoming(socket.next().await) },
+            async {
+                // The connection holds a sender, so the channel is only closed when it overflows.
+                SocketEvent::Outgoing(outgoing_receiver.recv().await.ok())
+            },
+        )
+        .await;
+
+        match event {
+            SocketEvent::Incoming(Some(Ok(message @ (Message::Text(_) | Message::Binary(_))))) => {
+                connection.handle_message(&message.into_data());
+            }
+            SocketEvent::Incoming(Some(Ok(Message::Close(_))) | None)
+            | SocketEvent::Outgoing(None) => break,
+            // Pings are answered by the WebSocket implementation itself.
+            SocketEvent::Incoming(Some(Ok(_))) => {}
+            SocketEvent::Incoming(Some(Err(error))) => return Err(error.into()),
+            SocketEvent::Outgoing(Some(response)) => socket.send(Message::text(response)).await?,
+        }
+    }
+
+    Ok(())
+}
+
+/// The parameters of a [`BRP_UNWATCH_METHOD`] request.
+#[derive(Debug, Deserialize)]
+struct BrpUnwatchParams {
+    /// The `id` of the watching request to stop.
+    #[serde(default)]
+    id: Value,
+}
+
+/// The state of a single client connection, independent of the underlying socket.
+///
+/// Serialized responses are sent to the `outgoing` channel, in the order they become available.
+/// The channel is closed once it is full, see [`queue_response`].
+struct BrpConnection {
+    request_sender: Sender<BrpMessage>,
+    outgoing: Sender<String>,
+    /// The tasks forwarding the responses of ongoing watching requests, keyed by serialized request `id`.
+    ///
+    /// Dropping a task closes its response channel, which ends the watching request on the world side.
+    /// Tasks of watches that ended on their own are removed whenever a new request comes in.
+    watches: HashMap<String, Task<()>>,
+}
+
+impl BrpConnection {
+    fn new(request_sender: Sender<BrpMessage>, outgoing: Sender<String>) -> Self {
+        Self {
+            request_sender,
+            outgoing,
+            watches: HashMap::default(),
+        }
+    }
+
+    /// Handles a message containing a single request or a batch of requests.
+    fn handle_message(&mut self, message: &[u8]) {
+        match serde_json::from_slice(message) {
+            Ok(BrpBatch::Single(request)) => match parse_request(request) {
+                Ok(request) => self.handle_request(request),
+                Err(response) => self.send(&response),
+            },
+            Ok(BrpBatch::Batch(requests)) => self.handle_batch(requests),
+            Err(err) => self.send(&BrpResponse::new(
+                None,
+                Err(BrpError {
+                    code: error_codes::INVALID_REQUEST,
+                    message: err.to_string(),
+                    data: None,
+                }),
+            )),
+        }
+    }
+
+    fn handle_request(&mut self, request: BrpRequest) {
+        self.watches.retain(|_, task| !task.is_finished());
+
+        if request.method == BRP_UNWATCH_METHOD {
+            let response = self.unwatch(request);
+            self.send(&response);
+            return;
+        }
+
+        let watch = request.method.contains("+watch");
+        let key = watch.then(|| watch_key(&request.id));
+        let size = if watch { 8 } else { 1 };
+        let (result_sender, result_receiver) = async_channel::bounded(size);
+        let request_sender = self.request_sender.clone();
+        let outgoing = self.outgoing.clone();
+
+        let task = IoTaskPool::get().spawn(async move {
+            let message = BrpMessage {
+                method: request.method,
+                params: request.params,
+                sender: result_sender,
+            };
+            if request_sender.send(message).await.is_err() {
+                return;
+            }
+            while let Ok(result) = result_receiver.recv().await {
+                let response = BrpResponse::new(request.id.clone(), result);
+                let Ok(serialized) = serde_json::to_string(&response) else {
+                    continue;
+                };
+                if !queue_response(&outgoing, serialized) || !watch {
+                    break;
+                }
+            }
+        });
+
+        match key {
+            // Replacing an existing watch with the same `id` stops it.
+            Some(key) => {
+                self.watches.insert(key, task);
+            }
+            None => task.detach(),
+        }
+    }
+
+    fn handle_batch(&mut self, requests: Vec<Value>) {
+        let requests = requests
+            .into_iter()
+            .map(|request| {
+                let request = parse_request(request)?;
+                if request.method == BRP_UNWATCH_METHOD {
+                    Err(self.unwatch(request))
+                } else if request.method.contains("+watch") {
+                    Err(BrpResponse::new(
+                        request.id,
+                        Err(BrpError {
+                            code: error_codes::INVALID_REQUEST,
+                            message: "Watching can not be used in batch requests".to_string(),
+                            data: None,
+                        }),
+                    ))
+                } else {
+                    Ok(request)
+                }
+            })
+            .collect::<Vec<_>>();
+        let request_sender = self.request_sender.clone();
+        let outgoing = self.outgoing.clone();
+
+        IoTaskPool::get()
+            .spawn(async move {
+                let mut responses = Vec::with_capacity(requests.len());
+                for request in requests {
+                    let request = match request {
+                        Ok(request) => request,
+                        Err(response) => {
+                            responses.push(response);
+                            continue;
+                        }
+                    };
+                    let (result_sender, result_receiver) = async_channel::bounded(1);
+                    let message = BrpMessage {
+                        method: request.method,
+                        params: request.params,
+                        sender: result_sender,
+                    };
+                    if request_sender.send(message).await.is_err() {
+                        return;
+                    }
+                    let Ok(result) = result_receiver.recv().await else {
+                        return;
+                    };
+                    responses.push(BrpResponse::new(request.id, result));
+                }
+                if let Ok(serialized) = serde_json::to_string(&responses) {
+                    queue_response(&outgoing, serialized);
+                }
+            })
+            .detach();
+    }
+
+    fn unwatch(&mut self, request: BrpRequest) -> BrpResponse {
+        let params: BrpUnwatchParams =
+            match serde_json::from_value(request.params.unwrap_or_default()) {
+                Ok(params) => params,
+                Err(err) => {
+                    return BrpResponse::new(
+                        request.id,
+                        Err(BrpError {
+                            code: error_codes::INVALID_PARAMS,
+                            message: err.to_string(),
+                            data: None,
+                        }),
+                    );
+                }
+            };
+
+        let result = match self.watches.remove(&watch_key(&Some(params.id.clone()))) {
+            Some(_) => Ok(Value::Null),
+            None => Err(BrpError {
+                code: error_codes::INVALID_PARAMS,
+                message: format!("No watching request with id {} is running", params.id),
+                data: None,
+            }),
+        };
+        BrpResponse::new(request.id, result)
+    }
+
+    fn send(&self, response: &BrpResponse) {
+        if let Ok(serialized) = serde_json::to_string(response) {
+            queue_response(&self.outgoing, serialized);
+        }
+    }
+}
+
+/// Queues a serialized response to be sent to the client.
+///
+/// If the queue is full, the client isn't reading its responses fast enough. The queue is closed
+/// instead of growing without bound, which disconnects the client.
+///
+/// Returns `false` if the response was not queued.
+fn queue_response(outgoing: &Sender<String>, response: String) -> bool {
+    match outgoing.try_send(response) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_)) => {
+            outgoing.close();
+            false
+        }
+        Err(TrySendError::Closed(_)) => false,
+    }
+}
+
+/// Parses a single request, returning the error response to send if it is invalid.
+fn parse_request(request: Value) -> Result<BrpRequest, BrpResponse> {
+    // Reach in and get the request ID early so that we can report it even when parsing fails.
+    let id = request.as_object().and_then(|map| map.get("id")).cloned();
+
+    let request: BrpRequest = serde_json::from_value(request).map_err(|err| {
+        BrpResponse::new(
+            id.clone(),
+            Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: err.to_string(),
+                data: None,
+            }),
+        )
+    })?;
+
+    if request.jsonrpc != "2.0" {
+        return Err(BrpResponse::new(
+            id,
+            Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: String::from("JSON-RPC request requires `\"jsonrpc\": \"2.0\"`"),
+                data: None,
+            }),
+        ));
+    }
+
+    Ok(request)
+}
+
+/// The key of a watching request in [`BrpConnection::watches`].
+fn watch_key(id: &Option<Value>) -> String {
+    id.as_ref().unwrap_or(&Value::Null).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_channel::Receiver;
+    use bevy_tasks::{block_on, TaskPool};
+    use core::time::Duration;
+    use serde_json::json;
+
+    fn connection() -> (BrpConnection, Receiver<BrpMessage>, Receiver<String>) {
+        connection_with_capacity(MAX_QUEUED_RESPONSES)
+    }
+
+    fn connection_with_capacity(
+        capacity: usize,
+    ) -> (BrpConnection, Receiver<BrpMessage>, Receiver<String>) {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let (request_sender, request_receiver) = async_channel::unbounded();
+        let (outgoing_sender, outgoing_receiver) = async_channel::bounded(capacity);
+        (
+            BrpConnection::new(request_sender, outgoing_sender),
+            request_receiver,
+            outgoing_receiver,
+        )
+    }
+
+    fn recv_json(receiver: &Receiver<String>) -> Value {
+        serde_json::from_str(&block_on(receiver.recv()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn single_request() {
+        let (mut connection, requests, outgoing) = connection();
+        connection.handle_message(
+            br#"{ "jsonrpc": "2.0", "id": 1, "method": "test/echo", "params": [5] }"#,
+        );
+
+        let message = block_on(requests.recv()).unwrap();
+        assert_eq!(message.method, "test/echo");
+        message
+            .sender
+            .force_send(Ok(message.params.unwrap()))
+            .unwrap();
+
+        assert_eq!(
+            recv_json(&outgoing),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": [5] })
+        );
+    }
+
+    #[test]
+    fn batch_request() {
+        let (mut connection, requests, outgoing) = connection();
+        connection.handle_message(
+            br#"[
+                { "jsonrpc": "2.0", "id": 1, "method": "test/echo", "params": 1 },
+                { "jsonrpc": "2.0", "id": 2, "method": "test/get+watch" },
+                { "jsonrpc": "1.0", "id": 3, "method": "test/echo" }
+            ]"#,
+        );
+
+        let message = block_on(requests.recv()).unwrap();
+        message
+            .sender
+            .force_send(Ok(message.params.unwrap()))
+            .unwrap();
+
+        let responses = recv_json(&outgoing);
+        assert_eq!(
+            responses[0],
+            json!({ "jsonrpc": "2.0", "id": 1, "result": 1 })
+        );
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["error"]["code"], error_codes::INVALID_REQUEST);
+        assert_eq!(responses[2]["id"], 3);
+        assert_eq!(responses[2]["error"]["code"], error_codes::INVALID_REQUEST);
+        assert!(requests.is_empty());
+    }
+
+    #[test]
+    fn watch_and_unwatch() {
+        let (mut connection, requests, outgoing) = connection();
+        connection
+            .handle_message(br#"{ "jsonrpc": "2.0", "id": "w", "method": "test/get+watch" }"#);
+        let message = block_on(requests.recv()).unwrap();
+
+        // Every change is pushed with the id of the watching request.
+        for value in 0..3 {
+            message.sender.force_send(Ok(json!(value))).unwrap();
+            assert_eq!(
+                recv_json(&outgoing),
+                json!({ "jsonrpc": "2.0", "id": "w", "result": value })
+            );
+        }
+
+        connection.handle_message(
+            br#"{ "jsonrpc": "2.0", "id": 1, "method": "bevy/unwatch", "params": { "id": "w" } }"#,
+        );
+        assert_eq!(
+            recv_json(&outgoing),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": null })
+        );
+
+        // Stopping the watch closes its response channel, which ends the request in the world.
+        for _ in 0..100 {
+            if message.sender.is_closed() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(message.sender.is_closed());
+
+        connection.handle_message(
+            br#"{ "jsonrpc": "2.0", "id": 2, "method": "bevy/unwatch", "params": { "id": "w" } }"#,
+        );
+        let response = recv_json(&outgoing);
+        assert_eq!(response["id"], 2);
+        assert_eq!(response["error"]["code"], error_codes::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn invalid_request() {
+        let (mut connection, requests, outgoing) = connection();
+        connection.handle_message(b"not json");
+        assert_eq!(
+            recv_json(&outgoing)["error"]["code"],
+            error_codes::INVALID_REQUEST
+        );
+
+        connection.handle_message(br#"{ "jsonrpc": "2.0", "id": 7 }"#);
+        let response = recv_json(&outgoing);
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["error"]["code"], error_codes::INVALID_REQUEST);
+        assert!(requests.is_empty());
+    }
+
+    #[test]
+    fn finished_watches_are_removed() {
+        let (mut connection, requests, _outgoing) = connection();
+        connection
+            .handle_message(br#"{ "jsonrpc": "2.0", "id": "w", "method": "test/get+watch" }"#);
+        let message = block_on(requests.recv()).unwrap();
+        assert_eq!(connection.watches.len(), 1);
+
+        // The world side ends the watch by dropping its sender.
+        drop(message);
+        for _ in 0..100 {
+            if connection.watches["\"w\""].is_finished() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        connection.handle_message(br#"{ "jsonrpc": "2.0", "id": 1, "method": "test/echo" }"#);
+        assert!(connection.watches.is_empty());
+    }
+
+    #[test]
+    fn slow_clients_are_disconnected() {
+        let (mut connection, _requests, outgoing) = connection_with_capacity(2);
+        for _ in 0..3 {
+            connection.handle_message(b"not json");
+        }
+
+        // The queued responses are still delivered, then the connection ends.
+        assert!(block_on(outgoing.recv()).is_ok());
+        assert!(block_on(outgoing.recv()).is_ok());
+        assert!(block_on(outgoing.recv()).is_err());
+    }
+
+    #[test]
+    fn request_over_socket() {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let (request_sender, requests) = async_channel::unbounded();
+        let listener = Async::<TcpListener>::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.get_ref().local_addr().unwrap();
+        let server = IoTaskPool::get().spawn(async move {
+            let (client, _) = listener.accept().await.unwrap();
+            handle_client(client, request_sender).await
+        });
+
+        block_on(async {
+            let stream = Async::<TcpStream>::connect(address).await.unwrap();
+            let (mut socket, _) =
+                async_tungstenite::client_async(format!("ws://{address}"), stream)
+                    .await
+                    .unwrap();
+            socket
+                .send(Message::text(
+                    r#"{ "jsonrpc": "2.0", "id": 1, "method": "test/echo", "params": "hi" }"#,
+                ))
+                .await
+                .unwrap();
+
+            let message = requests.recv().await.unwrap();
+            assert_eq!(message.method, "test/echo");
+            message
+                .sender
+                .force_send(Ok(message.params.unwrap()))
+                .unwrap();
+
+            let response = socket.next().await.unwrap().unwrap();
+            let response: Value = serde_json::from_slice(&response.into_data()).unwrap();
+            assert_eq!(
+                response,
+                json!({ "jsonrpc": "2.0", "id": 1, "result": "hi" })
+            );
+
+            socket.close(None).await.unwrap();
+            assert!(server.await.is_ok());
+        });
+    }
+}
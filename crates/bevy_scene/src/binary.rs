@@ -0,0 +1,693 @@
+//! A compact binary format for Bevy scenes (`.scn.bin`).
+//!
+//! Unlike the RON format produced by [`DynamicScene::serialize`], component and resource values
+//! are not tagged with their type path. Instead, the file starts with a table of all type paths
+//! used in the scene, and every value refers to its type by index into that table. Type paths are
+//! resolved against the [`TypeRegistry`] when loading, so the format does not depend on the order
+//! in which types were registered.
+//!
+//! The layout of a binary scene file is:
+//! - the magic bytes [`BINARY_SCENE_MAGIC`],
+//! - the format version [`BINARY_SCENE_VERSION`] as a single byte,
+//! - the scene itself, encoded by [`BinarySceneSerializer`] with [`bincode`]'s standard configuration.
+//!
+//! The format is not streamed: a scene is always encoded into, and decoded from, a single in-memory
+//! buffer holding the whole file. It is faster to load than RON because values are not tagged with
+//! their type path and are decoded without parsing text.
+
+use crate::{DynamicEntity, DynamicScene};
+use bevy_asset::{
+    io::{Reader, Writer},
+    saver::{AssetSaver, SavedAsset},
+    AssetLoader, AsyncWriteExt, LoadContext,
+};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    PartialReflect, ReflectFromReflect, TypeRegistration, TypeRegistry, TypeRegistryArc,
+};
+use core::fmt::Formatter;
+use serde::{
+    de::{DeserializeSeed, Error, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeTuple},
+    Deserializer, Serialize, Serializer,
+};
+use thiserror::Error;
+
+/// The magic bytes at the start of every binary scene file.
+pub const BINARY_SCENE_MAGIC: [u8; 4] = *b"BSCN";
+/// The version of the binary scene format written by this crate.
+pub const BINARY_SCENE_VERSION: u8 = 1;
+
+/// The largest number of elements preallocated for a sequence, as its length is read from the
+/// file and a corrupted or malicious length must not cause a huge allocation.
+const MAX_PREALLOCATED_LEN: usize = 4096;
+
+/// Possible errors that can be produced when reading or writing binary scenes.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum BinarySceneError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read or write the scene file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The data does not start with [`BINARY_SCENE_MAGIC`].
+    #[error("Not a binary scene file")]
+    WrongFileType,
+    /// The data was written with an unsupported version of the format.
+    #[error("Unsupported binary scene version {found}, expected {BINARY_SCENE_VERSION}")]
+    WrongVersion {
+        /// The version found in the data.
+        found: u8,
+    },
+    /// A [bincode encode error](bincode::error::EncodeError)
+    #[error("Could not encode binary scene: {0}")]
+    Encode(#[from] bincode::error::EncodeError),
+    /// A [bincode decode error](bincode::error::DecodeError)
+    #[error("Could not decode binary scene: {0}")]
+    Decode(#[from] bincode::error::DecodeError),
+}
+
+/// Serializes a [`DynamicScene`] into the binary scene format, including the header, returning the
+/// whole file as one buffer.
+///
+/// All types used by the scene must be registered in `registry`.
+pub fn serialize_binary(
+    scene: &DynamicScene,
+    registry: &TypeRegistry,
+) -> Result<Vec<u8>, BinarySceneError> {
+    let mut bytes = Vec::from(BINARY_SCENE_MAGIC);
+    bytes.push(BINARY_SCENE_VERSION);
+    bincode::serde::encode_into_std_write(
+        BinarySceneSerializer::new(scene, registry),
+        &mut bytes,
+        bincode::config::standard(),
+    )?;
+    Ok(bytes)
+}
+
+/// Deserializes a [`DynamicScene`] written by [`serialize_binary`].
+///
+/// All types used by the scene must be registered in `registry`.
+pub fn deserialize_binary(
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<DynamicScene, BinarySceneError> {
+    let Some((magic, bytes)) = bytes.split_first_chunk::<4>() else {
+        return Err(BinarySceneError::WrongFileType);
+    };
+    if *magic != BINARY_SCENE_MAGIC {
+        return Err(BinarySceneError::WrongFileType);
+    }
+    let Some((&version, bytes)) = bytes.split_first() else {
+        return Err(BinarySceneError::WrongFileType);
+    };
+    if version != BINARY_SCENE_VERSION {
+        return Err(BinarySceneError::WrongVersion { found: version });
+    }
+    let (scene, _) = bincode::serde::seed_decode_from_slice(
+        BinarySceneDeserializer {
+            type_registry: registry,
+        },
+        bytes,
+        bincode::config::standard(),
+    )?;
+    Ok(scene)
+}
+
+/// Serializer for a [`DynamicScene`] in the binary scene format, without the magic bytes and version.
+///
+/// The scene is serialized as a tuple of the type path table, the resources and the entities.
+/// This layout is meant for non-self-describing formats. For RON, use
+/// [`SceneSerializer`](crate::serde::SceneSerializer) instead.
+pub struct BinarySceneSerializer<'a> {
+    /// The scene to serialize.
+    pub scene: &'a DynamicScene,
+    /// The type registry containing the types present in the scene.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> BinarySceneSerializer<'a> {
+    /// Create a new serializer from a [`DynamicScene`] and an associated [`TypeRegistry`].
+    pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistry) -> Self {
+        BinarySceneSerializer { scene, registry }
+    }
+}
+
+impl<'a> Serialize for BinarySceneSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut type_paths = self
+            .scene
+            .resources
+            .iter()
+            .chain(
+                self.scene
+                    .entities
+                    .iter()
+                    .flat_map(|entity| &entity.components),
+            )
+            .map(|value| value.get_represented_type_info().unwrap().type_path())
+            .collect::<Vec<_>>();
+        type_paths.sort_unstable();
+        type_paths.dedup();
+        let type_indices = type_paths
+            .iter()
+            .enumerate()
+            .map(|(index, type_path)| (*type_path, index as u32))
+            .collect::<HashMap<_, _>>();
+
+        let mut state = serializer.serialize_tuple(3)?;
+        state.serialize_element(&type_paths)?;
+        state.serialize_element(&BinaryValuesSerializer {
+            values: &self.scene.resources,
+            type_indices: &type_indices,
+            registry: self.registry,
+        })?;
+        state.serialize_element(&BinaryEntitiesSerializer {
+            entities: &self.scene.entities,
+            type_indices: &type_indices,
+            registry: self.registry,
+        })?;
+        state.end()
+    }
+}
+
+struct BinaryEntitiesSerializer<'a> {
+    entities: &'a [DynamicEntity],
+    type_indices: &'a HashMap<&'a str, u32>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for BinaryEntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities {
+            state.serialize_element(&(
+                entity.entity,
+                BinaryValuesSerializer {
+                    values: &entity.components,
+                    type_indices: self.type_indices,
+                    registry: self.registry,
+                },
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// Serializes values as a sequence of type index and value pairs.
+struct BinaryValuesSerializer<'a> {
+    values: &'a [Box<dyn PartialReflect>],
+    type_indices: &'a HashMap<&'a str, u32>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for BinaryValuesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.values.len()))?;
+        for value in self.values {
+            let type_path = value.get_represented_type_info().unwrap().type_path();
+            state.serialize_element(&(
+                self.type_indices[type_path],
+                TypedReflectSerializer::new(value.as_partial_reflect(), self.registry),
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// Handles deserialization of a [`DynamicScene`] serialized with [`BinarySceneSerializer`].
+pub struct BinarySceneDeserializer<'a> {
+    /// Type registry in which the components and resources types used in the scene to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinarySceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(
+            3,
+            BinarySceneVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct BinarySceneVisitor<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BinarySceneVisitor<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("binary scene")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let type_paths = seq
+            .next_element::<Vec<String>>()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let registrations = type_paths
+            .iter()
+            .map(|type_path| {
+                self.type_registry
+                    .get_with_type_path(type_path)
+                    .ok_or_else(|| {
+                        Error::custom(format_args!("no registration found for `{type_path}`"))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let resources = seq
+            .next_element_seed(BinaryValuesDeserializer {
+                registrations: &registrations,
+                registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        let entities = seq
+            .next_element_seed(BinaryEntitiesDeserializer {
+                registrations: &registrations,
+                registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::invalid_length(2, &self))?;
+
+        Ok(DynamicScene {
+            resources,
+            entities,
+        })
+    }
+}
+
+struct BinaryEntitiesDeserializer<'a> {
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("sequence of entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entities =
+            Vec::with_capacity(seq.size_hint().unwrap_or(0).min(MAX_PREALLOCATED_LEN));
+        while let Some(entity) = seq.next_element_seed(BinaryEntityDeserializer {
+            registrations: self.registrations,
+            registry: self.registry,
+        })? {
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+}
+
+struct BinaryEntityDeserializer<'a> {
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("entity and its components")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entity = seq
+            .next_element::<Entity>()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let components = seq
+            .next_element_seed(BinaryValuesDeserializer {
+                registrations: self.registrations,
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        Ok(DynamicEntity { entity, components })
+    }
+}
+
+struct BinaryValuesDeserializer<'a> {
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryValuesDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryValuesDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("sequence of reflect values")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut added = <HashSet<_>>::default();
+        let mut entries =
+            Vec::with_capacity(seq.size_hint().unwrap_or(0).min(MAX_PREALLOCATED_LEN));
+        while let Some(value) = seq.next_element_seed(BinaryValueDeserializer {
+            registrations: self.registrations,
+            registry: self.registry,
+        })? {
+            let type_id = value.get_represented_type_info().unwrap().type_id();
+            if !added.insert(type_id) {
+                return Err(Error::custom(format_args!(
+                    "duplicate reflect type: `{}`",
+                    value.reflect_type_path(),
+                )));
+            }
+            entries.push(value);
+        }
+        Ok(entries)
+    }
+}
+
+struct BinaryValueDeserializer<'a> {
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryValueDeserializer<'a> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryValueDeserializer<'a> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("type index and reflect value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let index = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let registration = *self
+            .registrations
+            .get(index as usize)
+            .ok_or_else(|| Error::custom(format_args!("type index {index} is out of bounds")))?;
+        let value = seq
+            .next_element_seed(TypedReflectDeserializer::new(registration, self.registry))?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+
+        // Attempt to convert using FromReflect.
+        Ok(registration
+            .data::<ReflectFromReflect>()
+            .and_then(|fr| fr.from_reflect(value.as_partial_reflect()))
+            .map(PartialReflect::into_partial_reflect)
+            .unwrap_or(value))
+    }
+}
+
+/// Asset loader for a Bevy dynamic scene in the binary scene format (`.scn.bin`).
+///
+/// The loader handles assets serialized with [`serialize_binary`] or saved by [`BinarySceneSaver`].
+/// The whole file is read into memory before it is decoded.
+#[derive(Debug)]
+pub struct BinarySceneLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for BinarySceneLoader {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type Error = BinarySceneError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        deserialize_binary(&bytes, &self.type_registry.read())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn.bin"]
+    }
+}
+
+/// An [`AssetSaver`] that writes [`DynamicScene`]s in the binary scene format, e.g. to convert
+/// `.scn.ron` scenes during asset processing.
+#[derive(Debug)]
+pub struct BinarySceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneSaver {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetSaver for BinarySceneSaver {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type OutputLoader = BinarySceneLoader;
+    type Error = BinarySceneError;
+
+    async fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, DynamicScene>,
+        _settings: &(),
+    ) -> Result<(), BinarySceneError> {
+        let bytes = serialize_binary(asset.get(), &self.type_registry.read())?;
+        writer.write_all(&bytes).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        deserialize_binary, serialize_binary, BinarySceneError, BINARY_SCENE_MAGIC,
+        BINARY_SCENE_VERSION,
+    };
+    use crate::{DynamicScene, DynamicSceneBuilder};
+    use bevy_ecs::{
+        entity::Entity,
+        prelude::{Component, ReflectComponent, ReflectResource, Resource, World},
+        reflect::AppTypeRegistry,
+    };
+    use bevy_reflect::{Reflect, TypeRegistry};
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Foo(i32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Bar {
+        name: String,
+        values: Vec<f32>,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    enum MyEnum {
+        #[default]
+        Unit,
+        Tuple(u64),
+    }
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct MyEntityRef(#[entities] Entity);
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct MyResource {
+        foo: i32,
+    }
+
+    fn register(registry: &mut TypeRegistry) {
+        registry.register::<Foo>();
+        registry.register::<Bar>();
+        registry.register::<MyEnum>();
+        registry.register::<MyEntityRef>();
+        registry.register::<MyResource>();
+    }
+
+    fn create_scene() -> (World, DynamicScene) {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        register(&mut registry.write());
+        world.insert_resource(registry);
+
+        let a = world.spawn(Foo(123)).id();
+        world.spawn((
+            Foo(-1),
+            Bar {
+                name: "bar".to_string(),
+                values: vec![1.0, 2.5],
+            },
+            MyEnum::Tuple(42),
+            MyEntityRef(a),
+        ));
+        world.spawn(MyEnum::Unit);
+        world.insert_resource(MyResource { foo: 7 });
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities(world.iter_entities().map(|entity| entity.id()))
+            .extract_resources()
+            .build();
+        (world, scene)
+    }
+
+    #[test]
+    fn should_roundtrip_with_same_ron_output() {
+        let (world, scene) = create_scene();
+        let registry = world.resource::<AppTypeRegistry>().read();
+
+        let bytes = serialize_binary(&scene, &registry).unwrap();
+        let deserialized = deserialize_binary(&bytes, &registry).unwrap();
+
+        let expected = scene.serialize(&registry).unwrap();
+        assert_eq!(expected, deserialized.serialize(&registry).unwrap());
+        assert!(bytes.len() < expected.len());
+    }
+
+    #[test]
+    fn should_roundtrip_with_reordered_registry() {
+        let (world, scene) = create_scene();
+        let bytes = serialize_binary(&scene, &world.resource::<AppTypeRegistry>().read()).unwrap();
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<MyResource>();
+        registry.register::<u128>();
+        registry.register::<MyEntityRef>();
+        registry.register::<MyEnum>();
+        registry.register::<Bar>();
+        registry.register::<Foo>();
+
+        let deserialized = deserialize_binary(&bytes, &registry).unwrap();
+        assert_eq!(
+            scene.serialize(&registry).unwrap(),
+            deserialized.serialize(&registry).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_fail_on_unregistered_type() {
+        let (world, scene) = create_scene();
+        let bytes = serialize_binary(&scene, &world.resource::<AppTypeRegistry>().read()).unwrap();
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Foo>();
+        registry.register::<MyEnum>();
+        registry.register::<MyEntityRef>();
+        registry.register::<MyResource>();
+
+        let Err(error) = deserialize_binary(&bytes, &registry) else {
+            panic!("deserializing an unregistered type should fail");
+        };
+        assert!(error
+            .to_string()
+            .contains("no registration found for `bevy_scene::binary::tests::Bar`"));
+    }
+
+    #[test]
+    fn should_fail_on_wrong_header() {
+        let registry = TypeRegistry::new();
+        assert!(matches!(
+            deserialize_binary(b"(resources: {}, entities: {})", &registry),
+            Err(BinarySceneError::WrongFileType)
+        ));
+
+        let mut bytes = Vec::from(BINARY_SCENE_MAGIC);
+        bytes.push(BINARY_SCENE_VERSION + 1);
+        assert!(matches!(
+            deserialize_binary(&bytes, &registry),
+            Err(BinarySceneError::WrongVersion { found }) if found == BINARY_SCENE_VERSION + 1
+        ));
+    }
+}
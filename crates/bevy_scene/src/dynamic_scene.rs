@@ -199,6 +199,20 @@ impl DynamicScene {
     pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into the compact binary scene format (`.scn.bin`).
+    ///
+    /// To deserialize the scene, use the [`BinarySceneLoader`] or [`deserialize_binary`].
+    ///
+    /// [`BinarySceneLoader`]: crate::BinarySceneLoader
+    /// [`deserialize_binary`]: crate::binary::deserialize_binary
+    #[cfg(feature = "serialize")]
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistry,
+    ) -> Result<Vec<u8>, crate::binary::BinarySceneError> {
+        crate::binary::serialize_binary(self, registry)
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -21,6 +21,8 @@ mod scene_filter;
 mod scene_loader;
 mod scene_spawner;
 
+#[cfg(feature = "serialize")]
+pub mod binary;
 #[cfg(feature = "serialize")]
 pub mod serde;
 
@@ -35,6 +37,9 @@ pub use scene_filter::*;
 pub use scene_loader::*;
 pub use scene_spawner::*;
 
+#[cfg(feature = "serialize")]
+pub use binary::{BinarySceneLoader, BinarySceneSaver};
+
 /// The scene prelude.
 ///
 /// This includes the most common types in this crate, re-exported for your convenience.
@@ -61,6 +66,7 @@ impl Plugin for ScenePlugin {
         app.init_asset::<DynamicScene>()
             .init_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
             .init_resource::<SceneSpawner>()
             .register_type::<SceneRoot>()
             .register_type::<DynamicSceneRoot>()
@@ -0,0 +1,107 @@
+//! Maps keys and gamepad input to actions for two local players, and rebinds them at runtime.
+//!
+//! The first player moves with `A` and `D` and jumps with `W`, the second one moves with the arrow
+//! keys and jumps with the up arrow. Each connected gamepad is given to a player.
+//! Press `Enter` then any key or button to change the jump binding of the first player.
+
+use bevy::{input::action::InputSources, prelude::*};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, InputActionPlugin::<PlayerAction>::default()))
+        .add_systems(Startup, spawn_players)
+        .add_systems(Update, (assign_gamepads, print_actions, rebind_jump))
+        .run();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PlayerAction {
+    Move,
+    Jump,
+}
+
+#[derive(Component)]
+struct Player(usize);
+
+fn spawn_players(mut commands: Commands) {
+    let gamepad_bindings = ActionMap::default()
+        .with(PlayerAction::Move, GamepadAxis::LeftStickX)
+        .with(PlayerAction::Jump, GamepadButton::South);
+
+    commands.spawn((
+        Player(1),
+        gamepad_bindings
+            .clone()
+            .with(
+                PlayerAction::Move,
+                InputBinding::virtual_axis(KeyCode::KeyA, KeyCode::KeyD),
+            )
+            .with(PlayerAction::Jump, KeyCode::KeyW),
+    ));
+    commands.spawn((
+        Player(2),
+        gamepad_bindings
+            .with(
+                PlayerAction::Move,
+                InputBinding::virtual_axis(KeyCode::ArrowLeft, KeyCode::ArrowRight),
+            )
+            .with(PlayerAction::Jump, KeyCode::ArrowUp),
+    ));
+}
+
+/// Gives each new gamepad to the first player without one.
+fn assign_gamepads(
+    gamepads: Query<Entity, Added<Gamepad>>,
+    mut players: Query<(&Player, &mut ActionMap<PlayerAction>)>,
+) {
+    for gamepad in &gamepads {
+        let mut players = players.iter_mut().collect::<Vec<_>>();
+        players.sort_by_key(|(player, _)| player.0);
+        if let Some((player, mut map)) =
+            players.into_iter().find(|(_, map)| map.gamepad().is_none())
+        {
+            info!("Player {} uses gamepad {}", player.0, gamepad);
+            map.set_gamepad(Some(gamepad));
+        }
+    }
+}
+
+fn print_actions(players: Query<(&Player, &ActionState<PlayerAction>)>) {
+    for (player, actions) in &players {
+        if actions.just_pressed(PlayerAction::Jump) {
+            info!("Player {} jumped", player.0);
+        }
+        if actions.pressed(PlayerAction::Move) {
+            info!(
+                "Player {} moves at {}",
+                player.0,
+                actions.value(PlayerAction::Move)
+            );
+        }
+    }
+}
+
+/// Waits for the next input after `Enter` is pressed, and binds it to the first player's jump.
+fn rebind_jump(
+    mut waiting: Local<bool>,
+    keys: Res<ButtonInput<KeyCode>>,
+    sources: InputSources,
+    mut players: Query<(&Player, &mut ActionMap<PlayerAction>)>,
+) {
+    if !*waiting {
+        if keys.just_pressed(KeyCode::Enter) {
+            info!("Press any key or button to make player 1 jump with it");
+            *waiting = true;
+        }
+        return;
+    }
+
+    let Some((_, mut map)) = players.iter_mut().find(|(player, _)| player.0 == 1) else {
+        return;
+    };
+    if let Some(binding) = sources.just_pressed_binding(map.gamepad()) {
+        info!("Player 1 now jumps with {:?}", binding);
+        map.rebind(PlayerAction::Jump, binding);
+        *waiting = false;
+    }
+}